@@ -2,11 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::generator::rules::RuleType;
-use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, ValueEnum};
-use std::path::PathBuf;
+use crate::packer::CompressionLevel;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
 
 /// Supported LLM provider names for CLI validation.
-const SUPPORTED_PROVIDERS: [&str; 4] = ["anthropic", "openai", "ollama", "openrouter"];
+const SUPPORTED_PROVIDERS: [&str; 8] = [
+    "anthropic",
+    "openai",
+    "openai-compatible",
+    "ollama",
+    "openrouter",
+    "xai",
+    "groq",
+    "gemini",
+];
 
 /// Supported conflict resolution strategies for CLI validation.
 const SUPPORTED_CONFLICT_STRATEGIES: [&str; 4] = ["prompt", "overwrite", "skip", "smart-merge"];
@@ -44,6 +54,8 @@ pub struct ArgsPresence {
     pub rule_type: bool,
     /// Whether --compress was explicitly provided
     pub compress: bool,
+    /// Whether --compress-level was explicitly provided
+    pub compress_level: bool,
     /// Whether --chunk-size was explicitly provided
     pub chunk_size: bool,
     /// Whether --no-confirm was explicitly provided
@@ -66,6 +78,7 @@ pub fn from_matches(matches: &ArgMatches) -> Self {
             format: is_from_cli(matches, "format"),
             rule_type: is_from_cli(matches, "rule_type"),
             compress: is_from_cli(matches, "compress"),
+            compress_level: is_from_cli(matches, "compress_level"),
             chunk_size: is_from_cli(matches, "chunk_size"),
             no_confirm: is_from_cli(matches, "no_confirm"),
             retry_on_validation_failure: is_from_cli(matches, "retry_on_validation_failure"),
@@ -90,14 +103,18 @@ fn is_from_cli(matches: &ArgMatches, name: &str) -> bool {
 #[command(about = "Make your codebase ruley - generate AI IDE rules from codebases")]
 #[command(version)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to repository (local path or remote URL)
     #[arg(default_value = ".")]
     pub path: PathBuf,
 
-    /// LLM provider (anthropic, openai, ollama, openrouter)
+    /// LLM provider (anthropic, openai, openai-compatible, ollama, openrouter, xai, groq, gemini)
     #[arg(
         short,
         long,
+        global = true,
         default_value = "anthropic",
         env = "RULEY_PROVIDER",
         value_parser = clap::builder::PossibleValuesParser::new(SUPPORTED_PROVIDERS)
@@ -105,13 +122,25 @@ pub struct Args {
     pub provider: String,
 
     /// Model to use
-    #[arg(short, long, env = "RULEY_MODEL")]
+    #[arg(short, long, global = true, env = "RULEY_MODEL")]
     pub model: Option<String>,
 
     /// Output file path
     #[arg(short, long, env = "RULEY_OUTPUT")]
     pub output: Option<PathBuf>,
 
+    /// Directory to write rule files into (required when PATH is a remote URL)
+    #[arg(long, env = "RULEY_OUTPUT_DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Branch, tag or commit to check out when cloning a remote URL
+    #[arg(long = "ref", value_name = "REF", env = "RULEY_REF")]
+    pub git_ref: Option<String>,
+
+    /// Commit history depth when cloning a remote URL (0 = full history)
+    #[arg(long, default_value_t = 1, env = "RULEY_DEPTH")]
+    pub depth: u32,
+
     /// Path to existing repomix file for input
     #[arg(long, env = "RULEY_REPOMIX_FILE")]
     pub repomix_file: Option<PathBuf>,
@@ -135,33 +164,83 @@ pub struct Args {
     pub rule_type: RuleType,
 
     /// Config file path
-    #[arg(short, long, default_value = "ruley.toml", env = "RULEY_CONFIG")]
+    #[arg(
+        short,
+        long,
+        global = true,
+        default_value = "ruley.toml",
+        env = "RULEY_CONFIG"
+    )]
     pub config: PathBuf,
 
     /// Include only matching files (repeatable)
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub include: Vec<String>,
 
     /// Exclude matching files (repeatable)
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub exclude: Vec<String>,
 
-    /// Enable tree-sitter compression
-    #[arg(long, env = "RULEY_COMPRESS")]
+    /// Enable tree-sitter compression (same as --compress-level signatures)
+    #[arg(long, global = true, env = "RULEY_COMPRESS")]
     pub compress: bool,
 
+    /// Compression level (takes precedence over --compress)
+    #[arg(long, global = true, value_enum, env = "RULEY_COMPRESS_LEVEL")]
+    pub compress_level: Option<CompressionLevel>,
+
     /// Max tokens per LLM chunk
     #[arg(long, default_value_t = 100000, env = "RULEY_CHUNK_SIZE")]
     pub chunk_size: usize,
 
+    /// Analyze only the most informative files that fit in this many tokens
+    #[arg(long, global = true, env = "RULEY_TOKEN_BUDGET")]
+    pub token_budget: Option<usize>,
+
+    /// Analyze every file, chunking if needed, even when a token budget is set
+    #[arg(long, env = "RULEY_FULL_COVERAGE")]
+    pub full_coverage: bool,
+
+    /// Detect workspace packages and generate rules for each one
+    #[arg(long, env = "RULEY_WORKSPACES")]
+    pub workspaces: bool,
+
     /// Skip cost confirmation prompt
-    #[arg(long, env = "RULEY_NO_CONFIRM")]
+    #[arg(long, global = true, env = "RULEY_NO_CONFIRM")]
     pub no_confirm: bool,
 
     /// Show what would be processed without calling LLM
     #[arg(long, env = "RULEY_DRY_RUN")]
     pub dry_run: bool,
 
+    /// Show which directories and files the tokens go to, without calling LLM
+    #[arg(long, env = "RULEY_TOKEN_REPORT")]
+    pub token_report: bool,
+
+    /// Also write the token report to this file as JSON (implies --token-report)
+    #[arg(long, value_name = "PATH")]
+    pub token_report_json: Option<PathBuf>,
+
+    /// Reuse chunk analyses cached in .ruley/ by an interrupted run
+    #[arg(long, env = "RULEY_RESUME")]
+    pub resume: bool,
+
+    /// Analyze only files changed since the last run and update the existing rules
+    #[arg(long, env = "RULEY_INCREMENTAL")]
+    pub incremental: bool,
+
+    /// Check whether the rules are out of date without calling LLM (exits 3 if stale)
+    #[arg(long, env = "RULEY_CHECK")]
+    pub check: bool,
+
+    /// Bypass the LLM response cache for this run
+    #[arg(long, global = true, env = "RULEY_NO_CACHE")]
+    pub no_cache: bool,
+
+    /// Abort instead of redacting when file contents contain secrets
+    #[arg(long, env = "RULEY_FAIL_ON_SECRETS")]
+    pub fail_on_secrets: bool,
+
     /// Automatically retry with LLM fix when validation fails
     #[arg(long)]
     pub retry_on_validation_failure: bool,
@@ -171,7 +250,7 @@ pub struct Args {
     pub no_deconflict: bool,
 
     /// Disable all semantic validation checks
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub no_semantic_validation: bool,
 
     /// Conflict resolution strategy when output files exist (prompt, overwrite, skip, smart-merge)
@@ -183,14 +262,65 @@ pub struct Args {
     pub on_conflict: Option<String>,
 
     /// Increase verbosity (-v, -vv, -vvv)
-    #[arg(short, action = clap::ArgAction::Count)]
+    #[arg(short, global = true, action = clap::ArgAction::Count)]
     pub verbose: u8,
 
     /// Suppress non-essential output
-    #[arg(short)]
+    #[arg(short, global = true)]
     pub quiet: bool,
 }
 
+/// Commands that inspect existing rules instead of generating them.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Report where the code has drifted from the conventions in existing rule files
+    Drift(DriftArgs),
+    /// Validate existing rule files without calling LLM (exits 1 on errors)
+    Validate(ValidateArgs),
+}
+
+/// Arguments for `ruley drift`.
+#[derive(clap::Args, Debug, Clone)]
+pub struct DriftArgs {
+    /// Path to repository (defaults to the top-level PATH)
+    pub path: Option<PathBuf>,
+
+    /// Also write the report to this file as Markdown
+    #[arg(long, value_name = "PATH")]
+    pub markdown: Option<PathBuf>,
+
+    /// Also write the report to this file as JSON
+    #[arg(long, value_name = "PATH")]
+    pub json: Option<PathBuf>,
+}
+
+/// Arguments for `ruley validate`.
+#[derive(clap::Args, Debug, Clone)]
+pub struct ValidateArgs {
+    /// Path to repository (defaults to the top-level PATH)
+    pub path: Option<PathBuf>,
+
+    /// Also write the results to this file as JSON
+    #[arg(long, value_name = "PATH")]
+    pub json: Option<PathBuf>,
+
+    /// Also write the results to this file as SARIF 2.1.0
+    #[arg(long, value_name = "PATH")]
+    pub sarif: Option<PathBuf>,
+}
+
+impl Args {
+    /// Repository path, taking a subcommand's PATH over the top-level one.
+    pub fn repository_path(&self) -> &Path {
+        let path = match self.command {
+            Some(Command::Drift(ref args)) => args.path.as_ref(),
+            Some(Command::Validate(ref args)) => args.path.as_ref(),
+            None => None,
+        };
+        path.unwrap_or(&self.path)
+    }
+}
+
 impl OutputFormat {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -27,6 +27,8 @@
 
 use crate::cli::args::{Args, ArgsPresence};
 use crate::generator::rules::RuleType;
+use crate::llm::provider::Pricing;
+use crate::packer::CompressionLevel;
 use crate::utils::error::RuleyError;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -36,8 +38,10 @@
 pub struct ChunkingConfig {
     /// Maximum tokens per chunk
     pub chunk_size: Option<usize>,
-    /// Token overlap between chunks (for future use)
+    /// Token overlap between chunks (text strategy only)
     pub overlap: Option<usize>,
+    /// How to split codebases that exceed one chunk ("files" or "text")
+    pub strategy: Option<crate::llm::chunker::ChunkStrategy>,
 }
 
 /// Serde helper that returns `true`.
@@ -50,6 +54,139 @@ fn default_max_retries() -> usize {
     3
 }
 
+/// Serde helper that returns the default LLM request concurrency.
+fn default_max_concurrency() -> usize {
+    crate::llm::analysis::DEFAULT_MAX_CONCURRENCY
+}
+
+/// Configuration for LLM request scheduling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmConfig {
+    /// Maximum LLM requests in flight at once (chunk analyses, format refinements)
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: default_max_concurrency(),
+        }
+    }
+}
+
+/// Serde helper that returns the default response cache TTL (7 days).
+fn default_cache_ttl_hours() -> u64 {
+    168
+}
+
+/// Serde helper that returns the default response cache size limit.
+fn default_cache_max_size_mb() -> u64 {
+    100
+}
+
+/// Configuration for the LLM response cache in `.ruley/cache/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Serve identical LLM requests from the cache (opt-in)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hours a cached response stays valid
+    #[serde(default = "default_cache_ttl_hours")]
+    pub ttl_hours: u64,
+    /// Maximum cache size in megabytes; the oldest responses are evicted first
+    #[serde(default = "default_cache_max_size_mb")]
+    pub max_size_mb: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_hours: default_cache_ttl_hours(),
+            max_size_mb: default_cache_max_size_mb(),
+        }
+    }
+}
+
+/// Configuration for tree-sitter compression (`[compression]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// Query files replacing the built-in extractor, keyed by language name
+    /// (e.g. `rust = "queries/rust.scm"`); relative to the repository root
+    #[serde(default)]
+    pub queries: std::collections::HashMap<String, PathBuf>,
+    /// Compression level overrides keyed by language name (e.g. `python = "truncate"`)
+    #[serde(default)]
+    pub levels: std::collections::HashMap<String, CompressionLevel>,
+    /// Lines of each function body kept at the `truncate` level
+    #[serde(default = "default_truncate_lines")]
+    pub truncate_lines: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            queries: std::collections::HashMap::new(),
+            levels: std::collections::HashMap::new(),
+            truncate_lines: default_truncate_lines(),
+        }
+    }
+}
+
+fn default_truncate_lines() -> usize {
+    crate::packer::compress::DEFAULT_TRUNCATE_LINES
+}
+
+/// Configuration for which files the scanner skips (`[scan]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanConfig {
+    /// Files larger than this many bytes are skipped
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: u64,
+    /// Keep lockfiles, minified bundles and files with a generated-code header
+    #[serde(default)]
+    pub include_generated: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: default_max_file_size(),
+            include_generated: false,
+        }
+    }
+}
+
+fn default_max_file_size() -> u64 {
+    1024 * 1024
+}
+
+/// Configuration for secret detection before content is sent (`[secrets]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    /// Scan file contents and redact secrets
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Abort the run instead of redacting when secrets are found
+    #[serde(default)]
+    pub fail_on_secrets: bool,
+    /// Extra regexes keyed by name; if a regex has a capture group, only the
+    /// group is redacted
+    #[serde(default)]
+    pub patterns: std::collections::HashMap<String, String>,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fail_on_secrets: false,
+            patterns: std::collections::HashMap::new(),
+        }
+    }
+}
+
 /// Configuration for semantic validation checks.
 ///
 /// Controls which semantic checks are performed during validation.
@@ -200,8 +337,21 @@ pub struct Config {
     pub exclude: ExcludeConfig,
     #[serde(default)]
     pub providers: ProvidersConfig,
+    /// Per-model overrides keyed by model name (`[models."gpt-4.1"]`)
+    #[serde(default)]
+    pub models: std::collections::HashMap<String, ModelConfig>,
     pub chunking: Option<ChunkingConfig>,
     #[serde(default)]
+    pub llm: LlmConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub compression: CompressionConfig,
+    #[serde(default)]
+    pub scan: ScanConfig,
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    #[serde(default)]
     pub validation: ValidationConfig,
     #[serde(default)]
     pub finalization: FinalizationConfig,
@@ -217,8 +367,18 @@ pub struct GeneralConfig {
     pub format: Vec<String>,
     #[serde(default)]
     pub compress: bool,
+    /// Compression level; overrides `compress` when set
+    pub compress_level: Option<CompressionLevel>,
     #[serde(default = "default_chunk_size")]
     pub chunk_size: usize,
+    /// Token budget; when set, only the most informative files that fit are analyzed
+    pub token_budget: Option<usize>,
+    /// Analyze every file (chunking if needed) even when a token budget is set
+    #[serde(default)]
+    pub full_coverage: bool,
+    /// Generate rules per package when the repository is a monorepo workspace
+    #[serde(default)]
+    pub workspaces: bool,
     #[serde(default)]
     pub no_confirm: bool,
     #[serde(default)]
@@ -265,6 +425,11 @@ pub struct ProvidersConfig {
     pub openai: Option<ProviderConfig>,
     pub ollama: Option<OllamaConfig>,
     pub openrouter: Option<ProviderConfig>,
+    pub xai: Option<ProviderConfig>,
+    pub groq: Option<ProviderConfig>,
+    pub gemini: Option<ProviderConfig>,
+    #[serde(rename = "openai-compatible", alias = "openai_compatible")]
+    pub openai_compatible: Option<OpenAICompatibleConfig>,
 }
 
 /// Configuration for a single LLM provider.
@@ -274,6 +439,58 @@ pub struct ProviderConfig {
     pub max_tokens: Option<usize>,
 }
 
+/// Configuration for a generic OpenAI-compatible endpoint (vLLM, LM Studio, gateways).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OpenAICompatibleConfig {
+    /// API base URL including any version prefix (e.g. `http://localhost:8000/v1`)
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    pub max_tokens: Option<usize>,
+    /// Environment variable holding the API key (unauthenticated when unset)
+    pub api_key_env: Option<String>,
+    /// Extra HTTP headers sent with every request
+    #[serde(default)]
+    pub headers: std::collections::HashMap<String, String>,
+    /// Pricing used for cost estimation (defaults to free)
+    pub pricing: Option<PricingConfig>,
+    /// Context window in tokens, used to decide when to chunk
+    pub context_window: Option<usize>,
+}
+
+/// Custom pricing in USD per 1K tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingConfig {
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+}
+
+impl From<&PricingConfig> for Pricing {
+    fn from(config: &PricingConfig) -> Self {
+        Self {
+            input_per_1k: config.input_per_1k,
+            output_per_1k: config.output_per_1k,
+        }
+    }
+}
+
+/// Per-model overrides for the built-in model registry.
+///
+/// Every field is optional; unset fields fall back to the built-in entry for
+/// the model, then to the provider's defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModelConfig {
+    /// Context window in tokens
+    pub context_window: Option<usize>,
+    /// Maximum tokens per response
+    pub max_output_tokens: Option<usize>,
+    /// Input price in USD per 1K tokens
+    pub input_per_1k: Option<f64>,
+    /// Output price in USD per 1K tokens
+    pub output_per_1k: Option<f64>,
+    /// Tokenizer encoding: `cl100k_base` or `o200k_base`
+    pub tokenizer: Option<String>,
+}
+
 /// Ollama-specific configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OllamaConfig {
@@ -390,11 +607,17 @@ pub fn merge_config(args: &Args, config: Config, presence: &ArgsPresence) -> cra
         config.general.rule_type
     };
 
-    // Compress: CLI explicit > config
-    let compress = if presence.compress {
-        args.compress
+    // Compression level: CLI --compress-level > CLI --compress > config
+    // compress_level > config compress (which means `signatures`)
+    let compress_level = if presence.compress_level {
+        args.compress_level.unwrap_or_default()
+    } else if presence.compress {
+        compress_flag_level(args.compress)
     } else {
-        config.general.compress
+        config
+            .general
+            .compress_level
+            .unwrap_or_else(|| compress_flag_level(config.general.compress))
     };
 
     // Chunk size: CLI explicit > general.chunk_size (if non-default) > chunking.chunk_size > CLI default
@@ -410,6 +633,13 @@ pub fn merge_config(args: &Args, config: Config, presence: &ArgsPresence) -> cra
             .unwrap_or(args.chunk_size)
     };
 
+    // Token budget: CLI > config; --full-coverage can only turn coverage on
+    let token_budget = args.token_budget.or(config.general.token_budget);
+    let full_coverage = args.full_coverage || config.general.full_coverage;
+
+    // Workspaces: either source can turn per-package generation on
+    let workspaces = args.workspaces || config.general.workspaces;
+
     // No confirm: CLI explicit > config
     let no_confirm = if presence.no_confirm {
         args.no_confirm
@@ -450,6 +680,12 @@ pub fn merge_config(args: &Args, config: Config, presence: &ArgsPresence) -> cra
         finalization.deconflict = false;
     }
 
+    // Secrets: --fail-on-secrets can only turn failing on
+    let mut secrets = config.secrets;
+    if args.fail_on_secrets {
+        secrets.fail_on_secrets = true;
+    }
+
     // On-conflict strategy: CLI explicit > config file > default ("prompt")
     let on_conflict = if presence.on_conflict {
         args.on_conflict
@@ -468,27 +704,57 @@ pub fn merge_config(args: &Args, config: Config, presence: &ArgsPresence) -> cra
         model: args.model.clone().or(config.general.model),
         format,
         output: args.output.clone(),
+        output_dir: args.output_dir.clone(),
+        git_ref: args.git_ref.clone(),
+        depth: args.depth,
         repomix_file: args.repomix_file.clone(),
-        path: args.path.clone(),
+        path: args.repository_path().to_path_buf(),
         description: args.description.clone(),
         rule_type,
         include,
         exclude,
-        compress,
+        compress_level,
         chunk_size,
+        token_budget,
+        full_coverage,
+        workspaces,
         no_confirm,
         dry_run: args.dry_run,
+        token_report: args.token_report || args.token_report_json.is_some(),
+        token_report_json: args.token_report_json.clone(),
+        resume: args.resume,
+        incremental: args.incremental,
+        check: args.check,
+        command: args.command.clone(),
         verbose: args.verbose,
         quiet: args.quiet,
         chunking: config.chunking,
         output_paths: config.output.paths,
         providers: config.providers,
+        models: config.models,
+        llm: config.llm,
+        cache: CacheConfig {
+            enabled: config.cache.enabled && !args.no_cache,
+            ..config.cache
+        },
+        compression: config.compression,
+        scan: config.scan,
+        secrets,
         validation,
         finalization,
         on_conflict,
     }
 }
 
+/// The level selected by the boolean `compress` flag.
+fn compress_flag_level(compress: bool) -> CompressionLevel {
+    if compress {
+        CompressionLevel::Signatures
+    } else {
+        CompressionLevel::None
+    }
+}
+
 /// Returns the first non-empty vector from the slice, or None if all are empty.
 fn first_non_empty(vecs: &[&Vec<String>]) -> Option<Vec<String>> {
     vecs.iter().find_map(|v| {
@@ -520,7 +786,11 @@ fn create_test_config() -> Config {
                     model: Some("gpt-4o".to_string()),
                     format: vec!["cursor".to_string(), "claude".to_string()],
                     compress: true,
+                    compress_level: None,
                     chunk_size: 50000,
+                    token_budget: None,
+                    full_coverage: false,
+                    workspaces: false,
                     no_confirm: false,
                     rule_type: RuleType::Manual,
                 },
@@ -540,9 +810,16 @@ fn create_test_config() -> Config {
                     patterns: vec!["**/target/**".to_string()],
                 },
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: Some(ChunkingConfig {
                     chunk_size: Some(75000),
                     overlap: None,
+                    strategy: None,
                 }),
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -551,10 +828,14 @@ fn create_test_config() -> Config {
 
         fn create_test_args() -> Args {
             Args {
+                command: None,
                 path: PathBuf::from("."),
                 provider: "anthropic".to_string(),
                 model: Some("claude-sonnet-4".to_string()),
                 output: None,
+                output_dir: None,
+                git_ref: None,
+                depth: 1,
                 repomix_file: None,
                 format: vec![OutputFormat::Copilot, OutputFormat::Windsurf],
                 description: None,
@@ -563,9 +844,20 @@ fn create_test_args() -> Args {
                 include: vec!["**/*.ts".to_string()],
                 exclude: vec!["**/node_modules/**".to_string()],
                 compress: false,
+                compress_level: None,
                 chunk_size: 100000,
+                token_budget: None,
+                full_coverage: false,
+                workspaces: false,
                 no_confirm: true,
                 dry_run: false,
+                token_report: false,
+                token_report_json: None,
+                resume: false,
+                incremental: false,
+                check: false,
+                no_cache: false,
+                fail_on_secrets: false,
                 retry_on_validation_failure: false,
                 no_deconflict: false,
                 no_semantic_validation: false,
@@ -581,6 +873,7 @@ fn create_test_presence() -> ArgsPresence {
                 format: true,
                 rule_type: true,
                 compress: true,
+                compress_level: false,
                 chunk_size: true,
                 no_confirm: true,
                 retry_on_validation_failure: false,
@@ -602,7 +895,7 @@ fn test_merge_config_cli_explicit() {
             assert_eq!(merged.provider, "anthropic");
             assert_eq!(merged.format, vec!["copilot", "windsurf"]);
             assert_eq!(merged.rule_type, RuleType::Auto);
-            assert!(!merged.compress);
+            assert_eq!(merged.compress_level, CompressionLevel::None);
             assert_eq!(merged.chunk_size, 100000);
             assert!(merged.no_confirm);
         }
@@ -619,11 +912,53 @@ fn test_merge_config_cli_defaults() {
             assert_eq!(merged.provider, "openai");
             assert_eq!(merged.format, vec!["cursor", "claude"]);
             assert_eq!(merged.rule_type, RuleType::Manual);
-            assert!(merged.compress);
+            assert_eq!(merged.compress_level, CompressionLevel::Signatures);
             assert_eq!(merged.chunk_size, 50000);
             assert!(!merged.no_confirm);
         }
 
+        #[test]
+        fn test_merge_config_compress_level_precedence() {
+            let mut config = create_test_config();
+            config.general.compress_level = Some(CompressionLevel::Truncate);
+            let mut args = create_test_args();
+            let mut presence = ArgsPresence::default();
+
+            // Config compress_level wins over config compress
+            let merged = merge_config(&args, config.clone(), &presence);
+            assert_eq!(merged.compress_level, CompressionLevel::Truncate);
+
+            // CLI --compress wins over config
+            args.compress = true;
+            presence.compress = true;
+            let merged = merge_config(&args, config.clone(), &presence);
+            assert_eq!(merged.compress_level, CompressionLevel::Signatures);
+
+            // CLI --compress-level wins over --compress
+            args.compress_level = Some(CompressionLevel::Whitespace);
+            presence.compress_level = true;
+            let merged = merge_config(&args, config, &presence);
+            assert_eq!(merged.compress_level, CompressionLevel::Whitespace);
+        }
+
+        #[test]
+        fn test_merge_config_no_cache_disables_response_cache() {
+            let mut config = create_test_config();
+            config.cache.enabled = true;
+            config.cache.ttl_hours = 24;
+            let mut args = create_test_args();
+            let presence = create_test_presence();
+
+            let merged = merge_config(&args, config.clone(), &presence);
+            assert!(merged.cache.enabled);
+            assert_eq!(merged.cache.ttl_hours, 24);
+
+            args.no_cache = true;
+            let merged = merge_config(&args, config, &presence);
+            assert!(!merged.cache.enabled);
+            assert_eq!(merged.cache.ttl_hours, 24);
+        }
+
         #[test]
         fn test_merge_config_format_precedence() {
             let config = Config {
@@ -638,6 +973,12 @@ fn test_merge_config_format_precedence() {
                 include: IncludeConfig::default(),
                 exclude: ExcludeConfig::default(),
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: None,
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -676,9 +1017,16 @@ fn test_merge_config_chunk_size_precedence() {
                 include: IncludeConfig::default(),
                 exclude: ExcludeConfig::default(),
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: Some(ChunkingConfig {
                     chunk_size: Some(75000),
                     overlap: None,
+                    strategy: None,
                 }),
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -715,9 +1063,16 @@ fn test_merge_config_chunk_size_precedence() {
                 include: IncludeConfig::default(),
                 exclude: ExcludeConfig::default(),
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: Some(ChunkingConfig {
                     chunk_size: Some(75000),
                     overlap: None,
+                    strategy: None,
                 }),
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -730,6 +1085,73 @@ fn test_merge_config_chunk_size_precedence() {
             assert_eq!(merged.chunk_size, 75000);
         }
 
+        #[test]
+        fn test_merge_config_token_budget() {
+            let mut config = create_test_config();
+            config.general.token_budget = Some(150000);
+            let presence = ArgsPresence::default();
+
+            // Config budget applies when the CLI doesn't set one
+            let merged = merge_config(&create_test_args(), config.clone(), &presence);
+            assert_eq!(merged.token_budget, Some(150000));
+            assert!(!merged.full_coverage);
+
+            // CLI budget and --full-coverage win
+            let args = Args {
+                token_budget: Some(50000),
+                full_coverage: true,
+                ..create_test_args()
+            };
+            let merged = merge_config(&args, config, &presence);
+            assert_eq!(merged.token_budget, Some(50000));
+            assert!(merged.full_coverage);
+        }
+
+        #[test]
+        fn test_merge_config_workspaces() {
+            let presence = ArgsPresence::default();
+            let merged = merge_config(&create_test_args(), create_test_config(), &presence);
+            assert!(!merged.workspaces);
+
+            let args = Args {
+                workspaces: true,
+                ..create_test_args()
+            };
+            assert!(merge_config(&args, create_test_config(), &presence).workspaces);
+
+            let mut config = create_test_config();
+            config.general.workspaces = true;
+            assert!(merge_config(&create_test_args(), config, &presence).workspaces);
+        }
+
+        #[test]
+        fn test_merge_config_subcommand_path() {
+            use crate::cli::args::{Command, DriftArgs};
+            use clap::{CommandFactory, FromArgMatches};
+
+            let matches = Args::command()
+                .try_get_matches_from(["ruley", "drift", "repo", "--provider", "openai"])
+                .unwrap();
+            let args = Args::from_arg_matches(&matches).unwrap();
+            let presence = ArgsPresence::from_matches(&matches);
+            let merged = merge_config(&args, create_test_config(), &presence);
+            assert_eq!(merged.path, PathBuf::from("repo"));
+            assert_eq!(merged.provider, "openai");
+            assert!(matches!(merged.command, Some(Command::Drift(_))));
+
+            let args = Args {
+                command: Some(Command::Drift(DriftArgs {
+                    path: None,
+                    markdown: None,
+                    json: None,
+                })),
+                path: PathBuf::from("top"),
+                ..create_test_args()
+            };
+            let merged = merge_config(&args, create_test_config(), &presence);
+            assert_eq!(merged.path, PathBuf::from("top"));
+        }
+
         #[test]
         fn test_merge_config_include_exclude() {
             let config = Config {
@@ -742,6 +1164,12 @@ fn test_merge_config_include_exclude() {
                     patterns: vec!["**/target/**".to_string()],
                 },
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: None,
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -772,6 +1200,12 @@ fn test_merge_config_include_exclude_empty_cli() {
                     patterns: vec!["**/target/**".to_string()],
                 },
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: None,
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -807,6 +1241,12 @@ fn test_merge_config_output_paths() {
                 include: IncludeConfig::default(),
                 exclude: ExcludeConfig::default(),
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: None,
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -836,6 +1276,12 @@ fn test_merge_config_model_precedence() {
                 include: IncludeConfig::default(),
                 exclude: ExcludeConfig::default(),
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: None,
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -863,6 +1309,12 @@ fn test_merge_config_model_from_config() {
                 include: IncludeConfig::default(),
                 exclude: ExcludeConfig::default(),
                 providers: ProvidersConfig::default(),
+                models: std::collections::HashMap::new(),
+                llm: LlmConfig::default(),
+                cache: CacheConfig::default(),
+                compression: CompressionConfig::default(),
+                scan: ScanConfig::default(),
+                secrets: SecretsConfig::default(),
                 chunking: None,
                 validation: ValidationConfig::default(),
                 finalization: FinalizationConfig::default(),
@@ -879,4 +1331,225 @@ fn test_merge_config_model_from_config() {
             assert_eq!(merged.model, Some("gpt-4o".to_string()));
         }
     }
+
+    mod providers {
+        use super::*;
+
+        fn parse(toml: &str) -> Config {
+            config::Config::builder()
+                .add_source(config::File::from_str(toml, config::FileFormat::Toml))
+                .build()
+                .expect("config should build")
+                .try_deserialize()
+                .expect("config should deserialize")
+        }
+
+        #[test]
+        fn test_openai_compatible_section() {
+            let config = parse(
+                r#"
+                [providers.openai-compatible]
+                base_url = "http://localhost:8000/v1"
+                model = "Qwen/Qwen2.5-Coder-32B-Instruct"
+                api_key_env = "VLLM_API_KEY"
+                context_window = 32768
+
+                [providers.openai-compatible.headers]
+                X-Gateway-Team = "platform"
+
+                [providers.openai-compatible.pricing]
+                input_per_1k = 0.0005
+                output_per_1k = 0.0015
+                "#,
+            );
+
+            let compat = config
+                .providers
+                .openai_compatible
+                .expect("openai-compatible section should be parsed");
+            assert_eq!(compat.base_url.as_deref(), Some("http://localhost:8000/v1"));
+            assert_eq!(compat.api_key_env.as_deref(), Some("VLLM_API_KEY"));
+            assert_eq!(compat.context_window, Some(32768));
+            assert_eq!(
+                compat.headers.get("X-Gateway-Team").map(String::as_str),
+                Some("platform")
+            );
+            let pricing = compat.pricing.expect("pricing should be parsed");
+            assert_eq!(pricing.input_per_1k, 0.0005);
+            assert_eq!(pricing.output_per_1k, 0.0015);
+        }
+
+        #[test]
+        fn test_chunking_section_strategy() {
+            use crate::llm::chunker::ChunkStrategy;
+
+            let config = parse("[chunking]\nstrategy = \"text\"\n");
+            let chunking = config.chunking.expect("chunking section");
+            assert_eq!(chunking.strategy, Some(ChunkStrategy::Text));
+
+            let config = parse("[chunking]\nchunk_size = 50000\n");
+            assert_eq!(config.chunking.and_then(|c| c.strategy), None);
+        }
+
+        #[test]
+        fn test_llm_section_max_concurrency() {
+            let config = parse("[llm]\nmax_concurrency = 8\n");
+            assert_eq!(config.llm.max_concurrency, 8);
+
+            let config = parse("[general]\nprovider = \"openai\"\n");
+            assert_eq!(
+                config.llm.max_concurrency,
+                crate::llm::analysis::DEFAULT_MAX_CONCURRENCY
+            );
+        }
+
+        #[test]
+        fn test_cache_section() {
+            let config = parse("[cache]\nenabled = true\nmax_size_mb = 20\n");
+            assert!(config.cache.enabled);
+            assert_eq!(config.cache.max_size_mb, 20);
+            assert_eq!(config.cache.ttl_hours, 168);
+
+            let config = parse("[general]\nprovider = \"openai\"\n");
+            assert!(!config.cache.enabled);
+        }
+
+        #[test]
+        fn test_compression_queries_section() {
+            let config = parse(
+                "[compression.queries]\nrust = \"queries/rust.scm\"\ncsharp = \"q/cs.scm\"\n",
+            );
+            assert_eq!(
+                config.compression.queries.get("rust"),
+                Some(&PathBuf::from("queries/rust.scm"))
+            );
+            assert_eq!(config.compression.queries.len(), 2);
+
+            let config = parse("[general]\nprovider = \"openai\"\n");
+            assert!(config.compression.queries.is_empty());
+        }
+
+        #[test]
+        fn test_scan_section() {
+            let config = parse("[scan]\nmax_file_size = 2048\ninclude_generated = true\n");
+            assert_eq!(config.scan.max_file_size, 2048);
+            assert!(config.scan.include_generated);
+
+            let config = parse("[general]\nprovider = \"openai\"\n");
+            assert_eq!(config.scan.max_file_size, 1024 * 1024);
+            assert!(!config.scan.include_generated);
+        }
+
+        #[test]
+        fn test_token_budget_settings() {
+            let config = parse("[general]\ntoken_budget = 150000\nfull_coverage = true\n");
+            assert_eq!(config.general.token_budget, Some(150000));
+            assert!(config.general.full_coverage);
+
+            let config = parse("[general]\nprovider = \"openai\"\n");
+            assert_eq!(config.general.token_budget, None);
+            assert!(!config.general.full_coverage);
+        }
+
+        #[test]
+        fn test_workspaces_setting() {
+            assert!(parse("[general]\nworkspaces = true\n").general.workspaces);
+            assert!(
+                !parse("[general]\nprovider = \"openai\"\n")
+                    .general
+                    .workspaces
+            );
+        }
+
+        #[test]
+        fn test_secrets_section() {
+            let config = parse(
+                "[secrets]\nfail_on_secrets = true\n\n[secrets.patterns]\ninternal-token = \"itk_[a-z0-9]{16}\"\n",
+            );
+            assert!(config.secrets.enabled);
+            assert!(config.secrets.fail_on_secrets);
+            assert_eq!(
+                config
+                    .secrets
+                    .patterns
+                    .get("internal-token")
+                    .map(String::as_str),
+                Some("itk_[a-z0-9]{16}")
+            );
+
+            let config = parse("[secrets]\nenabled = false\n");
+            assert!(!config.secrets.enabled);
+            assert!(!config.secrets.fail_on_secrets);
+            assert!(config.secrets.patterns.is_empty());
+        }
+
+        #[test]
+        fn test_compression_levels_section() {
+            let config = parse(
+                "[general]\ncompress_level = \"truncate\"\n\n[compression]\ntruncate_lines = 8\n\n[compression.levels]\npython = \"signatures\"\n",
+            );
+            assert_eq!(
+                config.general.compress_level,
+                Some(CompressionLevel::Truncate)
+            );
+            assert_eq!(config.compression.truncate_lines, 8);
+            assert_eq!(
+                config.compression.levels.get("python"),
+                Some(&CompressionLevel::Signatures)
+            );
+
+            let config = parse("[general]\nprovider = \"openai\"\n");
+            assert_eq!(config.general.compress_level, None);
+            assert_eq!(
+                config.compression.truncate_lines,
+                crate::packer::compress::DEFAULT_TRUNCATE_LINES
+            );
+        }
+
+        #[test]
+        fn test_models_section_with_dotted_names() {
+            let config = parse(
+                r#"
+                [models."gpt-4.1"]
+                context_window = 1047576
+                input_per_1k = 0.002
+
+                [models."anthropic/claude-3.5-sonnet"]
+                tokenizer = "cl100k_base"
+                "#,
+            );
+
+            let gpt = config
+                .models
+                .get("gpt-4.1")
+                .expect("dotted model name should be kept intact");
+            assert_eq!(gpt.context_window, Some(1_047_576));
+            assert_eq!(gpt.input_per_1k, Some(0.002));
+            assert!(gpt.output_per_1k.is_none());
+            assert_eq!(
+                config.models["anthropic/claude-3.5-sonnet"]
+                    .tokenizer
+                    .as_deref(),
+                Some("cl100k_base")
+            );
+        }
+
+        #[test]
+        fn test_openai_compatible_underscore_alias() {
+            let config = parse(
+                r#"
+                [providers.openai_compatible]
+                base_url = "http://localhost:1234/v1"
+                "#,
+            );
+
+            let compat = config
+                .providers
+                .openai_compatible
+                .expect("underscore alias should be accepted");
+            assert_eq!(compat.base_url.as_deref(), Some("http://localhost:1234/v1"));
+            assert!(compat.headers.is_empty());
+            assert!(compat.pricing.is_none());
+        }
+    }
 }
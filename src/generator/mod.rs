@@ -12,7 +12,10 @@
 pub mod refinement;
 pub mod rules;
 
-pub use prompts::{build_analysis_prompt, build_refinement_prompt, build_smart_merge_prompt};
+pub use prompts::{
+    build_analysis_prompt, build_drift_prompt, build_incremental_prompt, build_refinement_prompt,
+    build_smart_merge_prompt,
+};
 pub use refinement::{RefinementResult, refine_invalid_output};
 pub use rules::{
     FormattedRules, GeneratedRules, GenerationMetadata, RuleType, get_default_rule_type,
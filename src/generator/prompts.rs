@@ -7,6 +7,8 @@
 //! - Initial codebase analysis
 //! - Format-specific rule refinement (Cursor, Claude, Copilot)
 //! - Smart merging of existing and new rules
+//! - Incremental updates of a previous analysis
+//! - Drift reports comparing existing rules with the current code
 //!
 //! # Example
 //!
@@ -19,6 +21,7 @@
 //! ```
 
 use crate::packer::CompressedCodebase;
+use crate::utils::incremental::FileChanges;
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -62,6 +65,16 @@ pub fn generic_prompt() -> &'static str {
     include_str!("../../prompts/generic.md")
 }
 
+/// Load the incremental analysis prompt template.
+pub fn incremental_prompt() -> &'static str {
+    include_str!("../../prompts/incremental.md")
+}
+
+/// Load the drift report prompt template.
+pub fn drift_prompt() -> &'static str {
+    include_str!("../../prompts/drift.md")
+}
+
 /// Build the analysis prompt for initial codebase analysis.
 ///
 /// This function constructs a comprehensive prompt that includes:
@@ -194,6 +207,77 @@ pub fn build_smart_merge_prompt(existing_rules: &str, new_analysis: &str) -> Str
         .replace("{{new_analysis}}", new_analysis)
 }
 
+/// Build the prompt for updating a previous analysis with changed files.
+///
+/// The changed files themselves are not part of the prompt; they are
+/// appended as the codebase content of each analysis chunk, like the full
+/// codebase in a regular run.
+///
+/// # Arguments
+///
+/// * `previous_analysis` - The analysis the current rules were generated from
+/// * `changes` - Files added, modified and removed since that analysis
+/// * `focus` - Optional focus area or description to guide the analysis
+pub fn build_incremental_prompt(
+    previous_analysis: &str,
+    changes: &FileChanges,
+    focus: Option<&str>,
+) -> String {
+    let list = |paths: &[std::path::PathBuf]| {
+        if paths.is_empty() {
+            "none".to_string()
+        } else {
+            paths
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+
+    let focus_section = if let Some(focus_text) = focus {
+        format!("Special Focus:\n{}\n", focus_text)
+    } else {
+        String::new()
+    };
+
+    incremental_prompt()
+        .replace("{{modified_files}}", &list(&changes.modified))
+        .replace("{{added_files}}", &list(&changes.added))
+        .replace("{{removed_files}}", &list(&changes.removed))
+        .replace("{{focus_section}}", &focus_section)
+        .replace("{{previous_analysis}}", previous_analysis)
+}
+
+/// Build the prompt that asks for a drift report of existing rules.
+///
+/// # Arguments
+///
+/// * `rule_files` - Path and content of each existing rule file
+/// * `file_paths` - Every current file of the codebase
+/// * `sample` - The sampled files to judge the rules against
+pub fn build_drift_prompt(
+    rule_files: &[(String, String)],
+    file_paths: &[String],
+    sample: &CompressedCodebase,
+) -> String {
+    let rules = rule_files
+        .iter()
+        .map(|(path, content)| format!("=== {} ===\n{}\n", path, content))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    drift_prompt()
+        .replace(
+            "{{sampled_files}}",
+            &sample.metadata.total_files.to_string(),
+        )
+        .replace("{{total_files}}", &file_paths.len().to_string())
+        .replace("{{rule_files}}", &rules)
+        .replace("{{file_paths}}", &file_paths.join("\n"))
+        .replace("{{code_sample}}", &format_codebase_content(sample))
+}
+
 /// Format the compressed codebase content for inclusion in prompts.
 ///
 /// Creates a structured representation of all files with their paths
@@ -350,6 +434,42 @@ fn test_build_smart_merge_prompt() {
         assert!(prompt.contains("Preserve"));
     }
 
+    #[test]
+    fn test_build_incremental_prompt() {
+        let changes = FileChanges {
+            added: vec![PathBuf::from("src/api.rs")],
+            modified: vec![PathBuf::from("src/main.rs"), PathBuf::from("Cargo.toml")],
+            removed: Vec::new(),
+        };
+        let prompt =
+            build_incremental_prompt("Uses anyhow for errors", &changes, Some("Error handling"));
+
+        assert!(prompt.contains("<previous_analysis> Uses anyhow for errors </previous_analysis>"));
+        assert!(prompt.contains("- Modified: src/main.rs, Cargo.toml"));
+        assert!(prompt.contains("- Added: src/api.rs"));
+        assert!(prompt.contains("- Removed: none"));
+        assert!(prompt.contains("Special Focus:\nError handling"));
+        assert!(!prompt.contains("{{"));
+    }
+
+    #[test]
+    fn test_build_drift_prompt() {
+        let sample = create_test_codebase();
+        let rule_files = vec![("CLAUDE.md".to_string(), "- Use anyhow".to_string())];
+        let paths = vec![
+            "src/main.rs".to_string(),
+            "src/lib.rs".to_string(),
+            "Cargo.toml".to_string(),
+        ];
+        let prompt = build_drift_prompt(&rule_files, &paths, &sample);
+
+        assert!(prompt.contains("A sample of 2 of the 3 current files"));
+        assert!(prompt.contains("=== CLAUDE.md ===\n- Use anyhow"));
+        assert!(prompt.contains("<file_paths> src/main.rs\nsrc/lib.rs\nCargo.toml </file_paths>"));
+        assert!(prompt.contains("pub mod utils;"));
+        assert!(!prompt.contains("{{"));
+    }
+
     #[test]
     fn test_detect_primary_language() {
         assert_eq!(detect_primary_language("This is a Rust project"), "rust");
@@ -103,11 +103,7 @@ pub async fn refine_invalid_output(
     // Track cost
     let attempt_cost = if let Some(tracker) = cost_tracker {
         let cost_before = tracker.total_cost();
-        tracker.add_operation(
-            format!("refinement_{}_{}", format, attempt),
-            response.prompt_tokens,
-            response.completion_tokens,
-        );
+        tracker.add_completion(format!("refinement_{}_{}", format, attempt), &response);
         tracker.total_cost() - cost_before
     } else {
         0.0
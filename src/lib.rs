@@ -47,17 +47,21 @@
 
 use anyhow::{Context, Result};
 use chrono::Utc;
-use cli::config::{ChunkingConfig, FinalizationConfig, ProvidersConfig, ValidationConfig};
+use cli::config::{
+    CacheConfig, ChunkingConfig, CompressionConfig, FinalizationConfig, LlmConfig, ModelConfig,
+    ProvidersConfig, ScanConfig, SecretsConfig, ValidationConfig,
+};
+use futures_util::{StreamExt, TryStreamExt, stream};
 use generator::rules::RuleType;
 use llm::chunker::{Chunk, ChunkConfig};
 use llm::client::LLMClient;
 use llm::cost::{CostCalculator, CostTracker};
+use llm::models::{ModelInfo, ModelRegistry};
 use llm::provider::LLMProvider;
-#[cfg(feature = "anthropic")]
-use llm::tokenizer::AnthropicTokenizer;
-use llm::tokenizer::{TiktokenTokenizer, Tokenizer, TokenizerModel};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use llm::tokenizer::{TiktokenTokenizer, Tokenizer};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use utils::cache::TempFileManager;
 use utils::cost_display::{display_cost_estimate, prompt_confirmation};
@@ -66,7 +70,8 @@
 use utils::progress::ProgressManager;
 use utils::progress::stages;
 use utils::state::State;
-use utils::summary::display_success_summary;
+use utils::summary::{ResumeSummary, display_success_summary};
+use utils::token_report::{build_token_report, display_token_report, write_token_report_json};
 use utils::validation::ValidationResult;
 
 /// Initialize logging based on verbosity level.
@@ -102,6 +107,12 @@ pub struct MergedConfig {
     pub format: Vec<String>,
     /// Output file path (optional)
     pub output: Option<PathBuf>,
+    /// Directory rule files are written into (defaults to the repository path)
+    pub output_dir: Option<PathBuf>,
+    /// Branch, tag or commit to check out when `path` is a remote URL
+    pub git_ref: Option<String>,
+    /// Clone depth when `path` is a remote URL (0 = full history)
+    pub depth: u32,
     /// Path to repomix file (optional)
     pub repomix_file: Option<PathBuf>,
     /// Repository path to process
@@ -114,14 +125,32 @@ pub struct MergedConfig {
     pub include: Vec<String>,
     /// File exclude patterns
     pub exclude: Vec<String>,
-    /// Enable tree-sitter compression
-    pub compress: bool,
+    /// Compression level for files without a per-language override
+    pub compress_level: packer::CompressionLevel,
     /// Maximum chunk size for processing
     pub chunk_size: usize,
+    /// Token budget for file selection (`None` analyzes every file)
+    pub token_budget: Option<usize>,
+    /// Ignore the token budget and analyze every file, chunking if needed
+    pub full_coverage: bool,
+    /// Generate rules per workspace package instead of once for the repository
+    pub workspaces: bool,
     /// Skip cost confirmation prompt
     pub no_confirm: bool,
     /// Dry run mode (show what would be processed)
     pub dry_run: bool,
+    /// Token report mode (show per-directory token attribution)
+    pub token_report: bool,
+    /// Path to also write the token report to as JSON
+    pub token_report_json: Option<PathBuf>,
+    /// Reuse cached chunk analyses from an interrupted run
+    pub resume: bool,
+    /// Analyze only files changed since the last run and update its analysis
+    pub incremental: bool,
+    /// Check mode (compare against the recorded state without calling LLM)
+    pub check: bool,
+    /// Subcommand to run instead of generating rules
+    pub command: Option<cli::args::Command>,
     /// Verbosity level (0 = INFO, 1 = DEBUG, 2+ = TRACE)
     pub verbose: u8,
     /// Quiet mode (suppress all output)
@@ -132,6 +161,18 @@ pub struct MergedConfig {
     pub output_paths: HashMap<String, String>,
     /// Provider-specific configurations
     pub providers: ProvidersConfig,
+    /// Per-model registry overrides keyed by model name
+    pub models: HashMap<String, ModelConfig>,
+    /// LLM request scheduling configuration
+    pub llm: LlmConfig,
+    /// LLM response cache configuration (disabled by `--no-cache`)
+    pub cache: CacheConfig,
+    /// Tree-sitter compression configuration (custom queries, per-language levels)
+    pub compression: CompressionConfig,
+    /// Binary, size and generated-file filters for the scanner
+    pub scan: ScanConfig,
+    /// Secret detection and redaction settings
+    pub secrets: SecretsConfig,
     /// Validation stage configuration
     pub validation: ValidationConfig,
     /// Finalization stage configuration
@@ -140,6 +181,14 @@ pub struct MergedConfig {
     pub on_conflict: String,
 }
 
+impl MergedConfig {
+    /// Directory rule files are written into: `--output-dir` if set,
+    /// otherwise the repository itself.
+    pub fn output_root(&self) -> &Path {
+        self.output_dir.as_deref().unwrap_or(&self.path)
+    }
+}
+
 /// Tracks the current stage of pipeline execution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PipelineStage {
@@ -199,7 +248,9 @@ pub fn new() -> Self {
         Self::default()
     }
 
-    /// Register a temporary file for tracking and cleanup
+    /// Register a temporary file or directory for tracking and cleanup.
+    ///
+    /// Directories are removed with all of their contents.
     pub fn add(&mut self, path: PathBuf) {
         self.files.push(path);
     }
@@ -224,9 +275,14 @@ pub fn clear(&mut self) -> std::io::Result<()> {
         let mut last_error = None;
 
         for path in &self.files {
-            if path.exists()
-                && let Err(e) = std::fs::remove_file(path)
-            {
+            let removed = if path.is_dir() {
+                std::fs::remove_dir_all(path)
+            } else if path.exists() {
+                std::fs::remove_file(path)
+            } else {
+                Ok(())
+            };
+            if let Err(e) = removed {
                 tracing::warn!("Failed to delete temp file {}: {}", path.display(), e);
                 failure_count += 1;
                 last_error = Some(e);
@@ -280,8 +336,21 @@ pub struct PipelineContext {
     pub progress_manager: Option<ProgressManager>,
     /// Pipeline start time for elapsed time tracking
     pub start_time: std::time::Instant,
+    /// Files left out by the binary, size and generated-file filters
+    pub skipped_files: Vec<packer::SkippedFile>,
+    /// Secrets found (and redacted) in the compressed codebase
+    pub secret_findings: Vec<packer::SecretFinding>,
+    /// Content hash of every file in the codebase, recorded in state for `--incremental`
+    pub file_hashes: BTreeMap<PathBuf, String>,
+    /// Files changed since the last run, if this is an incremental run
+    pub file_changes: Option<utils::incremental::FileChanges>,
+    /// Files kept and dropped to fit `--token-budget`, if a budget applied
+    pub file_selection: Option<llm::selection::FileSelection>,
     /// Compressed codebase data
     pub compressed_codebase: Option<packer::CompressedCodebase>,
+    /// Part of the codebase sent to the LLM when `--incremental` or
+    /// `--token-budget` narrows it; validation, stats and state still use the whole `compressed_codebase`
+    pub analysis_codebase: Option<packer::CompressedCodebase>,
     /// Analysis result from LLM (populated in Stage 4)
     pub analysis_result: Option<String>,
     /// Generated rules from analysis (populated in Stage 4)
@@ -296,6 +365,15 @@ pub struct PipelineContext {
     pub validation_results: Vec<ValidationResult>,
     /// Finalization result from Stage 7
     pub finalization_result: Option<FinalizationResult>,
+    /// Chunk analyses reused by `--resume` (populated in Stage 4)
+    pub resume_summary: Option<ResumeSummary>,
+    /// Remote URL the repository was cloned from, if `path` was a URL
+    pub cloned_from: Option<String>,
+    /// Package directory relative to the workspace root, when running for a
+    /// workspace package
+    pub package_scope: Option<PathBuf>,
+    /// Files written in Stage 8
+    pub output_results: Vec<output::OutputResult>,
 }
 
 impl PipelineContext {
@@ -316,7 +394,13 @@ pub fn new(config: MergedConfig) -> Self {
             progress: ProgressTracker::new(),
             progress_manager,
             start_time: std::time::Instant::now(),
+            skipped_files: Vec::new(),
+            secret_findings: Vec::new(),
+            file_hashes: BTreeMap::new(),
+            file_changes: None,
+            file_selection: None,
             compressed_codebase: None,
+            analysis_codebase: None,
             analysis_result: None,
             generated_rules: None,
             cost_tracker: None,
@@ -324,6 +408,10 @@ pub fn new(config: MergedConfig) -> Self {
             loaded_state: None,
             validation_results: Vec::new(),
             finalization_result: None,
+            resume_summary: None,
+            cloned_from: None,
+            package_scope: None,
+            output_results: Vec::new(),
         }
     }
 
@@ -340,20 +428,449 @@ pub async fn run(config: MergedConfig) -> Result<()> {
     // Log version and configuration summary
     tracing::info!("ruley v{} starting", env!("CARGO_PKG_VERSION"));
     tracing::debug!(
-        "Configuration: provider={}, model={:?}, format={:?}, compress={}, chunk_size={}",
+        "Configuration: provider={}, model={:?}, format={:?}, compress_level={}, chunk_size={}",
         config.provider,
         config.model,
         config.format,
-        config.compress,
+        config.compress_level,
         config.chunk_size
     );
 
-    // Initialize context
+    if config.workspaces && config.command.is_none() {
+        return run_workspace(config).await;
+    }
+
     let mut ctx = PipelineContext::new(config);
+    run_context(&mut ctx).await
+}
+
+/// Run the pipeline for `ctx`, removing a cloned repository afterwards.
+async fn run_context(ctx: &mut PipelineContext) -> Result<()> {
+    let result = run_pipeline(ctx).await;
+
+    // Failed and dry runs skip Stage 10, but a clone must never outlive the run
+    if ctx.cloned_from.is_some()
+        && !ctx.temp_files.is_empty()
+        && let Err(e) = cleanup_temp_files(ctx)
+    {
+        tracing::warn!("Failed to remove cloned repository: {:#}", e);
+    }
+
+    result
+}
+
+/// Generate rules for each package of a monorepo workspace.
+///
+/// Every package runs through the full pipeline with its directory as the
+/// repository path, so its outputs land inside the package (`CLAUDE.md`,
+/// `.github/copilot-instructions.md`, ...). Cursor rules are the exception:
+/// they go to the root `.cursor/rules/<package>.mdc` with globs limited to the
+/// package. A failing package does not stop the others. Afterwards a summary
+/// of every package's rule files is written to the output root.
+///
+/// Falls back to a single run for the whole repository when no workspace is
+/// detected.
+///
+/// # Errors
+///
+/// Returns an error if `path` is a remote URL, `--output` is set, the
+/// workspace manifest cannot be parsed, or any package failed.
+async fn run_workspace(config: MergedConfig) -> Result<()> {
+    if config.path.to_str().is_some_and(packer::git::is_remote_url) {
+        return Err(anyhow::anyhow!(
+            "--workspaces requires a local repository path"
+        ));
+    }
+    if config.output.is_some() {
+        return Err(anyhow::anyhow!(
+            "--output cannot be used with --workspaces; use --output-dir instead"
+        ));
+    }
+
+    let Some(workspace) = packer::detect_workspace(&config.path)? else {
+        tracing::warn!(
+            "No workspace found in {}, generating rules for the whole repository",
+            config.path.display()
+        );
+        let mut ctx = PipelineContext::new(config);
+        return run_context(&mut ctx).await;
+    };
+
+    tracing::info!(
+        "Detected {} workspace with {} package(s)",
+        workspace.kind,
+        workspace.members.len()
+    );
+    if !config.quiet {
+        println!(
+            "Detected {} workspace with {} package(s)",
+            workspace.kind,
+            workspace.members.len()
+        );
+    }
+
+    if !config.check {
+        utils::cache::ensure_gitignore_entry(&config.path)?;
+    }
+
+    let output_root = config.output_root().to_path_buf();
+    let mut outcomes = Vec::with_capacity(workspace.members.len());
+    let mut stale_packages = 0;
+    let mut stale_reasons = Vec::new();
+    for member in &workspace.members {
+        if !config.quiet {
+            println!();
+            println!("==> {} ({})", member.name, member.path.display());
+        }
+
+        let mut package_config = config.clone();
+        package_config.path = config.path.join(&member.path);
+        package_config.output_dir = Some(output_root.join(&member.path));
+        package_config.output_paths.insert(
+            "cursor".to_string(),
+            output_root
+                .join(".cursor")
+                .join("rules")
+                .join(format!("{}.mdc", member.slug()))
+                .to_string_lossy()
+                .into_owned(),
+        );
+
+        let mut ctx = PipelineContext::new(package_config);
+        ctx.package_scope = Some(member.path.clone());
+        let result = run_context(&mut ctx).await;
+        if let Err(ref e) = result {
+            match e.downcast_ref::<utils::error::RuleyError>() {
+                Some(utils::error::RuleyError::StaleRules { reasons }) => {
+                    stale_packages += 1;
+                    stale_reasons.extend(reasons.iter().map(|r| format!("{}: {}", member.name, r)));
+                }
+                _ => tracing::error!("Failed to generate rules for {}: {:#}", member.name, e),
+            }
+        }
+
+        outcomes.push(output::PackageOutcome {
+            member: member.clone(),
+            files: ctx
+                .output_results
+                .iter()
+                .filter(|r| !r.skipped)
+                .map(|r| r.path.clone())
+                .collect(),
+            error: result.err().map(|e| format!("{e:#}")),
+        });
+    }
+
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    if !config.quiet {
+        println!();
+        println!("Workspace Summary");
+        println!("=================");
+        for outcome in &outcomes {
+            match outcome.error {
+                Some(ref error) => println!("  {} -> FAILED: {}", outcome.member.name, error),
+                None => println!(
+                    "  {} -> {} file(s)",
+                    outcome.member.name,
+                    outcome.files.len()
+                ),
+            }
+        }
+    }
+
+    if !config.dry_run && !config.token_report && !config.check {
+        let summary = output::render_workspace_summary(&workspace, &outcomes, &output_root);
+        let summary_path = output_root.join(output::SUMMARY_FILENAME);
+        std::fs::write(&summary_path, summary).with_context(|| {
+            format!(
+                "Failed to write workspace summary to {}",
+                summary_path.display()
+            )
+        })?;
+        if !config.quiet {
+            println!("  summary -> {}", summary_path.display());
+        }
+    }
+
+    // Report stale packages with the staleness exit code unless a package
+    // failed for another reason
+    if failed > 0 && failed == stale_packages {
+        return Err(utils::error::RuleyError::StaleRules {
+            reasons: stale_reasons,
+        }
+        .into());
+    }
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "Rule generation failed for {} of {} workspace package(s)",
+            failed,
+            outcomes.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Compare the scanned repository with the state recorded by the last run.
+///
+/// # Errors
+///
+/// Returns [`RuleyError::StaleRules`](utils::error::RuleyError::StaleRules) if no state was recorded, the sources
+/// or generation settings changed, or a rule file is missing or was edited.
+fn check_rules(ctx: &PipelineContext) -> Result<()> {
+    let Some(ref state) = ctx.loaded_state else {
+        return Err(utils::error::RuleyError::StaleRules {
+            reasons: vec!["No .ruley/state.json recorded by a previous run".to_string()],
+        }
+        .into());
+    };
+
+    let report = utils::staleness::check_staleness(
+        state,
+        &ctx.file_hashes,
+        &utils::staleness::config_fingerprint(&ctx.config),
+        &ctx.config.path,
+    );
+    if !ctx.config.quiet {
+        utils::staleness::display_staleness_report(&report)?;
+    }
+    if report.is_stale() {
+        return Err(utils::error::RuleyError::StaleRules {
+            reasons: report.reasons(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Validate the existing rule files against the scanned codebase.
+///
+/// # Errors
+///
+/// Returns an error if the repository has no rule files, no codebase is
+/// available to validate against, a results file cannot be written, or any
+/// rule file fails validation.
+fn run_validate(ctx: &PipelineContext, args: &cli::args::ValidateArgs) -> Result<()> {
+    let mut rule_files: Vec<(String, String)> =
+        utils::finalization::detect_existing_rules(&ctx.config.path, &[])
+            .into_iter()
+            .collect();
+    rule_files.sort();
+    if rule_files.is_empty() {
+        return Err(utils::error::RuleyError::Config(format!(
+            "No rule files found in {}",
+            ctx.config.path.display()
+        ))
+        .into());
+    }
+
+    let Some(ref codebase) = ctx.compressed_codebase else {
+        return Err(utils::error::RuleyError::Config(
+            "Cannot validate rule files: no scanned codebase to check file references against"
+                .to_string(),
+        )
+        .into());
+    };
+    let results =
+        utils::validation::validate_rule_files(&rule_files, &ctx.config.validation, codebase)?;
+    utils::validation::display_validation_report(&results, ctx.config.quiet);
+
+    if let Some(ref path) = args.json {
+        utils::validation::write_validation_json(&results, path)?;
+        tracing::info!("Validation results written to {}", path.display());
+    }
+    if let Some(ref path) = args.sarif {
+        utils::sarif::write_validation_sarif(&results, path)?;
+        tracing::info!("Validation results written to {}", path.display());
+    }
+
+    let failed = results.iter().filter(|r| !r.passed).count();
+    if failed > 0 {
+        return Err(utils::error::RuleyError::ValidationError {
+            message: format!(
+                "{} of {} rule file(s) failed validation",
+                failed,
+                results.len()
+            ),
+            suggestion: "Fix the errors listed in the validation report".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Reject generation-only flags that the drift command does not honor.
+///
+/// # Errors
+///
+/// Returns [`RuleyError::Config`](utils::error::RuleyError::Config) naming every
+/// `--dry-run`, `--token-report` or `--check` flag that was set.
+fn check_drift_flags(config: &MergedConfig) -> Result<()> {
+    let flags: Vec<&str> = [
+        ("--dry-run", config.dry_run),
+        ("--token-report", config.token_report),
+        ("--check", config.check),
+    ]
+    .into_iter()
+    .filter_map(|(flag, set)| set.then_some(flag))
+    .collect();
+    if flags.is_empty() {
+        return Ok(());
+    }
+    Err(utils::error::RuleyError::Config(format!(
+        "{} cannot be used with the drift command",
+        flags.join(", ")
+    ))
+    .into())
+}
+
+/// Report how the code has drifted from the existing rule files.
+///
+/// Samples the compressed codebase within the token budget (50,000 tokens by
+/// default) and asks the LLM for a verdict on every rule.
+///
+/// # Errors
+///
+/// Returns an error if the repository has no rule files, the LLM call fails,
+/// its response cannot be parsed, or a report file cannot be written.
+async fn run_drift(ctx: &mut PipelineContext, args: &cli::args::DriftArgs) -> Result<()> {
+    let root = ctx.config.path.clone();
+    let mut rule_files: Vec<(String, String)> =
+        utils::finalization::detect_existing_rules(&root, &[])
+            .into_iter()
+            .collect();
+    rule_files.sort();
+    if rule_files.is_empty() {
+        return Err(utils::error::RuleyError::Config(format!(
+            "No rule files found in {}; generate rules with ruley first",
+            root.display()
+        ))
+        .into());
+    }
+
+    let Some(codebase) = ctx.compressed_codebase.take() else {
+        return Err(anyhow::anyhow!(
+            "No compressed codebase available for drift analysis"
+        ));
+    };
+    let file_paths: Vec<String> = codebase
+        .files
+        .iter()
+        .map(|f| {
+            f.path
+                .strip_prefix(&root)
+                .unwrap_or(&f.path)
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+
+    let mut model_info = resolve_model_info(&ctx.config)?;
+    let tokenizer = get_tokenizer(&model_info)?;
+    // Leave room in the context window for the rule files and the file list
+    let budget = ctx
+        .config
+        .token_budget
+        .unwrap_or(utils::drift::DEFAULT_SAMPLE_TOKENS)
+        .min(model_info.context_window / 2);
+    let sample = utils::drift::sample_codebase(&codebase, budget, tokenizer.as_ref(), &root);
+    tracing::info!(
+        "Drift report: {} rule file(s), sampled {} of {} files",
+        rule_files.len(),
+        sample.metadata.total_files,
+        file_paths.len()
+    );
+
+    let prompt = generator::build_drift_prompt(&rule_files, &file_paths, &sample);
+    let client = create_llm_client(&ctx.config).await?;
+    model_info.pricing = client.pricing();
+    let calculator = CostCalculator::new(model_info.pricing.clone());
+    let estimate = calculator.estimate_cost(
+        tokenizer.count_tokens(&prompt),
+        utils::drift::ESTIMATED_REPORT_TOKENS.min(model_info.max_output_tokens),
+    );
+
+    if !ctx.config.quiet {
+        println!();
+        println!(
+            "Drift check of {} rule file(s) against {} of {} files",
+            rule_files.len(),
+            sample.metadata.total_files,
+            file_paths.len()
+        );
+        println!(
+            "Estimated cost: ${:.4} ({} input tokens)",
+            estimate.total_cost, estimate.input_tokens
+        );
+    }
+    if !ctx.config.no_confirm && !prompt_confirmation("Proceed with drift analysis?", true).await? {
+        tracing::info!("User cancelled operation");
+        return Ok(());
+    }
+
+    let messages = vec![llm::provider::Message {
+        role: "user".to_string(),
+        content: prompt,
+    }];
+    let response = client
+        .complete(&messages, &llm::provider::CompletionOptions::default())
+        .await
+        .context("Failed to generate drift report")?;
+    let mut tracker = CostTracker::new(calculator);
+    tracker.add_completion("drift", &response);
+
+    let mut findings = utils::drift::parse_drift_response(&response.content)?;
+    // The LLM sees only the file list; drop references that exist after all
+    findings.retain_missing_references(&root);
+
+    let report = utils::drift::DriftReport {
+        generated_at: Utc::now(),
+        rule_files: rule_files.into_iter().map(|(path, _)| path).collect(),
+        files_sampled: sample.metadata.total_files,
+        files_total: file_paths.len(),
+        rules: findings.rules,
+        uncovered_patterns: findings.uncovered_patterns,
+        missing_references: findings.missing_references,
+        cost: tracker.summary(),
+    };
+    ctx.cost_tracker = Some(tracker);
 
+    if !ctx.config.quiet {
+        utils::drift::display_drift_report(&report)?;
+    }
+    if let Some(ref path) = args.markdown {
+        std::fs::write(path, utils::drift::render_drift_markdown(&report))
+            .with_context(|| format!("Failed to write drift report to {}", path.display()))?;
+        tracing::info!("Drift report written to {}", path.display());
+    }
+    if let Some(ref path) = args.json {
+        utils::drift::write_drift_json(&report, path)?;
+        tracing::info!("Drift report written to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Run all pipeline stages against `ctx`.
+async fn run_pipeline(ctx: &mut PipelineContext) -> Result<()> {
     // Stage 1: Init (Configuration Validation)
     ctx.transition_to(PipelineStage::Init);
 
+    if matches!(ctx.config.command, Some(cli::args::Command::Drift(_))) {
+        check_drift_flags(&ctx.config)?;
+    }
+
+    // Clone remote repositories into a temporary directory
+    if let Some(url) = ctx
+        .config
+        .path
+        .to_str()
+        .filter(|p| packer::git::is_remote_url(p))
+    {
+        let url = url.to_string();
+        clone_remote_repository(ctx, &url).await?;
+    }
+
     // Validate repository path exists
     if !ctx.config.path.exists() {
         return Err(anyhow::anyhow!(
@@ -373,8 +890,14 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         tracing::info!("Cleaned up {} old temp files", cleanup_result.deleted);
     }
 
-    // Ensure .ruley/ is in .gitignore
-    utils::cache::ensure_gitignore_entry(&ctx.config.path)?;
+    // Ensure .ruley/ is in .gitignore (the workspace root's entry covers packages;
+    // check and validate leave the repository untouched)
+    if ctx.package_scope.is_none()
+        && !ctx.config.check
+        && !matches!(ctx.config.command, Some(cli::args::Command::Validate(_)))
+    {
+        utils::cache::ensure_gitignore_entry(&ctx.config.path)?;
+    }
 
     // Load previous state
     let loaded_state = utils::state::load_state(cache_manager.ruley_dir())?;
@@ -397,18 +920,23 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             pm.update(stages::SCANNING, 0, "discovering files...");
         }
 
-        let entries = packer::scan_files(&ctx.config.path, &ctx.config)
+        let scan = packer::scan_repository(&ctx.config.path, &ctx.config)
             .await
             .context("Failed to scan repository files")?;
 
         if let Some(ref pm) = ctx.progress_manager {
             pm.finish(
                 stages::SCANNING,
-                &format!("Scanned {} files", entries.len()),
+                &format!("Scanned {} files", scan.files.len()),
             );
         }
-        tracing::info!("Discovered {} files", entries.len());
-        entries
+        tracing::info!(
+            "Discovered {} files, skipped {}",
+            scan.files.len(),
+            scan.skipped.len()
+        );
+        ctx.skipped_files = scan.skipped;
+        scan.files
     };
 
     // Write scanned files to cache (for debugging/recovery)
@@ -452,14 +980,16 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         pm.update(stages::COMPRESSING, 0, "processing...");
     }
 
-    let compressed_codebase = if let Some(path) = &ctx.config.repomix_file {
+    let mut compressed_codebase = if let Some(path) = &ctx.config.repomix_file {
         packer::parse_repomix(path.as_path())
             .await
             .context("Failed to parse repomix file")?
     } else {
-        packer::compress_codebase(file_entries, &ctx.config)
+        let (codebase, skipped) = packer::compress_codebase_with_skipped(file_entries, &ctx.config)
             .await
-            .context("Failed to compress codebase")?
+            .context("Failed to compress codebase")?;
+        ctx.skipped_files.extend(skipped);
+        codebase
     };
 
     if let Some(ref pm) = ctx.progress_manager {
@@ -472,6 +1002,69 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         pm.finish(stages::COMPRESSING, &msg);
     }
 
+    // Redact secrets before any content can leave the machine
+    if ctx.config.secrets.enabled {
+        let scanner = packer::SecretScanner::from_config(&ctx.config.secrets)?;
+        ctx.secret_findings = packer::redact_codebase(&mut compressed_codebase, &scanner);
+        if !ctx.secret_findings.is_empty() {
+            tracing::warn!(
+                "Redacted {} potential secret(s) from the codebase",
+                ctx.secret_findings.len()
+            );
+        }
+    }
+
+    // Hash every file for the saved state; incremental runs then keep only
+    // the files changed since the last run
+    ctx.file_hashes = utils::incremental::hash_files(&compressed_codebase, &ctx.config.path);
+    if ctx.config.check {
+        return check_rules(ctx);
+    }
+    if ctx.config.incremental {
+        match ctx
+            .loaded_state
+            .as_ref()
+            .filter(|s| s.analysis.is_some() && !s.file_hashes.is_empty())
+        {
+            Some(state)
+                if state.config_hash.as_deref()
+                    != Some(utils::staleness::config_fingerprint(&ctx.config).as_str()) =>
+            {
+                tracing::warn!(
+                    "Generation settings changed since the last run, analyzing the whole repository"
+                );
+            }
+            Some(state) => {
+                // Rule files written by the last run are not sources
+                let changes = utils::incremental::diff_hashes(
+                    &utils::incremental::source_hashes(&state.file_hashes, &state.output_files),
+                    &utils::incremental::source_hashes(&ctx.file_hashes, &state.output_files),
+                );
+                let since = state
+                    .commit
+                    .as_deref()
+                    .map(|c| format!("commit {}", &c[..c.len().min(7)]))
+                    .unwrap_or_else(|| "the last run".to_string());
+                if changes.is_empty() {
+                    if !ctx.config.quiet {
+                        println!("No files changed since {}; rules are up to date", since);
+                    }
+                    return Ok(());
+                }
+                tracing::info!("Incremental run: {} since {}", changes, since);
+                if !ctx.config.quiet {
+                    println!("Changes since {}: {}", since, changes);
+                }
+                ctx.analysis_codebase =
+                    Some(changes.apply(compressed_codebase.clone(), &ctx.config.path));
+                ctx.file_changes = Some(changes);
+            }
+            None => tracing::warn!(
+                "No analysis recorded by a previous run, analyzing the whole repository"
+            ),
+        }
+    }
+
     ctx.compressed_codebase = Some(compressed_codebase);
 
     // Write compressed codebase summary to cache
@@ -490,52 +1083,155 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         tracing::debug!("Cached compressed codebase summary");
     }
 
+    match ctx.config.command {
+        Some(cli::args::Command::Drift(ref args)) => {
+            let args = args.clone();
+            // Drift sends sampled code to the LLM
+            check_secrets(ctx)?;
+            return run_drift(ctx, &args).await;
+        }
+        Some(cli::args::Command::Validate(ref args)) => {
+            let args = args.clone();
+            return run_validate(ctx, &args);
+        }
+        None => {}
+    }
+
+    // Fit the codebase into the token budget instead of chunking it, unless
+    // full coverage was asked for
+    if let Some(budget) = ctx.config.token_budget
+        && !ctx.config.full_coverage
+        && let Some(codebase) = ctx
+            .analysis_codebase
+            .as_ref()
+            .or(ctx.compressed_codebase.as_ref())
+    {
+        let model_info = resolve_model_info(&ctx.config)?;
+        let tokenizer = get_tokenizer(&model_info)?;
+        // The selected files share the context window with the analysis
+        // prompt and the response; a budget beyond what is left would still
+        // need chunking
+        let prompt_tokens = tokenizer.count_tokens(&generator::build_analysis_prompt(
+            &packer::CompressedCodebase::new(Vec::new()),
+            ctx.config.description.as_deref(),
+        ));
+        let output_tokens = llm::analysis::AnalysisOptions::default()
+            .max_tokens
+            .unwrap_or(0);
+        let budget = budget.min(
+            model_info
+                .context_window
+                .saturating_sub(prompt_tokens + output_tokens),
+        );
+
+        let selection = llm::selection::select_files(codebase, budget, tokenizer.as_ref());
+        tracing::info!(
+            "Token budget: selected {} of {} files ({} of {} tokens)",
+            selection.included.len(),
+            codebase.files.len(),
+            selection.included_tokens(),
+            budget
+        );
+        ctx.analysis_codebase = Some(selection.apply(codebase.clone()));
+        ctx.file_selection = Some(selection);
+    }
+
+    // Token report mode: show where the tokens go, then stop before any LLM call
+    if ctx.config.token_report {
+        if let Some(codebase) = ctx
+            .analysis_codebase
+            .as_ref()
+            .or(ctx.compressed_codebase.as_ref())
+        {
+            let model_info = resolve_model_info(&ctx.config)?;
+            let tokenizer = get_tokenizer(&model_info)?;
+            let report = build_token_report(codebase, &ctx.config.path, tokenizer.as_ref());
+            display_token_report(&report)?;
+            if let Some(ref path) = ctx.config.token_report_json {
+                write_token_report_json(&report, path)?;
+                tracing::info!("Token report written to {}", path.display());
+            }
+        }
+        return Ok(());
+    }
+
     // Check for dry-run mode (after scanning/compression so we can show file breakdown)
     if ctx.config.dry_run {
-        if let Some(ref codebase) = ctx.compressed_codebase {
-            // Try to get pricing from client, fall back to default pricing
-            // (dry-run shouldn't require API keys)
-            let pricing = match create_llm_client(&ctx.config).await {
-                Ok(client) => client.pricing(),
-                Err(_) => default_pricing(&ctx.config.provider),
-            };
+        if let Some(codebase) = ctx
+            .analysis_codebase
+            .as_ref()
+            .or(ctx.compressed_codebase.as_ref())
+        {
+            // Prefer the client's pricing (live for OpenRouter), but fall back to
+            // the registry since dry-run shouldn't require API keys
+            let mut model_info = resolve_model_info(&ctx.config)?;
+            if let Ok(client) = create_llm_client(&ctx.config).await {
+                model_info.pricing = client.pricing();
+            }
 
             // Convert Vec<String> to &[String] for display function
             let formats: Vec<String> = ctx.config.format.clone();
 
-            display_dry_run_summary(codebase, &formats, &ctx.config, &pricing)?;
+            display_dry_run_summary(
+                codebase,
+                &ctx.skipped_files,
+                &ctx.secret_findings,
+                ctx.file_selection.as_ref(),
+                &formats,
+                &ctx.config,
+                &model_info,
+            )?;
         }
+        check_secrets(ctx)?;
         return Ok(());
     }
 
+    check_secrets(ctx)?;
+
     // Stage 4: Analyzing
     ctx.transition_to(PipelineStage::Analyzing);
 
-    // Get the compressed codebase for analysis
+    // Get the compressed codebase for analysis, narrowed to the changed files
+    // or the budget if either applied
     let codebase = ctx
-        .compressed_codebase
+        .analysis_codebase
         .as_ref()
+        .or(ctx.compressed_codebase.as_ref())
         .ok_or_else(|| anyhow::anyhow!("No compressed codebase available for analysis"))?;
 
-    // Get the tokenizer for the provider
-    let tokenizer = get_tokenizer(&ctx.config.provider, ctx.config.model.as_deref())?;
+    // Resolve model limits and tokenizer from the registry
+    let mut model_info = resolve_model_info(&ctx.config)?;
+    let tokenizer = get_tokenizer(&model_info)?;
 
     // Calculate total tokens in the codebase
     let total_tokens = llm::tokenizer::calculate_tokens(codebase, tokenizer.as_ref());
     tracing::info!("Codebase contains {} tokens", total_tokens);
 
-    // Get context limit for the provider
-    let context_limit = get_context_limit(&ctx.config.provider, ctx.config.model.as_deref());
+    // Get the context limit for the model
+    let context_limit = model_info.context_window;
 
     // Determine chunk configuration
     let chunk_config = if let Some(ref chunking) = ctx.config.chunking {
         let chunk_size = chunking.chunk_size.unwrap_or(ctx.config.chunk_size);
         let overlap = chunking.overlap.unwrap_or(chunk_size / 10);
-        ChunkConfig::new(chunk_size, overlap).context("Invalid chunking configuration")?
+        ChunkConfig::new(chunk_size, overlap)
+            .context("Invalid chunking configuration")?
+            .with_strategy(chunking.strategy.unwrap_or_default())
     } else {
         ChunkConfig::with_chunk_size(ctx.config.chunk_size)
             .context("Invalid chunk size configuration")?
-    };
+    }
+    .fit_to_context(context_limit);
+
+    // Budget-selected files are analyzed in one request; only --full-coverage chunks
+    if ctx.file_selection.is_some() && total_tokens > context_limit {
+        return Err(utils::error::RuleyError::Config(format!(
+            "Files selected for --token-budget ({} tokens) exceed the {} token context window; \
+             lower the budget or use --full-coverage",
+            total_tokens, context_limit
+        ))
+        .into());
+    }
 
     // Chunk the codebase if needed
     let chunks = if total_tokens > context_limit {
@@ -556,12 +1252,23 @@ pub async fn run(config: MergedConfig) -> Result<()> {
     let client = create_llm_client(&ctx.config).await?;
 
     // Initialize cost tracker
-    let pricing = client.pricing();
-    let calculator = CostCalculator::new(pricing.clone());
+    model_info.pricing = client.pricing();
+    let calculator = CostCalculator::new(model_info.pricing.clone());
     ctx.cost_tracker = Some(CostTracker::new(calculator.clone()));
 
-    // Build the analysis prompt
-    let prompt = generator::build_analysis_prompt(codebase, ctx.config.description.as_deref());
+    // Build the analysis prompt; incremental runs update the previous analysis
+    let previous_analysis = ctx
+        .loaded_state
+        .as_ref()
+        .and_then(|s| s.analysis.as_deref());
+    let prompt = match (&ctx.file_changes, previous_analysis) {
+        (Some(changes), Some(previous)) => generator::build_incremental_prompt(
+            previous,
+            changes,
+            ctx.config.description.as_deref(),
+        ),
+        _ => generator::build_analysis_prompt(codebase, ctx.config.description.as_deref()),
+    };
 
     // Show cost estimation and confirm (unless --no-confirm)
     if !ctx.config.no_confirm {
@@ -571,7 +1278,7 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             &chunks,
             &ctx.config.format,
             &ctx.config.provider,
-            &pricing,
+            &model_info,
             ctx.config.quiet,
         )?;
 
@@ -588,27 +1295,38 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             &chunks,
             &ctx.config.format,
             &ctx.config.provider,
-            &pricing,
+            &model_info,
             false,
         )?;
     }
 
     // Start analyzing progress (spinner-based, indeterminate)
     if let Some(ref mut pm) = ctx.progress_manager {
-        let _ = pm.add_stage(stages::ANALYZING, 0);
+        let _ = pm.add_stage(stages::ANALYZING, chunks.len() as u64);
         pm.update(
             stages::ANALYZING,
             0,
             &format!("{} tokens sent", total_tokens),
         );
+        // Stream responses so received tokens show up on the spinner
+        client.set_token_progress(pm.token_progress(stages::ANALYZING));
     }
 
     // Perform the analysis
-    let analysis_result = perform_analysis(&mut ctx, &client, chunks, &prompt).await?;
+    let analysis_result = perform_analysis(
+        ctx,
+        &client,
+        tokenizer.as_ref(),
+        context_limit,
+        chunks,
+        &prompt,
+    )
+    .await?;
 
     if let Some(ref pm) = ctx.progress_manager {
         pm.finish(stages::ANALYZING, "Analysis complete");
     }
+    client.set_token_progress(None);
     tracing::info!("Analysis complete ({} characters)", analysis_result.len());
 
     // Parse the analysis into GeneratedRules structure
@@ -656,38 +1374,59 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         ctx.config.format.len()
     );
 
-    for (i, format) in ctx.config.format.iter().enumerate() {
-        if let Some(ref pm) = ctx.progress_manager {
-            pm.update(stages::FORMATTING, i as u64, format);
-        }
-        tracing::info!("Generating {} format rules", format);
-
-        // Use machine-readable slug for prompt logic (always_apply computation)
-        let rule_type_slug = ctx.config.rule_type.slug();
+    if let Some(ref mut pm) = ctx.progress_manager {
+        let _ = pm.add_stage(stages::FORMATTING, ctx.config.format.len() as u64);
+        pm.update(stages::FORMATTING, 0, &ctx.config.format.join(", "));
+        client.set_token_progress(pm.token_progress(stages::FORMATTING));
+    }
 
-        // Build refinement prompt for this format
-        let refinement_prompt =
-            generator::build_refinement_prompt(analysis, format, Some(rule_type_slug));
+    // Use machine-readable slug for prompt logic (always_apply computation)
+    let rule_type_slug = ctx.config.rule_type.slug();
+    let progress_manager = ctx.progress_manager.as_ref();
+    let completed = AtomicUsize::new(0);
+
+    // Refine formats concurrently; `buffered` keeps responses in format order
+    let responses: Vec<(&String, llm::provider::CompletionResponse)> =
+        stream::iter(&ctx.config.format)
+            .map(|format| {
+                let completed = &completed;
+                let client = &client;
+                async move {
+                    tracing::info!("Generating {} format rules", format);
+
+                    // Build refinement prompt for this format
+                    let refinement_prompt =
+                        generator::build_refinement_prompt(analysis, format, Some(rule_type_slug));
+
+                    // Create messages for LLM call
+                    let messages = vec![llm::provider::Message {
+                        role: "user".to_string(),
+                        content: refinement_prompt,
+                    }];
+
+                    // Call LLM to generate format-specific rules
+                    let response = client
+                        .complete(&messages, &llm::provider::CompletionOptions::default())
+                        .await
+                        .with_context(|| format!("Failed to generate {} format rules", format))?;
 
-        // Create messages for LLM call
-        let messages = vec![llm::provider::Message {
-            role: "user".to_string(),
-            content: refinement_prompt.clone(),
-        }];
+                    let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(pm) = progress_manager {
+                        pm.update(stages::FORMATTING, done as u64, &format!("{} done", format));
+                    }
+                    tracing::info!("Generated {} format rules successfully", format);
 
-        // Call LLM to generate format-specific rules
-        let response = client
-            .complete(&messages, &llm::provider::CompletionOptions::default())
-            .await
-            .with_context(|| format!("Failed to generate {} format rules", format))?;
+                    Ok::<_, anyhow::Error>((format, response))
+                }
+            })
+            .buffered(ctx.config.llm.max_concurrency.max(1))
+            .try_collect()
+            .await?;
 
+    for (format, response) in responses {
         // Track cost using provider-reported token counts
         if let Some(ref mut tracker) = ctx.cost_tracker {
-            tracker.add_operation(
-                format!("format_refinement_{}", format),
-                response.prompt_tokens,
-                response.completion_tokens,
-            );
+            tracker.add_completion(format!("format_refinement_{}", format), &response);
         }
 
         // Create formatted rules and add to the collection
@@ -697,8 +1436,6 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             ctx.config.rule_type,
         );
         rules.add_format(formatted_rules);
-
-        tracing::info!("Generated {} format rules successfully", format);
     }
 
     if let Some(ref pm) = ctx.progress_manager {
@@ -707,6 +1444,7 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             &format!("Generated {} format(s)", ctx.config.format.len()),
         );
     }
+    client.set_token_progress(None);
 
     // Log final cost summary after all formats processed
     if let Some(ref tracker) = ctx.cost_tracker {
@@ -905,6 +1643,22 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         tracing::info!("Validation disabled, skipping");
     }
 
+    // Package rules share the root `.cursor/rules/`, so limit their globs to the
+    // package (before finalization puts the metadata header above the frontmatter)
+    if let Some(ref scope) = ctx.package_scope
+        && let Some(cursor) = ctx
+            .generated_rules
+            .as_mut()
+            .and_then(|r| r.rules_by_format.get_mut("cursor"))
+    {
+        let scope = scope
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        cursor.content = output::cursor::scope_globs(&cursor.content, &scope);
+    }
+
     // Stage 7: Finalizing
     ctx.transition_to(PipelineStage::Finalizing);
 
@@ -921,7 +1675,7 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             &ctx.config.finalization,
             &client,
             &mut ctx.cost_tracker,
-            &ctx.config.path,
+            ctx.config.output_root(),
             &ctx.config.format,
             ctx.config.no_confirm,
             ctx.config.quiet,
@@ -1026,15 +1780,22 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         );
     }
 
-    // Parse conflict strategy from config
-    let conflict_strategy = ctx
+    // Parse conflict strategy from config; incremental runs update existing
+    // rule files unless told to overwrite or skip them
+    let conflict_strategy = match ctx
         .config
         .on_conflict
         .parse::<output::ConflictStrategy>()
-        .unwrap_or(output::ConflictStrategy::Prompt);
+        .unwrap_or(output::ConflictStrategy::Prompt)
+    {
+        output::ConflictStrategy::Prompt if ctx.file_changes.is_some() => {
+            output::ConflictStrategy::SmartMerge
+        }
+        strategy => strategy,
+    };
     let is_interactive = console::Term::stdout().is_term();
 
-    let write_options = output::WriteOptions::new(&ctx.config.path)
+    let write_options = output::WriteOptions::new(ctx.config.output_root())
         .with_output_paths(output_paths)
         .with_backups(true)
         .with_conflict_strategy(conflict_strategy)
@@ -1109,6 +1870,7 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             }
         }
     }
+    ctx.output_results = results;
 
     // Stage 9: Reporting
     ctx.transition_to(PipelineStage::Reporting);
@@ -1134,21 +1896,23 @@ pub async fn run(config: MergedConfig) -> Result<()> {
         }
     });
 
-    let actual_cost = ctx
+    let (actual_cost, cache_hits) = ctx
         .cost_tracker
         .as_ref()
-        .map(|t| t.summary().total_cost)
-        .unwrap_or(0.0);
+        .map(|t| (t.total_cost(), t.cache_hits()))
+        .unwrap_or((0.0, 0));
 
     let elapsed = ctx.start_time.elapsed();
 
     // Display success summary
     display_success_summary(
-        &results,
+        &ctx.output_results,
         files_analyzed,
         tokens_processed,
         compression_ratio,
         actual_cost,
+        cache_hits,
+        ctx.resume_summary.as_ref(),
         elapsed,
         ctx.config.quiet,
     )?;
@@ -1163,7 +1927,17 @@ pub async fn run(config: MergedConfig) -> Result<()> {
             version: utils::state::CURRENT_STATE_VERSION.to_string(),
             last_run: Utc::now(),
             user_selections: utils::state::UserSelections::default(),
-            output_files: results.iter().map(|r| r.path.clone()).collect(),
+            output_files: ctx
+                .output_results
+                .iter()
+                .filter(|r| !r.skipped)
+                .map(|r| {
+                    r.path
+                        .strip_prefix(&ctx.config.path)
+                        .unwrap_or(&r.path)
+                        .to_path_buf()
+                })
+                .collect(),
             cost_spent: ctx
                 .cost_tracker
                 .as_ref()
@@ -1179,6 +1953,10 @@ pub async fn run(config: MergedConfig) -> Result<()> {
                 .as_ref()
                 .map(|c| c.metadata.compression_ratio)
                 .unwrap_or(1.0),
+            commit: packer::git::head_commit(&ctx.config.path),
+            file_hashes: ctx.file_hashes.clone(),
+            analysis: ctx.analysis_result.clone(),
+            config_hash: Some(utils::staleness::config_fingerprint(&ctx.config)),
         };
 
         // Save state
@@ -1193,7 +1971,7 @@ pub async fn run(config: MergedConfig) -> Result<()> {
     }
 
     // Also call the existing cleanup_temp_files function for TempFileRefs
-    cleanup_temp_files(&mut ctx).context("Failed to cleanup temporary files")?;
+    cleanup_temp_files(ctx).context("Failed to cleanup temporary files")?;
 
     // Pipeline Complete
     ctx.transition_to(PipelineStage::Complete);
@@ -1202,6 +1980,71 @@ pub async fn run(config: MergedConfig) -> Result<()> {
     Ok(())
 }
 
+/// Clone a remote repository and point the pipeline at the clone.
+///
+/// The clone lives in a fresh directory under the system temp dir, named
+/// after the repository so it still reads as the project name, and is
+/// registered in [`TempFileRefs`] for removal when the run ends. Rule files
+/// must go to `--output-dir`, since the clone is thrown away.
+///
+/// # Errors
+///
+/// Returns an error if `--output-dir` is missing (outside `--dry-run` and
+/// `--token-report`) or the clone fails.
+async fn clone_remote_repository(ctx: &mut PipelineContext, url: &str) -> Result<()> {
+    if ctx.config.output_dir.is_none() && !ctx.config.dry_run && !ctx.config.token_report {
+        return Err(anyhow::anyhow!(
+            "--output-dir is required when analyzing a remote repository ({})",
+            url
+        ));
+    }
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let clone_root =
+        std::env::temp_dir().join(format!("ruley-clone-{}-{}", std::process::id(), nanos));
+    let dest = clone_root.join(packer::git::repo_name_from_url(url));
+    ctx.temp_files.add(clone_root);
+    ctx.cloned_from = Some(url.to_string());
+
+    tracing::info!("Cloning {}", url);
+    let options = packer::git::CloneOptions {
+        git_ref: ctx.config.git_ref.clone(),
+        depth: ctx.config.depth,
+    };
+    packer::git::clone_repo(url, &dest, &options)
+        .await
+        .with_context(|| format!("Failed to clone {}", url))?;
+
+    tracing::info!("Cloned {} into {}", url, dest.display());
+
+    ctx.config.path = dest;
+    Ok(())
+}
+
+/// Abort the run when secrets were found and `--fail-on-secrets` is set.
+fn check_secrets(ctx: &PipelineContext) -> Result<()> {
+    if !ctx.config.secrets.fail_on_secrets || ctx.secret_findings.is_empty() {
+        return Ok(());
+    }
+
+    let locations = ctx
+        .secret_findings
+        .iter()
+        .map(|finding| {
+            format!(
+                "{}:{} ({})",
+                finding.path.display(),
+                finding.line,
+                finding.pattern
+            )
+        })
+        .collect();
+    Err(utils::error::RuleyError::SecretsDetected { locations }.into())
+}
+
 /// Cleanup temporary files created during pipeline execution.
 fn cleanup_temp_files(ctx: &mut PipelineContext) -> Result<()> {
     let file_count = ctx.temp_files.len();
@@ -1213,81 +2056,23 @@ fn cleanup_temp_files(ctx: &mut PipelineContext) -> Result<()> {
     Ok(())
 }
 
-/// Get the appropriate tokenizer for the given provider.
-///
-/// Returns a boxed tokenizer that matches the provider's tokenization scheme.
-///
-/// # Arguments
+/// Get the tokenizer for the resolved model.
 ///
-/// * `provider` - The LLM provider name (e.g., "anthropic", "openai")
-/// * `model` - Optional model name for more precise tokenizer selection
+/// The registry picks the tiktoken encoding per model. Claude models use
+/// `cl100k_base` as an approximation, since Anthropic's tokenizer is not public.
 ///
 /// # Errors
 ///
 /// Returns an error if the tokenizer cannot be created.
-#[allow(unused_variables)]
-fn get_tokenizer(provider: &str, model: Option<&str>) -> Result<Box<dyn Tokenizer>> {
-    match provider.to_lowercase().as_str() {
-        #[cfg(feature = "anthropic")]
-        "anthropic" => Ok(Box::new(
-            AnthropicTokenizer::new().context("Failed to create Anthropic tokenizer")?,
-        )),
-        #[cfg(feature = "openai")]
-        "openai" => {
-            let tokenizer_model = model
-                .map(TokenizerModel::from_model_name)
-                .unwrap_or(TokenizerModel::Gpt4o);
-            Ok(Box::new(
-                TiktokenTokenizer::new(tokenizer_model)
-                    .context("Failed to create OpenAI tokenizer")?,
-            ))
-        }
-        #[cfg(feature = "ollama")]
-        "ollama" => {
-            // Ollama uses tiktoken-compatible tokenization
-            Ok(Box::new(
-                TiktokenTokenizer::new(TokenizerModel::Gpt4o)
-                    .context("Failed to create Ollama tokenizer")?,
-            ))
-        }
-        #[cfg(feature = "openrouter")]
-        "openrouter" => {
-            // OpenRouter uses model-specific tokenization, default to cl100k_base
-            Ok(Box::new(
-                TiktokenTokenizer::new(TokenizerModel::Gpt4o)
-                    .context("Failed to create OpenRouter tokenizer")?,
-            ))
-        }
-        // Default to cl100k_base for other providers (reasonable approximation)
-        _ => Ok(Box::new(
-            TiktokenTokenizer::new(TokenizerModel::Gpt4)
-                .context("Failed to create default tokenizer")?,
-        )),
-    }
-}
-
-/// Returns default pricing for a given provider when dynamic pricing is unavailable.
-///
-/// Used in dry-run mode where API keys may not be configured.
-fn default_pricing(provider: &str) -> llm::provider::Pricing {
-    match provider.to_lowercase().as_str() {
-        "anthropic" => llm::provider::Pricing {
-            input_per_1k: 0.003,
-            output_per_1k: 0.015,
-        },
-        "openai" => llm::provider::Pricing {
-            input_per_1k: 0.0025,
-            output_per_1k: 0.010,
-        },
-        "ollama" => llm::provider::Pricing {
-            input_per_1k: 0.0,
-            output_per_1k: 0.0,
-        },
-        _ => llm::provider::Pricing {
-            input_per_1k: 0.003,
-            output_per_1k: 0.015,
-        },
-    }
+fn get_tokenizer(model: &ModelInfo) -> Result<Box<dyn Tokenizer>> {
+    Ok(Box::new(
+        TiktokenTokenizer::new(model.tokenizer).with_context(|| {
+            format!(
+                "Failed to create {} tokenizer",
+                model.tokenizer.encoding_name()
+            )
+        })?,
+    ))
 }
 
 /// Create an LLM client based on the configuration.
@@ -1312,17 +2097,7 @@ async fn create_llm_client(config: &MergedConfig) -> Result<LLMClient> {
             let api_key = std::env::var("ANTHROPIC_API_KEY")
                 .context("ANTHROPIC_API_KEY environment variable not set")?;
 
-            let model = config
-                .model
-                .clone()
-                .or_else(|| {
-                    config
-                        .providers
-                        .anthropic
-                        .as_ref()
-                        .and_then(|p| p.model.clone())
-                })
-                .unwrap_or_else(|| "claude-sonnet-4-5-20250929".to_string());
+            let model = resolve_model_name(config).context("No model configured")?;
 
             Box::new(
                 AnthropicProvider::new(api_key, model)
@@ -1336,22 +2111,47 @@ async fn create_llm_client(config: &MergedConfig) -> Result<LLMClient> {
             let api_key = std::env::var("OPENAI_API_KEY")
                 .context("OPENAI_API_KEY environment variable not set")?;
 
-            let model = config
-                .model
-                .clone()
-                .or_else(|| {
-                    config
-                        .providers
-                        .openai
-                        .as_ref()
-                        .and_then(|p| p.model.clone())
-                })
-                .unwrap_or_else(|| "gpt-4o".to_string());
+            let model = resolve_model_name(config).context("No model configured")?;
 
             Box::new(
                 OpenAIProvider::new(api_key, model).context("Failed to create OpenAI provider")?,
             )
         }
+        #[cfg(feature = "openai")]
+        "openai-compatible" => {
+            use llm::providers::openai_compatible::OpenAICompatibleProvider;
+
+            let compat = config.providers.openai_compatible.as_ref();
+
+            let base_url = compat.and_then(|p| p.base_url.clone()).context(
+                "The openai-compatible provider requires `base_url` in [providers.openai-compatible]",
+            )?;
+
+            let model = resolve_model_name(config).context(
+                    "The openai-compatible provider requires a model (--model or `model` in [providers.openai-compatible])",
+                )?;
+
+            let mut provider = OpenAICompatibleProvider::new(base_url, model)
+                .context("Failed to create OpenAI-compatible provider")?;
+
+            if let Some(compat) = compat {
+                provider = provider
+                    .with_headers(&compat.headers)
+                    .context("Invalid headers in [providers.openai-compatible]")?;
+
+                if let Some(ref env_var) = compat.api_key_env {
+                    let api_key = std::env::var(env_var)
+                        .with_context(|| format!("{} environment variable not set", env_var))?;
+                    provider = provider.with_api_key(api_key);
+                }
+
+                if let Some(ref pricing) = compat.pricing {
+                    provider = provider.with_pricing(pricing.into());
+                }
+            }
+
+            Box::new(provider)
+        }
         #[cfg(feature = "ollama")]
         "ollama" => {
             use llm::providers::ollama::OllamaProvider;
@@ -1367,17 +2167,7 @@ async fn create_llm_client(config: &MergedConfig) -> Result<LLMClient> {
 
             let final_host = host_override.unwrap_or(host);
 
-            let model = config
-                .model
-                .clone()
-                .or_else(|| {
-                    config
-                        .providers
-                        .ollama
-                        .as_ref()
-                        .and_then(|p| p.model.clone())
-                })
-                .unwrap_or_else(|| "llama3.1:70b".to_string());
+            let model = resolve_model_name(config).context("No model configured")?;
 
             Box::new(
                 OllamaProvider::new(final_host, model)
@@ -1391,17 +2181,7 @@ async fn create_llm_client(config: &MergedConfig) -> Result<LLMClient> {
             let api_key = std::env::var("OPENROUTER_API_KEY")
                 .context("OPENROUTER_API_KEY environment variable not set")?;
 
-            let model = config
-                .model
-                .clone()
-                .or_else(|| {
-                    config
-                        .providers
-                        .openrouter
-                        .as_ref()
-                        .and_then(|p| p.model.clone())
-                })
-                .unwrap_or_else(|| "anthropic/claude-3.5-sonnet".to_string());
+            let model = resolve_model_name(config).context("No model configured")?;
 
             let provider = OpenRouterProvider::new(api_key, model)
                 .context("Failed to create OpenRouter provider")?;
@@ -1413,28 +2193,160 @@ async fn create_llm_client(config: &MergedConfig) -> Result<LLMClient> {
 
             Box::new(provider)
         }
+        #[cfg(feature = "xai")]
+        "xai" => {
+            use llm::providers::xai::XaiProvider;
+
+            let api_key =
+                std::env::var("XAI_API_KEY").context("XAI_API_KEY environment variable not set")?;
+
+            let model = resolve_model_name(config).context("No model configured")?;
+
+            Box::new(XaiProvider::new(api_key, model).context("Failed to create xAI provider")?)
+        }
+        #[cfg(feature = "groq")]
+        "groq" => {
+            use llm::providers::groq::GroqProvider;
+
+            let api_key = std::env::var("GROQ_API_KEY")
+                .context("GROQ_API_KEY environment variable not set")?;
+
+            let model = resolve_model_name(config).context("No model configured")?;
+
+            Box::new(GroqProvider::new(api_key, model).context("Failed to create Groq provider")?)
+        }
+        #[cfg(feature = "gemini")]
+        "gemini" => {
+            use llm::providers::gemini::GeminiProvider;
+
+            let api_key = std::env::var("GEMINI_API_KEY")
+                .context("GEMINI_API_KEY environment variable not set")?;
+
+            let model = resolve_model_name(config).context("No model configured")?;
+
+            Box::new(
+                GeminiProvider::new(api_key, model).context("Failed to create Gemini provider")?,
+            )
+        }
         provider => {
             return Err(anyhow::anyhow!(
-                "Unsupported provider '{}'. Supported providers: anthropic, openai, ollama, openrouter",
+                "Unsupported provider '{}'. Supported providers: anthropic, openai, openai-compatible, ollama, openrouter, xai, groq, gemini \
+                 (non-default providers require building with the matching cargo feature)",
                 provider
             ));
         }
     };
 
-    Ok(LLMClient::new(provider))
+    let mut client = LLMClient::new(provider);
+
+    if config.cache.enabled {
+        let cache = llm::response_cache::ResponseCache::new(
+            config.path.join(".ruley").join("cache"),
+            config.provider.to_lowercase(),
+            std::time::Duration::from_secs(config.cache.ttl_hours.saturating_mul(3600)),
+            config.cache.max_size_mb.saturating_mul(1024 * 1024),
+        );
+        tracing::debug!("LLM response cache enabled at {}", cache.dir().display());
+        client = client.with_response_cache(cache);
+    }
+
+    // OpenRouter prices are fetched live for the exact model, so the registry
+    // only replaces them when `ruley.toml` sets pricing explicitly.
+    let model_name = client.model().to_string();
+    let registry = model_registry(config)?;
+    if config.provider.eq_ignore_ascii_case("openrouter")
+        && !registry.has_pricing_override(&model_name)
+    {
+        return Ok(client);
+    }
+
+    let model_info = registry.resolve(&config.provider, Some(&model_name));
+    Ok(client.with_pricing(model_info.pricing))
+}
+
+/// Resolve the model name for the configured provider.
+///
+/// Uses `--model` (or `general.model`), then the provider section's `model`,
+/// then the provider's default model. Returns `None` for providers without a
+/// default (`openai-compatible`) when no model is configured.
+fn resolve_model_name(config: &MergedConfig) -> Option<String> {
+    let providers = &config.providers;
+    let (configured, default) = match config.provider.to_lowercase().as_str() {
+        "anthropic" => (
+            providers.anthropic.as_ref().and_then(|p| p.model.clone()),
+            Some("claude-sonnet-4-5-20250929"),
+        ),
+        "openai" => (
+            providers.openai.as_ref().and_then(|p| p.model.clone()),
+            Some("gpt-4o"),
+        ),
+        "openai-compatible" => (
+            providers
+                .openai_compatible
+                .as_ref()
+                .and_then(|p| p.model.clone()),
+            None,
+        ),
+        "ollama" => (
+            providers.ollama.as_ref().and_then(|p| p.model.clone()),
+            Some("llama3.1:70b"),
+        ),
+        "openrouter" => (
+            providers.openrouter.as_ref().and_then(|p| p.model.clone()),
+            Some("anthropic/claude-3.5-sonnet"),
+        ),
+        "xai" => (
+            providers.xai.as_ref().and_then(|p| p.model.clone()),
+            Some("grok-4"),
+        ),
+        "groq" => (
+            providers.groq.as_ref().and_then(|p| p.model.clone()),
+            Some("llama-3.3-70b-versatile"),
+        ),
+        "gemini" => (
+            providers.gemini.as_ref().and_then(|p| p.model.clone()),
+            Some("gemini-2.5-pro"),
+        ),
+        _ => (None, None),
+    };
+
+    config
+        .model
+        .clone()
+        .or(configured)
+        .or_else(|| default.map(str::to_string))
 }
 
-/// Get the context limit for the given provider and model.
+/// Build the model registry from `[models]` overrides in the configuration.
 ///
-/// Returns a reasonable default context limit for the provider.
-fn get_context_limit(provider: &str, _model: Option<&str>) -> usize {
-    match provider.to_lowercase().as_str() {
-        "anthropic" => 200_000,  // Claude models support 200K context
-        "openai" => 128_000,     // GPT-4o supports 128K context
-        "ollama" => 100_000,     // Conservative default for local models
-        "openrouter" => 128_000, // Varies by model, use conservative default
-        _ => 100_000,            // Conservative default
+/// For the `openai-compatible` provider, `context_window` and `pricing` from
+/// `[providers.openai-compatible]` apply to the configured model; a matching
+/// `[models."name"]` entry takes precedence field by field.
+fn model_registry(config: &MergedConfig) -> Result<ModelRegistry> {
+    let mut overrides = config.models.clone();
+
+    if config.provider.eq_ignore_ascii_case("openai-compatible")
+        && let Some(ref compat) = config.providers.openai_compatible
+        && let Some(model) = resolve_model_name(config)
+    {
+        let entry = overrides.entry(model).or_default();
+        entry.context_window = entry.context_window.or(compat.context_window);
+        if let Some(ref pricing) = compat.pricing {
+            entry.input_per_1k = entry.input_per_1k.or(Some(pricing.input_per_1k));
+            entry.output_per_1k = entry.output_per_1k.or(Some(pricing.output_per_1k));
+        }
     }
+
+    ModelRegistry::with_overrides(overrides).context("Invalid [models] configuration")
+}
+
+/// Resolve context window, output limit, pricing and tokenizer for the
+/// configured provider and model.
+///
+/// Works without API keys, so dry-run can use it directly.
+fn resolve_model_info(config: &MergedConfig) -> Result<ModelInfo> {
+    let model = resolve_model_name(config);
+    Ok(model_registry(config)?.resolve(&config.provider, model.as_deref()))
 }
 
 /// User choices when validation fails.
@@ -1485,12 +2397,16 @@ async fn prompt_validation_choice(quiet: bool, no_confirm: bool) -> Result<Valid
 
 /// Perform LLM analysis on the codebase.
 ///
-/// Handles both single-chunk and multi-chunk analysis paths.
+/// Handles both single-chunk and multi-chunk analysis paths. Chunk analyses
+/// are cached in `.ruley/` as they complete; with `--resume`, cached analyses
+/// of identical chunks are reused instead of requested again.
 ///
 /// # Arguments
 ///
 /// * `ctx` - The pipeline context with compressed codebase
 /// * `client` - The LLM client to use
+/// * `tokenizer` - Tokenizer for the active model, used to size merge prompts
+/// * `context_limit` - The model's context window in tokens
 /// * `chunks` - The chunks to analyze
 /// * `prompt` - The analysis prompt
 ///
@@ -1500,58 +2416,124 @@ async fn prompt_validation_choice(quiet: bool, no_confirm: bool) -> Result<Valid
 async fn perform_analysis(
     ctx: &mut PipelineContext,
     client: &LLMClient,
+    tokenizer: &dyn Tokenizer,
+    context_limit: usize,
     chunks: Vec<Chunk>,
     prompt: &str,
 ) -> Result<String> {
     let num_chunks = chunks.len();
+    let mut options = llm::analysis::AnalysisOptions {
+        max_concurrency: ctx.config.llm.max_concurrency,
+        ..Default::default()
+    };
+    // Leave room in the context window for the merge response (twice max_tokens)
+    let merge_output_tokens = options.max_tokens.unwrap_or(0).saturating_mul(2);
+    options.merge_token_budget = Some(context_limit.saturating_sub(merge_output_tokens));
+    let on_chunk_complete = ctx
+        .progress_manager
+        .as_ref()
+        .and_then(|pm| pm.task_progress(stages::ANALYZING));
 
-    if num_chunks == 1 {
-        tracing::info!("Analyzing codebase (single chunk, no merge required)");
-        let result = llm::analysis::analyze_chunked_with_results(chunks, prompt, client)
-            .await
-            .context("Failed to analyze codebase")?;
-
-        // Track the operation cost using provider-reported token counts
-        if let Some(ref mut tracker) = ctx.cost_tracker {
-            let total_prompt: usize = result.chunk_results.iter().map(|r| r.prompt_tokens).sum();
-            let total_completion: usize = result
-                .chunk_results
-                .iter()
-                .map(|r| r.completion_tokens)
-                .sum();
-            tracker.add_operation("analysis", total_prompt, total_completion);
-        }
+    // Persist every chunk analysis so an interrupted run can be resumed
+    let store = ctx.cache_manager.as_ref().map(|cache| {
+        utils::cache::ChunkResultCache::new(cache, prompt, client.model(), ctx.config.resume)
+    });
+    let store = store
+        .as_ref()
+        .map(|s| s as &dyn llm::analysis::ChunkResultStore);
 
-        Ok(result.merged_analysis)
+    let failure = if num_chunks == 1 {
+        tracing::info!("Analyzing codebase (single chunk, no merge required)");
+        "Failed to analyze codebase"
     } else {
         tracing::info!(
-            "Analyzing codebase in {} chunks with merge step",
-            num_chunks
+            "Analyzing codebase in {} chunks with merge step (up to {} concurrent requests)",
+            num_chunks,
+            options.max_concurrency.max(1)
         );
-        let result = llm::analysis::analyze_chunked_with_results(chunks, prompt, client)
-            .await
-            .context("Failed to analyze chunked codebase")?;
+        "Failed to analyze chunked codebase"
+    };
+    let result = llm::analysis::analyze_chunked_with_results(
+        chunks,
+        prompt,
+        client,
+        tokenizer,
+        &options,
+        store,
+        on_chunk_complete.as_deref(),
+    )
+    .await
+    .context(failure)?;
 
-        // Track cost using provider-reported token counts (includes all chunks + merge)
-        if let Some(ref mut tracker) = ctx.cost_tracker {
-            let total_prompt: usize = result.chunk_results.iter().map(|r| r.prompt_tokens).sum();
-            let total_completion: usize = result
-                .chunk_results
+    // Track cost using provider-reported token counts. Reused chunks cost
+    // nothing this run; their original cost is reported as savings instead.
+    if let Some(ref mut tracker) = ctx.cost_tracker {
+        let (reused, fresh): (Vec<_>, Vec<_>) = result.chunk_results.iter().partition(|r| r.reused);
+
+        let name = if num_chunks == 1 {
+            "analysis"
+        } else {
+            "chunked_analysis"
+        };
+        let (cached, requested): (Vec<&llm::analysis::ChunkResult>, Vec<_>) =
+            fresh.into_iter().partition(|r| r.cached);
+        if !requested.is_empty() {
+            let total_prompt: usize = requested.iter().map(|r| r.prompt_tokens).sum();
+            let total_completion: usize = requested.iter().map(|r| r.completion_tokens).sum();
+            tracker.add_operation(name, total_prompt, total_completion);
+        }
+        if !cached.is_empty() {
+            let total_prompt: usize = cached.iter().map(|r| r.prompt_tokens).sum();
+            let total_completion: usize = cached.iter().map(|r| r.completion_tokens).sum();
+            tracker.add_cached_operation(name, total_prompt, total_completion);
+        }
+
+        let final_level = result.merge_steps.last().map_or(0, |step| step.level);
+        for step in &result.merge_steps {
+            let name = if step.level == final_level {
+                "chunk_merge".to_string()
+            } else {
+                format!("chunk_merge_{}_{}", step.level, step.group + 1)
+            };
+            if step.cached {
+                tracker.add_cached_operation(name, step.prompt_tokens, step.completion_tokens);
+            } else {
+                tracker.add_operation(name, step.prompt_tokens, step.completion_tokens);
+            }
+        }
+
+        if !reused.is_empty() {
+            let cost_saved: f64 = reused
                 .iter()
-                .map(|r| r.completion_tokens)
+                .map(|r| {
+                    tracker
+                        .calculator()
+                        .calculate_cost(r.prompt_tokens, r.completion_tokens)
+                })
                 .sum();
-            // Add merge step tokens if present
-            let merge_prompt = result.merge_prompt_tokens;
-            let merge_completion = result.merge_completion_tokens;
-            tracker.add_operation(
-                "chunked_analysis",
-                total_prompt + merge_prompt,
-                total_completion + merge_completion,
+            tracing::info!(
+                "Reused {} of {} cached chunk analyses, saving ${:.4}",
+                reused.len(),
+                num_chunks,
+                cost_saved
             );
+            ctx.resume_summary = Some(ResumeSummary {
+                chunks_reused: reused.len(),
+                cost_saved,
+            });
         }
+    }
 
+    if result.merge_steps.len() > 1 {
+        tracing::info!(
+            "Merged chunk analyses in {} requests across {} levels",
+            result.merge_steps.len(),
+            result.merge_steps.last().map_or(0, |step| step.level)
+        );
+    }
+    if num_chunks > 1 {
         tracing::info!("Chunk analysis and merge completed");
-
-        Ok(result.merged_analysis)
     }
+
+    Ok(result.merged_analysis)
 }
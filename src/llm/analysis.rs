@@ -4,8 +4,9 @@
 //! Chunk analysis and merge logic for processing large codebases.
 //!
 //! This module provides functionality to process large codebases that have been
-//! split into chunks. It handles sequential analysis of each chunk and merging
-//! the results into a coherent final output.
+//! split into chunks. It analyzes chunks concurrently (up to
+//! [`AnalysisOptions::max_concurrency`] requests at a time) and merges the
+//! results into a coherent final output.
 //!
 //! # Example
 //!
@@ -23,9 +24,19 @@
 
 use crate::llm::chunker::Chunk;
 use crate::llm::client::LLMClient;
-use crate::llm::provider::{CompletionOptions, Message};
+use crate::llm::provider::{CompletionOptions, CompletionResponse, Message};
+use crate::llm::tokenizer::Tokenizer;
 use crate::utils::error::RuleyError;
-use tracing::{debug, info};
+use futures_util::{StreamExt, TryStreamExt, stream};
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{debug, info, warn};
+
+/// Default number of LLM requests allowed in flight at once.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// Callback invoked as chunk analyses complete, with `(completed, total)`.
+pub type ChunkProgress<'a> = dyn Fn(usize, usize) + Send + Sync + 'a;
 
 /// Result of analyzing a single chunk.
 ///
@@ -44,6 +55,13 @@ pub struct ChunkResult {
 
     /// Number of completion/output tokens used for this chunk analysis.
     pub completion_tokens: usize,
+
+    /// Whether this result was loaded from a [`ChunkResultStore`] instead of
+    /// requested from the LLM. Token counts are those of the original request.
+    pub reused: bool,
+
+    /// Whether the LLM response was served from the response cache.
+    pub cached: bool,
 }
 
 impl ChunkResult {
@@ -67,6 +85,8 @@ pub fn new(
             analysis,
             prompt_tokens,
             completion_tokens,
+            reused: false,
+            cached: false,
         }
     }
 
@@ -77,6 +97,20 @@ pub fn total_tokens(&self) -> usize {
     }
 }
 
+/// Storage for chunk analysis results, used to resume interrupted runs.
+///
+/// Results are stored as each chunk completes, so a failure part-way through
+/// a run keeps the analyses that already succeeded.
+pub trait ChunkResultStore: Send + Sync {
+    /// Return a previously stored analysis of `chunk`, if one may be reused.
+    fn load(&self, chunk: &Chunk) -> Option<ChunkResult>;
+
+    /// Store a freshly requested analysis of `chunk`.
+    ///
+    /// Failures are logged rather than returned; they only cost a future resume.
+    fn store(&self, chunk: &Chunk, result: &ChunkResult);
+}
+
 /// Configuration options for chunk analysis.
 #[derive(Debug, Clone)]
 pub struct AnalysisOptions {
@@ -85,6 +119,16 @@ pub struct AnalysisOptions {
 
     /// Temperature for LLM generation.
     pub temperature: Option<f32>,
+
+    /// Maximum number of chunk analyses in flight at once (values below 1 act as 1).
+    pub max_concurrency: usize,
+
+    /// Token budget for a single merge prompt.
+    ///
+    /// When the combined chunk analyses exceed it, they are merged in groups
+    /// and the group summaries are merged recursively. `None` merges all
+    /// analyses in one request.
+    pub merge_token_budget: Option<usize>,
 }
 
 impl Default for AnalysisOptions {
@@ -92,6 +136,8 @@ fn default() -> Self {
         Self {
             max_tokens: Some(4096),
             temperature: Some(0.3),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            merge_token_budget: None,
         }
     }
 }
@@ -105,10 +151,36 @@ fn from(opts: &AnalysisOptions) -> Self {
     }
 }
 
+/// A single merge request made while combining chunk analyses.
+///
+/// Large chunk counts are merged as a tree: level 1 merges groups of chunk
+/// analyses, level 2 merges the level 1 summaries, and so on. The last step
+/// is always the final merge.
+#[derive(Debug, Clone)]
+pub struct MergeStep {
+    /// Tree level of this merge (1 for merges of chunk analyses).
+    pub level: usize,
+
+    /// Index of the merged group within its level (0-indexed).
+    pub group: usize,
+
+    /// Number of analyses combined by this merge.
+    pub inputs: usize,
+
+    /// Number of prompt/input tokens used for this merge.
+    pub prompt_tokens: usize,
+
+    /// Number of completion/output tokens used for this merge.
+    pub completion_tokens: usize,
+
+    /// Whether the merge response was served from the response cache.
+    pub cached: bool,
+}
+
 /// Result of a full analysis including per-chunk token counts.
 ///
 /// Contains the merged analysis text along with individual chunk results
-/// and merge-step token counts, enabling accurate cost tracking from
+/// and every merge request made, enabling accurate cost tracking from
 /// provider-reported values.
 #[derive(Debug, Clone)]
 pub struct AnalysisResult {
@@ -116,20 +188,33 @@ pub struct AnalysisResult {
     pub merged_analysis: String,
     /// Per-chunk results with provider-reported token counts.
     pub chunk_results: Vec<ChunkResult>,
-    /// Prompt tokens used in the merge step (0 if single chunk).
-    pub merge_prompt_tokens: usize,
-    /// Completion tokens used in the merge step (0 if single chunk).
-    pub merge_completion_tokens: usize,
+    /// Merge requests in the order they completed (empty if single chunk).
+    pub merge_steps: Vec<MergeStep>,
 }
 
 /// Analyze a codebase and return detailed results with per-chunk token counts.
 ///
-/// Like [`analyze_chunked`] but returns an [`AnalysisResult`] with provider-reported
-/// token counts for accurate cost tracking.
+/// Like [`analyze_chunked_with_options`] but returns an [`AnalysisResult`] with
+/// provider-reported token counts for accurate cost tracking.
+///
+/// `on_chunk_complete`, when given, is called with `(completed, total)` each
+/// time a chunk analysis finishes (chunks may finish out of order).
+///
+/// Merge prompts are measured with `tokenizer`; when they exceed
+/// [`AnalysisOptions::merge_token_budget`] the analyses are merged
+/// hierarchically (see [`merge_hierarchically`]).
+///
+/// With a `store`, chunks it already holds results for are not sent to the
+/// LLM (their results are marked [`ChunkResult::reused`]), and every new
+/// chunk analysis is stored as soon as it completes.
 pub async fn analyze_chunked_with_results(
     chunks: Vec<Chunk>,
     prompt_template: &str,
     client: &LLMClient,
+    tokenizer: &dyn Tokenizer,
+    options: &AnalysisOptions,
+    store: Option<&dyn ChunkResultStore>,
+    on_chunk_complete: Option<&ChunkProgress<'_>>,
 ) -> Result<AnalysisResult, RuleyError> {
     if chunks.is_empty() {
         return Err(RuleyError::ValidationError {
@@ -139,77 +224,78 @@ pub async fn analyze_chunked_with_results(
     }
 
     let total_chunks = chunks.len();
-    let options = AnalysisOptions::default();
 
     if total_chunks == 1 {
         info!("Analyzing single chunk (no merge required)");
         let chunk = &chunks[0];
-        let prompt = build_single_chunk_prompt(prompt_template, &chunk.content);
-        let messages = vec![Message {
-            role: "user".to_string(),
-            content: prompt,
-        }];
-
-        let completion_options = CompletionOptions::from(&options);
-        let response = client.complete(&messages, &completion_options).await?;
-
-        debug!(
-            prompt_tokens = response.prompt_tokens,
-            completion_tokens = response.completion_tokens,
-            "Single chunk analysis complete"
-        );
-
-        let chunk_result = ChunkResult::new(
-            chunk.id,
-            response.content.clone(),
-            response.prompt_tokens,
-            response.completion_tokens,
-        );
+        let chunk_result = match load_stored(store, chunk) {
+            Some(result) => result,
+            None => {
+                let prompt = build_single_chunk_prompt(prompt_template, &chunk.content);
+                let messages = vec![Message {
+                    role: "user".to_string(),
+                    content: prompt,
+                }];
+
+                let completion_options = CompletionOptions::from(options);
+                let response = client.complete(&messages, &completion_options).await?;
+
+                debug!(
+                    prompt_tokens = response.prompt_tokens,
+                    completion_tokens = response.completion_tokens,
+                    "Single chunk analysis complete"
+                );
+
+                let mut result = ChunkResult::new(
+                    chunk.id,
+                    response.content,
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                result.cached = response.cached;
+                if let Some(store) = store {
+                    store.store(chunk, &result);
+                }
+                result
+            }
+        };
+        if let Some(on_chunk_complete) = on_chunk_complete {
+            on_chunk_complete(1, 1);
+        }
 
         return Ok(AnalysisResult {
-            merged_analysis: response.content,
+            merged_analysis: chunk_result.analysis.clone(),
             chunk_results: vec![chunk_result],
-            merge_prompt_tokens: 0,
-            merge_completion_tokens: 0,
+            merge_steps: Vec::new(),
         });
     }
 
     info!(total_chunks = total_chunks, "Analyzing multiple chunks");
 
-    let chunk_results =
-        analyze_chunks_sequentially(&chunks, prompt_template, client, &options).await?;
-
-    // Merge all chunk results, capturing merge-step token counts
-    let merge_prompt = build_merge_prompt(&chunk_results);
-    let merge_messages = vec![Message {
-        role: "user".to_string(),
-        content: merge_prompt,
-    }];
-
-    let merge_options = CompletionOptions {
-        max_tokens: options.max_tokens.map(|t| t.saturating_mul(2)),
-        temperature: options.temperature,
-    };
+    let chunk_results = analyze_chunks_concurrently(
+        &chunks,
+        prompt_template,
+        client,
+        options,
+        store,
+        on_chunk_complete,
+    )
+    .await?;
 
-    let merge_response = client.complete(&merge_messages, &merge_options).await?;
-
-    debug!(
-        prompt_tokens = merge_response.prompt_tokens,
-        completion_tokens = merge_response.completion_tokens,
-        "Chunk results merged successfully"
-    );
+    // Merge all chunk results, capturing every merge step's token counts
+    let (merged_analysis, merge_steps) =
+        merge_hierarchically(&chunk_results, client, tokenizer, options).await?;
 
     Ok(AnalysisResult {
-        merged_analysis: merge_response.content,
+        merged_analysis,
         chunk_results,
-        merge_prompt_tokens: merge_response.prompt_tokens,
-        merge_completion_tokens: merge_response.completion_tokens,
+        merge_steps,
     })
 }
 
 /// Analyze a codebase that has been split into chunks.
 ///
-/// Processes each chunk sequentially, building context-aware prompts that
+/// Processes chunks concurrently, building context-aware prompts that
 /// include the chunk's position (N of M). After all chunks are analyzed,
 /// merges the results into a coherent final output.
 ///
@@ -303,13 +389,17 @@ pub async fn analyze_chunked_with_options(
     info!(total_chunks = total_chunks, "Analyzing multiple chunks");
 
     let chunk_results =
-        analyze_chunks_sequentially(&chunks, prompt_template, client, options).await?;
+        analyze_chunks_concurrently(&chunks, prompt_template, client, options, None, None).await?;
 
     // Merge all chunk results
     merge_chunk_results(chunk_results, client, options).await
 }
 
-/// Analyze chunks sequentially, building context-aware prompts.
+/// Analyze chunks concurrently, building context-aware prompts.
+///
+/// At most `options.max_concurrency` requests are in flight at once. Results
+/// are returned in input order (by `chunk_id`) regardless of completion order,
+/// and the first failure cancels the remaining requests.
 ///
 /// # Arguments
 ///
@@ -317,54 +407,91 @@ pub async fn analyze_chunked_with_options(
 /// * `prompt_template` - The base prompt template
 /// * `client` - The LLM client
 /// * `options` - Analysis options
+/// * `store` - Optional store of results to reuse and persist
+/// * `on_chunk_complete` - Optional `(completed, total)` progress callback
 ///
 /// # Returns
 ///
 /// A vector of chunk results, one for each input chunk.
-async fn analyze_chunks_sequentially(
+async fn analyze_chunks_concurrently(
     chunks: &[Chunk],
     prompt_template: &str,
     client: &LLMClient,
     options: &AnalysisOptions,
+    store: Option<&dyn ChunkResultStore>,
+    on_chunk_complete: Option<&ChunkProgress<'_>>,
 ) -> Result<Vec<ChunkResult>, RuleyError> {
     let total_chunks = chunks.len();
-    let mut results = Vec::with_capacity(total_chunks);
     let completion_options = CompletionOptions::from(options);
+    let completed = AtomicUsize::new(0);
+
+    stream::iter(chunks)
+        .map(|chunk| {
+            let completion_options = &completion_options;
+            let completed = &completed;
+            async move {
+                let chunk_number = chunk.id + 1; // 1-indexed for human readability
+                if let Some(result) = load_stored(store, chunk) {
+                    let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(on_chunk_complete) = on_chunk_complete {
+                        on_chunk_complete(done, total_chunks);
+                    }
+                    return Ok(result);
+                }
+
+                debug!(
+                    chunk = chunk_number,
+                    total = total_chunks,
+                    tokens = chunk.token_count,
+                    "Analyzing chunk"
+                );
+
+                let prompt =
+                    build_chunk_prompt(prompt_template, &chunk.content, chunk_number, total_chunks);
+                let messages = vec![Message {
+                    role: "user".to_string(),
+                    content: prompt,
+                }];
+
+                let response = client.complete(&messages, completion_options).await?;
+
+                debug!(
+                    chunk = chunk_number,
+                    prompt_tokens = response.prompt_tokens,
+                    completion_tokens = response.completion_tokens,
+                    "Chunk analysis complete"
+                );
+                let mut result = ChunkResult::new(
+                    chunk.id,
+                    response.content,
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                result.cached = response.cached;
+                if let Some(store) = store {
+                    store.store(chunk, &result);
+                }
+
+                let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                if let Some(on_chunk_complete) = on_chunk_complete {
+                    on_chunk_complete(done, total_chunks);
+                }
+
+                Ok::<_, RuleyError>(result)
+            }
+        })
+        .buffered(options.max_concurrency.max(1))
+        .try_collect()
+        .await
+}
 
-    for chunk in chunks {
-        let chunk_number = chunk.id + 1; // 1-indexed for human readability
-        debug!(
-            chunk = chunk_number,
-            total = total_chunks,
-            tokens = chunk.token_count,
-            "Analyzing chunk"
-        );
-
-        let prompt =
-            build_chunk_prompt(prompt_template, &chunk.content, chunk_number, total_chunks);
-        let messages = vec![Message {
-            role: "user".to_string(),
-            content: prompt,
-        }];
-
-        let response = client.complete(&messages, &completion_options).await?;
-
-        debug!(
-            chunk = chunk_number,
-            prompt_tokens = response.prompt_tokens,
-            completion_tokens = response.completion_tokens,
-            "Chunk analysis complete"
-        );
-
-        results.push(ChunkResult::new(
-            chunk.id,
-            response.content,
-            response.prompt_tokens,
-            response.completion_tokens,
-        ));
-    }
-
-    Ok(results)
+/// Load a stored result for `chunk`, marked as reused and renumbered to it.
+fn load_stored(store: Option<&dyn ChunkResultStore>, chunk: &Chunk) -> Option<ChunkResult> {
+    let mut result = store?.load(chunk)?;
+    debug!(chunk = chunk.id + 1, "Reusing stored chunk analysis");
+    result.chunk_id = chunk.id;
+    result.reused = true;
+    Some(result)
 }
 
 /// Merge multiple chunk analysis results into a coherent final output.
@@ -420,27 +547,195 @@ pub async fn merge_chunk_results(
         "Merging chunk analysis results"
     );
 
-    let prompt = build_merge_prompt(&chunk_results);
+    let merge_options = merge_completion_options(options);
+    let response = request_merge(&chunk_results, 1, client, &merge_options).await?;
+
+    Ok(response.content)
+}
+
+/// Merge chunk analyses as a tree so no merge prompt exceeds the token budget.
+///
+/// When the merge prompt for all analyses fits within
+/// [`AnalysisOptions::merge_token_budget`] (or no budget is set), this makes a
+/// single merge request. Otherwise the analyses are split into consecutive
+/// groups that each fit, the groups are merged concurrently, and the group
+/// summaries are merged recursively until one request can combine them.
+///
+/// # Arguments
+///
+/// * `chunk_results` - The analysis results from each chunk, in `chunk_id` order
+/// * `client` - The LLM client for making merge requests
+/// * `tokenizer` - Tokenizer used to measure merge prompts
+/// * `options` - Analysis options
+///
+/// # Returns
+///
+/// The final merged analysis and every merge request made, level by level.
+///
+/// # Errors
+///
+/// Returns an error if there are no results to merge or any merge call fails.
+pub async fn merge_hierarchically(
+    chunk_results: &[ChunkResult],
+    client: &LLMClient,
+    tokenizer: &dyn Tokenizer,
+    options: &AnalysisOptions,
+) -> Result<(String, Vec<MergeStep>), RuleyError> {
+    if chunk_results.is_empty() {
+        return Err(RuleyError::ValidationError {
+            message: "No chunk results to merge".to_string(),
+            suggestion: "Ensure chunks were analyzed before merging".to_string(),
+        });
+    }
+
+    let merge_options = merge_completion_options(options);
+    let mut steps = Vec::new();
+    let mut current: Vec<ChunkResult> = chunk_results.to_vec();
+    let mut level = 1;
+
+    loop {
+        if let [single] = &current[..] {
+            return Ok((single.analysis.clone(), steps));
+        }
+
+        let groups = group_for_merge(&current, level, tokenizer, options.merge_token_budget);
+        if groups.len() > 1 {
+            info!(
+                level = level,
+                num_results = current.len(),
+                groups = groups.len(),
+                "Merge prompt exceeds token budget, merging in groups"
+            );
+        } else {
+            info!(
+                level = level,
+                num_results = current.len(),
+                "Merging chunk analysis results"
+            );
+        }
+
+        let level_results = &current;
+        let merge_options = &merge_options;
+        let merged: Vec<(String, Option<MergeStep>)> = stream::iter(groups)
+            .enumerate()
+            .map(|(group, range)| async move {
+                let group_results = &level_results[range];
+                // A lone analysis has nothing to merge with; carry it up a level
+                if let [single] = group_results {
+                    return Ok::<_, RuleyError>((single.analysis.clone(), None));
+                }
+
+                let response = request_merge(group_results, level, client, merge_options).await?;
+                let step = MergeStep {
+                    level,
+                    group,
+                    inputs: group_results.len(),
+                    prompt_tokens: response.prompt_tokens,
+                    completion_tokens: response.completion_tokens,
+                    cached: response.cached,
+                };
+                Ok((response.content, Some(step)))
+            })
+            .buffered(options.max_concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        current = merged
+            .into_iter()
+            .enumerate()
+            .map(|(group, (analysis, step))| {
+                steps.extend(step);
+                ChunkResult::new(group, analysis, 0, 0)
+            })
+            .collect();
+        level += 1;
+    }
+}
+
+/// Send one merge request combining `results` at merge tree `level`.
+async fn request_merge(
+    results: &[ChunkResult],
+    level: usize,
+    client: &LLMClient,
+    merge_options: &CompletionOptions,
+) -> Result<CompletionResponse, RuleyError> {
     let messages = vec![Message {
         role: "user".to_string(),
-        content: prompt,
+        content: build_merge_prompt(results, level),
     }];
 
-    // Use higher max_tokens for merge since we're combining multiple analyses
-    let merge_options = CompletionOptions {
-        max_tokens: options.max_tokens.map(|t| t.saturating_mul(2)),
-        temperature: options.temperature,
-    };
-
-    let response = client.complete(&messages, &merge_options).await?;
+    let response = client.complete(&messages, merge_options).await?;
 
     debug!(
+        inputs = results.len(),
         prompt_tokens = response.prompt_tokens,
         completion_tokens = response.completion_tokens,
         "Chunk results merged successfully"
     );
 
-    Ok(response.content)
+    Ok(response)
+}
+
+/// Split results into consecutive groups whose merge prompts fit the budget.
+///
+/// Every group except possibly the last holds at least two results so each
+/// level of the merge tree shrinks, even if that means exceeding the budget
+/// when single analyses are very large.
+///
+/// # Arguments
+///
+/// * `results` - The analyses to group
+/// * `level` - Merge tree level the groups are merged at
+/// * `tokenizer` - Tokenizer used to measure merge prompts
+/// * `budget` - Token budget per merge prompt (`None` for a single group)
+fn group_for_merge(
+    results: &[ChunkResult],
+    level: usize,
+    tokenizer: &dyn Tokenizer,
+    budget: Option<usize>,
+) -> Vec<Range<usize>> {
+    let Some(budget) = budget else {
+        return std::iter::once(0..results.len()).collect();
+    };
+
+    // Prompt instructions are shared; each analysis adds its own wrapped text
+    let overhead = tokenizer.count_tokens(&build_merge_prompt(&[], level));
+    let sizes: Vec<usize> = results
+        .iter()
+        .map(|r| tokenizer.count_tokens(&format_chunk_analysis(r)))
+        .collect();
+
+    let mut groups = Vec::new();
+    let mut start = 0;
+    let mut used = overhead;
+    for (i, &size) in sizes.iter().enumerate() {
+        if i - start >= 2 && used + size > budget {
+            groups.push(start..i);
+            start = i;
+            used = overhead;
+        }
+        used += size;
+    }
+    if used > budget && sizes.len() - start > 1 {
+        warn!(
+            tokens = used,
+            budget = budget,
+            "Merge prompt exceeds token budget; analyses are too large to split further"
+        );
+    }
+    groups.push(start..sizes.len());
+
+    groups
+}
+
+/// Completion options for merge requests.
+///
+/// Uses a higher `max_tokens` since a merge combines multiple analyses.
+fn merge_completion_options(options: &AnalysisOptions) -> CompletionOptions {
+    CompletionOptions {
+        max_tokens: options.max_tokens.map(|t| t.saturating_mul(2)),
+        temperature: options.temperature,
+    }
 }
 
 /// Build a prompt for analyzing a single chunk (when no chunking is needed).
@@ -489,37 +784,48 @@ fn build_chunk_prompt(
 /// Build the merge prompt for combining chunk analyses.
 ///
 /// Instructs the LLM to synthesize multiple partial analyses into a coherent
-/// whole, deduplicating insights and combining observations.
+/// whole, deduplicating insights and combining observations. Above level 1
+/// the inputs are described as partial summaries rather than chunks.
 ///
 /// # Arguments
 ///
-/// * `chunk_results` - The analysis results from all chunks
-fn build_merge_prompt(chunk_results: &[ChunkResult]) -> String {
-    let mut analyses = String::new();
-
-    for result in chunk_results {
-        analyses.push_str(&format!(
-            "<chunk_analysis id=\"{}\">\n{}\n</chunk_analysis>\n\n",
-            result.chunk_id + 1,
-            result.analysis
-        ));
-    }
+/// * `chunk_results` - The analysis results to merge
+/// * `level` - Merge tree level (1 for merges of chunk analyses)
+fn build_merge_prompt(chunk_results: &[ChunkResult], level: usize) -> String {
+    let analyses: String = chunk_results.iter().map(format_chunk_analysis).collect();
+    let (inputs, input, origin) = if level <= 1 {
+        ("chunks", "chunk", "Each chunk was analyzed separately.")
+    } else {
+        (
+            "partial summaries",
+            "summary",
+            "Each summary already combines the analyses of several chunks.",
+        )
+    };
 
     format!(
-        "You are merging the analysis results from {count} chunks of a large codebase.\n\
-        Each chunk was analyzed separately. Your task is to:\n\n\
+        "You are merging the analysis results from {count} {inputs} of a large codebase.\n\
+        {origin} Your task is to:\n\n\
         1. **Synthesize** all insights into a coherent, unified analysis\n\
         2. **Deduplicate** any repeated observations or rules\n\
         3. **Combine** similar conventions or patterns into single, comprehensive rules\n\
         4. **Resolve conflicts** by choosing the most specific or accurate insight\n\
-        5. **Preserve** important details that appear in only one chunk\n\n\
+        5. **Preserve** important details that appear in only one {input}\n\n\
         Output a single, well-organized analysis that reads as if the entire codebase was analyzed at once.\n\
         Do not mention chunks or the merge process in your output.\n\n\
         <chunk_analyses>\n\
         {analyses}\
         </chunk_analyses>",
         count = chunk_results.len(),
-        analyses = analyses
+    )
+}
+
+/// Format one analysis as a `<chunk_analysis>` element for the merge prompt.
+fn format_chunk_analysis(result: &ChunkResult) -> String {
+    format!(
+        "<chunk_analysis id=\"{}\">\n{}\n</chunk_analysis>\n\n",
+        result.chunk_id + 1,
+        result.analysis
     )
 }
 
@@ -527,6 +833,7 @@ fn build_merge_prompt(chunk_results: &[ChunkResult]) -> String {
 mod tests {
     use super::*;
     use crate::llm::provider::{CompletionResponse, LLMProvider, Pricing};
+    use crate::llm::test_support::WordTokenizer;
     use async_trait::async_trait;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -581,6 +888,7 @@ fn create_test_chunk(id: usize, content: &str) -> Chunk {
             content: content.to_string(),
             token_count: content.split_whitespace().count(),
             overlap_token_count: 0,
+            files: Vec::new(),
         }
     }
 
@@ -599,6 +907,8 @@ fn test_analysis_options_default() {
         let opts = AnalysisOptions::default();
         assert_eq!(opts.max_tokens, Some(4096));
         assert_eq!(opts.temperature, Some(0.3));
+        assert_eq!(opts.max_concurrency, DEFAULT_MAX_CONCURRENCY);
+        assert_eq!(opts.merge_token_budget, None);
     }
 
     #[test]
@@ -635,7 +945,7 @@ fn test_build_merge_prompt() {
             ChunkResult::new(1, "Analysis 2".to_string(), 50, 50),
         ];
 
-        let prompt = build_merge_prompt(&results);
+        let prompt = build_merge_prompt(&results, 1);
 
         assert!(prompt.contains("merging the analysis results from 2 chunks"));
         assert!(prompt.contains("<chunk_analysis id=\"1\">"));
@@ -644,6 +954,12 @@ fn test_build_merge_prompt() {
         assert!(prompt.contains("Analysis 2"));
         assert!(prompt.contains("Deduplicate"));
         assert!(prompt.contains("Synthesize"));
+
+        // Higher levels merge group summaries, not chunks
+        let prompt = build_merge_prompt(&results, 2);
+        assert!(prompt.contains("merging the analysis results from 2 partial summaries"));
+        assert!(prompt.contains("appear in only one summary"));
+        assert!(!prompt.contains("from 2 chunks"));
     }
 
     #[tokio::test]
@@ -740,6 +1056,8 @@ async fn test_analyze_with_custom_options() {
         let options = AnalysisOptions {
             max_tokens: Some(8192),
             temperature: Some(0.5),
+            max_concurrency: 1,
+            merge_token_budget: None,
         };
 
         let result = analyze_chunked_with_options(chunks, "Analyze this", &client, &options).await;
@@ -747,4 +1065,241 @@ async fn test_analyze_with_custom_options() {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "Custom analysis");
     }
+
+    /// Mock provider that answers later chunks faster and records peak concurrency.
+    struct MockConcurrentProvider {
+        in_flight: AtomicUsize,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl LLMProvider for MockConcurrentProvider {
+        async fn complete(
+            &self,
+            messages: &[Message],
+            _options: &CompletionOptions,
+        ) -> Result<CompletionResponse, RuleyError> {
+            let content = &messages[0].content;
+            let Some(chunk_number) = content
+                .split("This is chunk ")
+                .nth(1)
+                .and_then(|rest| rest.split_whitespace().next())
+                .and_then(|n| n.parse::<u64>().ok())
+            else {
+                return Ok(CompletionResponse::new("Merged".to_string(), 10, 10));
+            };
+
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(40 / chunk_number)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(CompletionResponse::new(
+                format!("Analysis {}", chunk_number),
+                10,
+                10,
+            ))
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+
+        fn pricing(&self) -> Pricing {
+            Pricing {
+                input_per_1k: 0.0,
+                output_per_1k: 0.0,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_analysis_is_bounded_and_ordered() {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let provider = MockConcurrentProvider {
+            in_flight: AtomicUsize::new(0),
+            peak: peak.clone(),
+        };
+        let client = LLMClient::new(Box::new(provider));
+
+        let chunks: Vec<Chunk> = (0..5)
+            .map(|id| create_test_chunk(id, "fn main() {}"))
+            .collect();
+        let options = AnalysisOptions {
+            max_concurrency: 2,
+            ..AnalysisOptions::default()
+        };
+        let progress = std::sync::Mutex::new(Vec::new());
+        let on_chunk_complete = |done: usize, total: usize| {
+            progress.lock().unwrap().push((done, total));
+        };
+
+        let result = analyze_chunked_with_results(
+            chunks,
+            "Analyze this",
+            &client,
+            &WordTokenizer,
+            &options,
+            None,
+            Some(&on_chunk_complete),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(result.merged_analysis, "Merged");
+        let ids: Vec<usize> = result.chunk_results.iter().map(|r| r.chunk_id).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+        for r in &result.chunk_results {
+            assert_eq!(r.analysis, format!("Analysis {}", r.chunk_id + 1));
+        }
+        assert_eq!(
+            *progress.lock().unwrap(),
+            vec![(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)]
+        );
+    }
+
+    fn create_word_results(count: usize, words: usize) -> Vec<ChunkResult> {
+        (0..count)
+            .map(|id| ChunkResult::new(id, vec!["word"; words].join(" "), 0, 0))
+            .collect()
+    }
+
+    #[test]
+    fn test_group_for_merge_respects_budget() {
+        let results = create_word_results(6, 100);
+        let overhead = WordTokenizer.count_tokens(&build_merge_prompt(&[], 1));
+        let per_result = WordTokenizer.count_tokens(&format_chunk_analysis(&results[0]));
+
+        // No budget, or a budget that fits everything: a single group
+        for budget in [None, Some(overhead + 6 * per_result)] {
+            let groups = group_for_merge(&results, 1, &WordTokenizer, budget);
+            assert_eq!(groups.len(), 1);
+            assert_eq!(groups[0], 0..6);
+        }
+
+        // Room for three analyses per merge
+        assert_eq!(
+            group_for_merge(&results, 1, &WordTokenizer, Some(overhead + 3 * per_result)),
+            vec![0..3, 3..6]
+        );
+
+        // Analyses larger than the budget are still merged in pairs
+        assert_eq!(
+            group_for_merge(&results[..5], 1, &WordTokenizer, Some(10)),
+            vec![0..2, 2..4, 4..5]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_hierarchically_records_every_step() {
+        let provider = MockAnalysisProvider::new(vec![]);
+        let call_count = provider.call_count.clone();
+        let client = LLMClient::new(Box::new(provider));
+
+        let results = create_word_results(5, 100);
+        let overhead = WordTokenizer.count_tokens(&build_merge_prompt(&[], 1));
+        let per_result = WordTokenizer.count_tokens(&format_chunk_analysis(&results[0]));
+        let options = AnalysisOptions {
+            max_concurrency: 1,
+            merge_token_budget: Some(overhead + 2 * per_result),
+            ..AnalysisOptions::default()
+        };
+
+        let (merged, steps) = merge_hierarchically(&results, &client, &WordTokenizer, &options)
+            .await
+            .unwrap();
+
+        // Level 1 merges two pairs and carries the fifth analysis up; level 2
+        // fits the two summaries and the carried analysis in one final merge
+        assert_eq!(call_count.load(Ordering::SeqCst), 3);
+        assert_eq!(merged, "Response 2");
+        let shape: Vec<(usize, usize, usize)> =
+            steps.iter().map(|s| (s.level, s.group, s.inputs)).collect();
+        assert_eq!(shape, vec![(1, 0, 2), (1, 1, 2), (2, 0, 3)]);
+        assert!(
+            steps
+                .iter()
+                .all(|s| s.prompt_tokens == 50 && s.completion_tokens == 50)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_hierarchically_without_budget_is_single_merge() {
+        let provider = MockAnalysisProvider::new(vec!["Merged".to_string()]);
+        let client = LLMClient::new(Box::new(provider));
+
+        let results = create_word_results(12, 100);
+        let (merged, steps) = merge_hierarchically(
+            &results,
+            &client,
+            &WordTokenizer,
+            &AnalysisOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(merged, "Merged");
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].inputs, 12);
+    }
+
+    /// In-memory store that holds results for chunks with even IDs.
+    struct MockStore {
+        stored: std::sync::Mutex<Vec<usize>>,
+    }
+
+    impl ChunkResultStore for MockStore {
+        fn load(&self, chunk: &Chunk) -> Option<ChunkResult> {
+            chunk
+                .id
+                .is_multiple_of(2)
+                .then(|| ChunkResult::new(99, format!("Cached {}", chunk.id + 1), 1000, 100))
+        }
+
+        fn store(&self, chunk: &Chunk, _result: &ChunkResult) {
+            self.stored.lock().unwrap().push(chunk.id);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_analysis_reuses_stored_chunks() {
+        let provider = MockAnalysisProvider::new(vec![]);
+        let call_count = provider.call_count.clone();
+        let client = LLMClient::new(Box::new(provider));
+        let store = MockStore {
+            stored: std::sync::Mutex::new(Vec::new()),
+        };
+
+        let chunks: Vec<Chunk> = (0..4)
+            .map(|id| create_test_chunk(id, "fn main() {}"))
+            .collect();
+        let options = AnalysisOptions {
+            max_concurrency: 1,
+            ..AnalysisOptions::default()
+        };
+
+        let result = analyze_chunked_with_results(
+            chunks,
+            "Analyze this",
+            &client,
+            &WordTokenizer,
+            &options,
+            Some(&store),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Two chunk requests plus the merge; only fresh results are stored
+        assert_eq!(call_count.load(Ordering::SeqCst), 3);
+        assert_eq!(*store.stored.lock().unwrap(), vec![1, 3]);
+
+        let reused: Vec<bool> = result.chunk_results.iter().map(|r| r.reused).collect();
+        assert_eq!(reused, vec![true, false, true, false]);
+        let ids: Vec<usize> = result.chunk_results.iter().map(|r| r.chunk_id).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3]);
+        assert_eq!(result.chunk_results[2].analysis, "Cached 3");
+        assert_eq!(result.chunk_results[2].prompt_tokens, 1000);
+    }
 }
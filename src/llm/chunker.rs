@@ -4,8 +4,9 @@
 //! Token-based chunking logic for splitting large codebases.
 //!
 //! This module provides functionality to split a compressed codebase into
-//! manageable chunks that fit within LLM context limits. Each chunk includes
-//! configurable overlap to maintain context continuity.
+//! manageable chunks that fit within LLM context limits. By default whole files
+//! are packed into chunks (see [`ChunkStrategy::Files`]); the text strategy
+//! splits the formatted codebase with configurable overlap instead.
 //!
 //! # Example
 //!
@@ -22,11 +23,28 @@
 //! }
 //! ```
 
-use crate::packer::CompressedCodebase;
+use crate::packer::{CompressedCodebase, CompressedFile};
 use crate::utils::error::RuleyError;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 use super::tokenizer::Tokenizer;
 
+/// How a codebase that exceeds one chunk is split.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStrategy {
+    /// Pack whole files into chunks, keeping files from the same directory
+    /// together. Only files larger than a chunk are split, and each part after
+    /// the first is headed with a "continued" marker.
+    #[default]
+    Files,
+    /// Split the formatted codebase as one string, overlapping consecutive
+    /// chunks by `overlap_size` tokens.
+    Text,
+}
+
 /// Configuration for chunking a codebase.
 ///
 /// Controls how the codebase is split into chunks and how much overlap
@@ -42,7 +60,13 @@ pub struct ChunkConfig {
     ///
     /// Default: 10,000 tokens (10% of default chunk_size)
     /// This helps maintain context continuity across chunk boundaries.
+    /// Only used by [`ChunkStrategy::Text`].
     pub overlap_size: usize,
+
+    /// How the codebase is split when it exceeds one chunk.
+    ///
+    /// Default: [`ChunkStrategy::Files`]
+    pub strategy: ChunkStrategy,
 }
 
 impl Default for ChunkConfig {
@@ -50,6 +74,7 @@ fn default() -> Self {
         Self {
             chunk_size: 100_000,
             overlap_size: 10_000,
+            strategy: ChunkStrategy::default(),
         }
     }
 }
@@ -84,6 +109,7 @@ pub fn new(chunk_size: usize, overlap_size: usize) -> Result<Self, RuleyError> {
         Ok(Self {
             chunk_size,
             overlap_size,
+            strategy: ChunkStrategy::default(),
         })
     }
 
@@ -101,6 +127,37 @@ pub fn with_chunk_size(chunk_size: usize) -> Result<Self, RuleyError> {
         let overlap_size = chunk_size / 10; // 10% overlap
         Self::new(chunk_size, overlap_size)
     }
+
+    /// Set the chunking strategy.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: ChunkStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Shrink the chunk size so each chunk fits in `context_limit` tokens.
+    ///
+    /// The overlap is scaled down by the same ratio. Configurations that
+    /// already fit are returned unchanged.
+    #[must_use]
+    pub fn fit_to_context(self, context_limit: usize) -> Self {
+        if self.chunk_size <= context_limit || context_limit == 0 {
+            return self;
+        }
+
+        tracing::debug!(
+            "Reducing chunk size from {} to {} tokens to fit the model context window",
+            self.chunk_size,
+            context_limit
+        );
+        let overlap_size =
+            (self.overlap_size as u128 * context_limit as u128 / self.chunk_size as u128) as usize;
+        Self {
+            chunk_size: context_limit,
+            overlap_size,
+            strategy: self.strategy,
+        }
+    }
 }
 
 /// A single chunk of content from a codebase.
@@ -123,8 +180,12 @@ pub struct Chunk {
 
     /// Number of tokens that overlap with the previous chunk.
     ///
-    /// This is 0 for the first chunk.
+    /// This is 0 for the first chunk, and for every chunk produced by
+    /// [`ChunkStrategy::Files`].
     pub overlap_token_count: usize,
+
+    /// Paths of the files this chunk covers, whole or in part, in content order.
+    pub files: Vec<PathBuf>,
 }
 
 impl Chunk {
@@ -151,6 +212,7 @@ pub fn from_codebase(codebase: &CompressedCodebase, tokenizer: &dyn Tokenizer) -
             content,
             token_count,
             overlap_token_count: 0,
+            files: codebase.files.iter().map(|f| f.path.clone()).collect(),
         }
     }
 }
@@ -158,7 +220,8 @@ pub fn from_codebase(codebase: &CompressedCodebase, tokenizer: &dyn Tokenizer) -
 /// Split a compressed codebase into token-bounded chunks.
 ///
 /// If the codebase fits within a single chunk, returns a single chunk.
-/// Otherwise, splits the content into multiple chunks with configurable
+/// Otherwise, splits it according to [`ChunkConfig::strategy`]: packing whole
+/// files into chunks, or splitting the formatted content with configurable
 /// overlap for context continuity.
 ///
 /// # Arguments
@@ -198,7 +261,7 @@ pub fn chunk_codebase(
     tokenizer: &dyn Tokenizer,
 ) -> Result<Vec<Chunk>, RuleyError> {
     // Format the entire codebase as a single content string
-    let full_content = format_codebase_content(codebase);
+    let (full_content, spans) = format_codebase_sections(codebase);
     let total_tokens = tokenizer.count_tokens(&full_content);
 
     // If content fits in a single chunk, return it directly
@@ -208,11 +271,15 @@ pub fn chunk_codebase(
             content: full_content,
             token_count: total_tokens,
             overlap_token_count: 0,
+            files: codebase.files.iter().map(|f| f.path.clone()).collect(),
         }]);
     }
 
     // Need to split into multiple chunks
-    chunk_content(&full_content, config, tokenizer)
+    match config.strategy {
+        ChunkStrategy::Files => Ok(chunk_files(codebase, config, tokenizer)),
+        ChunkStrategy::Text => chunk_content(&full_content, &spans, config, tokenizer),
+    }
 }
 
 /// Format the codebase content for inclusion in prompts.
@@ -220,25 +287,200 @@ pub fn chunk_codebase(
 /// Creates a structured representation of all files in the codebase,
 /// suitable for sending to an LLM.
 fn format_codebase_content(codebase: &CompressedCodebase) -> String {
+    format_codebase_sections(codebase).0
+}
+
+/// Format the codebase content along with each file's byte range in it.
+fn format_codebase_sections(codebase: &CompressedCodebase) -> (String, Vec<(Range<usize>, &Path)>) {
     let mut content = String::new();
+    let mut spans = Vec::with_capacity(codebase.files.len());
 
     for file in &codebase.files {
-        content.push_str("--- ");
-        content.push_str(&file.path.to_string_lossy());
-        content.push_str(" ---\n");
-        content.push_str(&file.compressed_content);
-        content.push_str("\n\n");
+        let start = content.len();
+        push_file_section(&mut content, &file.path, &file.compressed_content, false);
+        spans.push((start..content.len(), file.path.as_path()));
+    }
+
+    (content, spans)
+}
+
+/// Append one file's section (header line and content) to `content`.
+///
+/// Sections holding a later part of a split file are headed with a
+/// "continued" marker so the LLM knows the part is not the file's start.
+fn push_file_section(content: &mut String, path: &Path, body: &str, continued: bool) {
+    content.push_str("--- ");
+    content.push_str(&path.to_string_lossy());
+    if continued {
+        content.push_str(" (continued)");
+    }
+    content.push_str(" ---\n");
+    content.push_str(body);
+    content.push_str("\n\n");
+}
+
+/// A unit of content to pack: a whole file or one part of an oversized file.
+struct FileSegment<'a> {
+    path: &'a Path,
+    content: String,
+    token_count: usize,
+}
+
+/// A chunk being filled by [`chunk_files`].
+#[derive(Default)]
+struct Bin<'a> {
+    segments: Vec<FileSegment<'a>>,
+    token_count: usize,
+}
+
+/// Pack whole files into chunks of at most `chunk_size` tokens.
+///
+/// Files are grouped by directory. A directory that fits in one chunk is
+/// placed as a unit into the first chunk with room for it (first-fit
+/// bin-packing); larger directories are packed file by file. Files that alone
+/// exceed the chunk size are split into parts. Chunks do not overlap.
+fn chunk_files(
+    codebase: &CompressedCodebase,
+    config: &ChunkConfig,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<Chunk> {
+    // Stable sort keeps the scan order of files within each directory
+    let mut files: Vec<&CompressedFile> = codebase.files.iter().collect();
+    files.sort_by(|a, b| a.path.parent().cmp(&b.path.parent()));
+
+    let mut bins: Vec<Bin> = Vec::new();
+    for directory in files.chunk_by(|a, b| a.path.parent() == b.path.parent()) {
+        let segments: Vec<FileSegment> = directory
+            .iter()
+            .flat_map(|file| file_segments(file, config.chunk_size, tokenizer))
+            .collect();
+        let directory_tokens: usize = segments.iter().map(|s| s.token_count).sum();
+
+        if directory_tokens <= config.chunk_size {
+            let bin = first_fit(&mut bins, directory_tokens, config.chunk_size);
+            bin.token_count += directory_tokens;
+            bin.segments.extend(segments);
+        } else {
+            for segment in segments {
+                let bin = first_fit(&mut bins, segment.token_count, config.chunk_size);
+                bin.token_count += segment.token_count;
+                bin.segments.push(segment);
+            }
+        }
     }
 
-    content
+    bins.into_iter()
+        .enumerate()
+        .map(|(id, bin)| {
+            let content: String = bin.segments.iter().map(|s| s.content.as_str()).collect();
+            let mut files: Vec<PathBuf> = Vec::new();
+            for segment in &bin.segments {
+                if files.last().map(PathBuf::as_path) != Some(segment.path) {
+                    files.push(segment.path.to_path_buf());
+                }
+            }
+
+            let token_count = tokenizer.count_tokens(&content);
+            tracing::debug!(
+                "Chunk {} packs {} file(s) in {} tokens",
+                id + 1,
+                files.len(),
+                token_count
+            );
+            Chunk {
+                id,
+                content,
+                token_count,
+                overlap_token_count: 0,
+                files,
+            }
+        })
+        .collect()
+}
+
+/// Return the first bin with room for `tokens`, opening a new one if none has.
+fn first_fit<'b, 'a>(
+    bins: &'b mut Vec<Bin<'a>>,
+    tokens: usize,
+    capacity: usize,
+) -> &'b mut Bin<'a> {
+    let index = bins
+        .iter()
+        .position(|bin| bin.token_count + tokens <= capacity)
+        .unwrap_or_else(|| {
+            bins.push(Bin::default());
+            bins.len() - 1
+        });
+    &mut bins[index]
+}
+
+/// Format a file as one or more sections of at most `chunk_size` tokens.
+///
+/// Files that fit are returned whole. Larger files are split at line breaks
+/// where possible, and every part after the first carries a "continued" marker
+/// with the file path.
+fn file_segments<'a>(
+    file: &'a CompressedFile,
+    chunk_size: usize,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<FileSegment<'a>> {
+    let path = file.path.as_path();
+    let body = file.compressed_content.as_str();
+
+    let mut whole = String::new();
+    push_file_section(&mut whole, path, body, false);
+    let token_count = tokenizer.count_tokens(&whole);
+    if token_count <= chunk_size {
+        return vec![FileSegment {
+            path,
+            content: whole,
+            token_count,
+        }];
+    }
+
+    // Reserve room for the longer "continued" header in every part
+    let mut header = String::new();
+    push_file_section(&mut header, path, "", true);
+    let target = chunk_size
+        .saturating_sub(tokenizer.count_tokens(&header))
+        .max(1);
+
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while start < body.len() {
+        let mut end = find_chunk_end(body, start, target, tokenizer);
+        if end <= start {
+            // The clean break snapped behind the start; take at least one character
+            end = start + body[start..].chars().next().map_or(1, char::len_utf8);
+        }
+
+        let mut content = String::new();
+        push_file_section(&mut content, path, &body[start..end], start > 0);
+        let token_count = tokenizer.count_tokens(&content);
+        segments.push(FileSegment {
+            path,
+            content,
+            token_count,
+        });
+        start = end;
+    }
+
+    tracing::debug!(
+        "Split {} into {} parts to fit the chunk size",
+        path.display(),
+        segments.len()
+    );
+    segments
 }
 
 /// Split content into chunks with overlap.
 ///
 /// This function performs character-based splitting while respecting
-/// token boundaries by using the tokenizer to count tokens.
+/// token boundaries by using the tokenizer to count tokens. `spans` gives each
+/// file's byte range in `content`, used to list the files each chunk covers.
 fn chunk_content(
     content: &str,
+    spans: &[(Range<usize>, &Path)],
     config: &ChunkConfig,
     tokenizer: &dyn Tokenizer,
 ) -> Result<Vec<Chunk>, RuleyError> {
@@ -271,11 +513,18 @@ fn chunk_content(
         let chunk_content = &content[chunk_start..chunk_end];
         let token_count = tokenizer.count_tokens(chunk_content);
 
+        let files = spans
+            .iter()
+            .filter(|(span, _)| span.start < chunk_end && span.end > chunk_start)
+            .map(|(_, path)| path.to_path_buf())
+            .collect();
+
         chunks.push(Chunk {
             id: chunk_id,
             content: chunk_content.to_string(),
             token_count,
             overlap_token_count,
+            files,
         });
 
         // Move to the next chunk starting position (after the non-overlap portion)
@@ -514,36 +763,11 @@ pub fn chunk_with_tokenizer(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::packer::{CodebaseMetadata, CompressedFile, CompressionMethod};
+    use crate::llm::test_support::{WordTokenizer, create_test_codebase};
+    use crate::packer::CodebaseMetadata;
     use std::collections::HashMap;
     use std::path::PathBuf;
 
-    /// Simple tokenizer for testing that counts words as tokens.
-    struct WordTokenizer;
-
-    impl Tokenizer for WordTokenizer {
-        fn count_tokens(&self, text: &str) -> usize {
-            text.split_whitespace().count()
-        }
-    }
-
-    fn create_test_codebase(files: Vec<(&str, &str)>) -> CompressedCodebase {
-        let compressed_files: Vec<_> = files
-            .into_iter()
-            .map(|(path, content)| CompressedFile {
-                path: PathBuf::from(path),
-                original_content: content.to_string(),
-                compressed_content: content.to_string(),
-                compression_method: CompressionMethod::None,
-                original_size: content.len(),
-                compressed_size: content.len(),
-                language: None,
-            })
-            .collect();
-
-        CompressedCodebase::new(compressed_files)
-    }
-
     #[test]
     fn test_chunk_config_default() {
         let config = ChunkConfig::default();
@@ -571,6 +795,18 @@ fn test_chunk_config_with_chunk_size() {
         assert_eq!(config.overlap_size, 5_000); // 10% of 50_000
     }
 
+    #[test]
+    fn test_chunk_config_fit_to_context() {
+        let config = ChunkConfig::new(100_000, 10_000).unwrap();
+        let fitted = config.fit_to_context(8_000);
+        assert_eq!(fitted.chunk_size, 8_000);
+        assert_eq!(fitted.overlap_size, 800);
+
+        let unchanged = ChunkConfig::default().fit_to_context(200_000);
+        assert_eq!(unchanged.chunk_size, 100_000);
+        assert_eq!(unchanged.overlap_size, 10_000);
+    }
+
     #[test]
     fn test_single_chunk_small_codebase() {
         let codebase =
@@ -610,7 +846,9 @@ fn test_multiple_chunks_large_codebase() {
         ]);
 
         // Use a chunk size that forces multiple chunks (1000 tokens with 100 overlap)
-        let config = ChunkConfig::new(1000, 100).unwrap();
+        let config = ChunkConfig::new(1000, 100)
+            .unwrap()
+            .with_strategy(ChunkStrategy::Text);
         let tokenizer = WordTokenizer;
 
         let chunks = chunk_codebase(&codebase, &config, &tokenizer).unwrap();
@@ -621,6 +859,14 @@ fn test_multiple_chunks_large_codebase() {
             chunks.len()
         );
 
+        // Chunks list the files they cover, including the one spanning the boundary
+        assert_eq!(chunks[0].files, vec![PathBuf::from("file1.txt")]);
+        assert!(chunks.iter().any(|c| c.files.len() == 2));
+        assert_eq!(
+            chunks.last().unwrap().files,
+            vec![PathBuf::from("file2.txt")]
+        );
+
         // First chunk should have no overlap
         assert_eq!(chunks[0].overlap_token_count, 0);
 
@@ -639,6 +885,97 @@ fn test_multiple_chunks_large_codebase() {
         }
     }
 
+    #[test]
+    fn test_files_strategy_packs_whole_files() {
+        let content = "word ".repeat(300);
+        let codebase = create_test_codebase(vec![
+            ("src/a.rs", &content),
+            ("tests/x.rs", &content),
+            ("src/b.rs", &content),
+            ("tests/y.rs", &content),
+            ("src/c.rs", &content),
+        ]);
+        let config = ChunkConfig::new(1000, 100).unwrap();
+
+        let chunks = chunk_codebase(&codebase, &config, &WordTokenizer).unwrap();
+
+        // Each directory fits in one chunk, so its files stay together
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            chunks[0].files,
+            vec![
+                PathBuf::from("src/a.rs"),
+                PathBuf::from("src/b.rs"),
+                PathBuf::from("src/c.rs")
+            ]
+        );
+        assert_eq!(
+            chunks[1].files,
+            vec![PathBuf::from("tests/x.rs"), PathBuf::from("tests/y.rs")]
+        );
+        for chunk in &chunks {
+            assert!(chunk.token_count <= config.chunk_size);
+            assert_eq!(chunk.overlap_token_count, 0);
+            assert!(!chunk.content.contains("(continued)"));
+            // Every file appears in exactly one section
+            assert_eq!(chunk.content.matches("--- ").count(), chunk.files.len());
+        }
+    }
+
+    #[test]
+    fn test_files_strategy_fills_earlier_chunks_first() {
+        let large = "word ".repeat(700);
+        let small = "word ".repeat(200);
+        let codebase = create_test_codebase(vec![
+            ("a/one.rs", &large),
+            ("b/two.rs", &large),
+            ("c/three.rs", &small),
+        ]);
+        let config = ChunkConfig::new(1000, 100).unwrap();
+
+        let chunks = chunk_codebase(&codebase, &config, &WordTokenizer).unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            chunks[0].files,
+            vec![PathBuf::from("a/one.rs"), PathBuf::from("c/three.rs")]
+        );
+        assert_eq!(chunks[1].files, vec![PathBuf::from("b/two.rs")]);
+    }
+
+    #[test]
+    fn test_files_strategy_splits_oversized_file() {
+        let large = "line of words\n".repeat(1000);
+        let small = "word ".repeat(100);
+        let codebase = create_test_codebase(vec![("src/big.rs", &large), ("src/small.rs", &small)]);
+        let config = ChunkConfig::new(1000, 100).unwrap();
+
+        let chunks = chunk_codebase(&codebase, &config, &WordTokenizer).unwrap();
+
+        let big_parts: Vec<&Chunk> = chunks
+            .iter()
+            .filter(|c| c.files.contains(&PathBuf::from("src/big.rs")))
+            .collect();
+        assert!(big_parts.len() >= 3, "3000-word file should span 3+ chunks");
+        assert!(big_parts[0].content.starts_with("--- src/big.rs ---\n"));
+        for part in &big_parts[1..] {
+            assert!(
+                part.content
+                    .starts_with("--- src/big.rs (continued) ---\nline of words\n")
+            );
+        }
+        for chunk in &chunks {
+            assert!(chunk.token_count <= config.chunk_size);
+        }
+
+        // Only the oversized file is split
+        let small_chunks = chunks
+            .iter()
+            .filter(|c| c.files.contains(&PathBuf::from("src/small.rs")))
+            .count();
+        assert_eq!(small_chunks, 1);
+    }
+
     #[test]
     fn test_empty_codebase() {
         let codebase = CompressedCodebase {
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message};
+use crate::llm::response_cache::ResponseCache;
 use crate::utils::error::RuleyError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, warn};
@@ -97,12 +100,24 @@ fn random_jitter(range: u64) -> i64 {
     }
 }
 
+/// Callback receiving the number of completion tokens received so far.
+///
+/// The count covers every request made since the callback was set, including
+/// concurrent ones. Counts are estimated from streamed text (about four bytes
+/// per token) and replaced by the provider's exact count once a response
+/// completes. Tokens from a failed attempt are subtracted before it is retried.
+pub type TokenProgress = Arc<dyn Fn(usize) + Send + Sync>;
+
 /// A high-level client for interacting with LLM providers.
 ///
 /// `LLMClient` wraps an [`LLMProvider`] implementation and provides additional
 /// functionality such as retry logic with exponential backoff. It serves as
 /// the primary interface for making LLM requests in ruley.
 ///
+/// When a [`TokenProgress`] callback is set, requests are streamed and the
+/// callback is updated as tokens arrive. When a [`ResponseCache`] is attached,
+/// identical requests are answered from the cache.
+///
 /// # Example
 ///
 /// ```no_run
@@ -125,6 +140,10 @@ fn random_jitter(range: u64) -> i64 {
 pub struct LLMClient {
     provider: Box<dyn LLMProvider>,
     retry_config: RetryConfig,
+    token_progress: RwLock<Option<TokenProgress>>,
+    received_tokens: AtomicUsize,
+    pricing: Option<crate::llm::provider::Pricing>,
+    response_cache: Option<ResponseCache>,
 }
 
 impl LLMClient {
@@ -137,6 +156,10 @@ pub fn new(provider: Box<dyn LLMProvider>) -> Self {
         Self {
             provider,
             retry_config: RetryConfig::default(),
+            token_progress: RwLock::new(None),
+            received_tokens: AtomicUsize::new(0),
+            pricing: None,
+            response_cache: None,
         }
     }
 
@@ -150,6 +173,104 @@ pub fn with_retry_config(provider: Box<dyn LLMProvider>, retry_config: RetryConf
         Self {
             provider,
             retry_config,
+            token_progress: RwLock::new(None),
+            received_tokens: AtomicUsize::new(0),
+            pricing: None,
+            response_cache: None,
+        }
+    }
+
+    /// Overrides the pricing reported by the provider.
+    ///
+    /// Used to apply model registry pricing, which is more precise than the
+    /// per-provider defaults most providers report.
+    #[must_use]
+    pub fn with_pricing(mut self, pricing: crate::llm::provider::Pricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Attaches a response cache.
+    ///
+    /// Successful responses are written to the cache, and later identical
+    /// requests are served from it without contacting the provider.
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Sets (or clears) the callback that receives live token counts.
+    ///
+    /// While a callback is set, [`complete`](Self::complete) streams responses
+    /// from the provider so progress can be reported before the response ends.
+    pub fn set_token_progress(&self, callback: Option<TokenProgress>) {
+        self.received_tokens.store(0, Ordering::Relaxed);
+        match self.token_progress.write() {
+            Ok(mut current) => *current = callback,
+            Err(e) => {
+                tracing::error!("Failed to update token progress callback (RwLock poisoned): {e}");
+            }
+        }
+    }
+
+    /// Returns the current token progress callback, if any.
+    fn token_progress(&self) -> Option<TokenProgress> {
+        self.token_progress
+            .read()
+            .ok()
+            .and_then(|callback| callback.clone())
+    }
+
+    /// Makes a single request, streaming it when a token progress callback is set.
+    ///
+    /// Each attempt adds its own token estimate to the shared count and takes
+    /// it back out if it fails, so a retried stream does not double-count
+    /// tokens from the dropped attempt.
+    async fn attempt(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let Some(progress) = self.token_progress() else {
+            return self.provider.complete(messages, options).await;
+        };
+
+        // This attempt's contribution to `received_tokens`
+        let contributed = AtomicUsize::new(0);
+        let report = |tokens: usize| {
+            let previous = contributed.swap(tokens, Ordering::Relaxed);
+            let total = if tokens >= previous {
+                let added = tokens - previous;
+                self.received_tokens.fetch_add(added, Ordering::Relaxed) + added
+            } else {
+                let removed = previous - tokens;
+                self.received_tokens
+                    .fetch_sub(removed, Ordering::Relaxed)
+                    .saturating_sub(removed)
+            };
+            progress(total);
+        };
+
+        let received_bytes = AtomicUsize::new(0);
+        let on_delta = |delta: &str| {
+            let total = received_bytes.fetch_add(delta.len(), Ordering::Relaxed) + delta.len();
+            report(total.div_ceil(4));
+        };
+
+        match self
+            .provider
+            .complete_stream(messages, options, &on_delta)
+            .await
+        {
+            Ok(response) => {
+                report(response.completion_tokens);
+                Ok(response)
+            }
+            Err(err) => {
+                report(0);
+                Err(err)
+            }
         }
     }
 
@@ -165,12 +286,19 @@ pub fn with_retry_config(provider: Box<dyn LLMProvider>, retry_config: RetryConf
     /// - HTTP 429 (rate limited)
     /// - HTTP 500, 502, 503, 504 (server errors)
     /// - Network timeouts and connection errors
+    /// - Streams that drop before the response is complete
     ///
     /// The following errors are NOT retried:
     /// - HTTP 400, 401, 403 (client errors)
     /// - Token/context length exceeded
     /// - Configuration errors
     ///
+    /// # Caching
+    ///
+    /// With a response cache attached, a cached response for the same
+    /// provider, model, options and messages is returned immediately with
+    /// [`CompletionResponse::cached`] set. Fresh responses are cached.
+    ///
     /// # Arguments
     ///
     /// * `messages` - The conversation messages to send to the LLM.
@@ -185,11 +313,32 @@ pub async fn complete(
         messages: &[Message],
         options: &CompletionOptions,
     ) -> Result<CompletionResponse, RuleyError> {
+        let cached = self.response_cache.as_ref().map(|cache| {
+            let key = cache.key(
+                self.provider.endpoint(),
+                self.provider.model(),
+                options,
+                messages,
+            );
+            (cache, key)
+        });
+        if let Some((cache, key)) = &cached
+            && let Some(response) = cache.get(key)
+        {
+            debug!(key = %key, "Serving response from cache");
+            return Ok(response);
+        }
+
         let mut last_error: Option<RuleyError> = None;
 
         for attempt in 0..=self.retry_config.max_retries {
-            match self.provider.complete(messages, options).await {
-                Ok(response) => return Ok(response),
+            match self.attempt(messages, options).await {
+                Ok(response) => {
+                    if let Some((cache, key)) = &cached {
+                        cache.put(key, &response);
+                    }
+                    return Ok(response);
+                }
                 Err(err) => {
                     if !Self::is_retryable(&err) {
                         debug!(
@@ -291,7 +440,9 @@ fn is_retryable(error: &RuleyError) -> bool {
             | RuleyError::ParseError { .. }
             | RuleyError::ValidationError { .. }
             | RuleyError::Cache(_)
-            | RuleyError::State(_) => false,
+            | RuleyError::State(_)
+            | RuleyError::SecretsDetected { .. }
+            | RuleyError::StaleRules { .. } => false,
         }
     }
 
@@ -315,9 +466,12 @@ pub fn model(&self) -> &str {
         self.provider.model()
     }
 
-    /// Returns pricing information from the underlying provider.
+    /// Returns pricing information, preferring an override set with
+    /// [`with_pricing`](Self::with_pricing) over the provider's own.
     pub fn pricing(&self) -> crate::llm::provider::Pricing {
-        self.provider.pricing()
+        self.pricing
+            .clone()
+            .unwrap_or_else(|| self.provider.pricing())
     }
 
     /// Returns a reference to the retry configuration.
@@ -330,9 +484,9 @@ pub fn retry_config(&self) -> &RetryConfig {
 mod tests {
     use super::*;
     use crate::llm::provider::Pricing;
+    use crate::llm::provider::StreamCallback;
     use async_trait::async_trait;
-    use std::sync::Arc;
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
 
     #[test]
     fn test_retry_config_default() {
@@ -662,4 +816,160 @@ async fn test_zero_retries_fails_immediately() {
         // Should have tried exactly once (no retries when max_retries = 0)
         assert_eq!(call_count.load(Ordering::SeqCst), 1);
     }
+
+    // Mock streaming provider whose first `drop_times` streams are cut off mid-response
+    struct MockStreamingProvider {
+        call_count: Arc<AtomicUsize>,
+        drop_times: usize,
+    }
+
+    #[async_trait]
+    impl LLMProvider for MockStreamingProvider {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _options: &CompletionOptions,
+        ) -> Result<CompletionResponse, RuleyError> {
+            panic!("complete should not be called while token progress is set");
+        }
+
+        async fn complete_stream(
+            &self,
+            _messages: &[Message],
+            _options: &CompletionOptions,
+            on_delta: &StreamCallback<'_>,
+        ) -> Result<CompletionResponse, RuleyError> {
+            let current_count = self.call_count.fetch_add(1, Ordering::SeqCst);
+
+            on_delta("partial ");
+            on_delta("response");
+            if current_count < self.drop_times {
+                return Err(RuleyError::NetworkError {
+                    message: "mock stream ended before the response was complete".to_string(),
+                    source: None,
+                });
+            }
+            Ok(CompletionResponse::new(
+                "partial response".to_string(),
+                5,
+                3,
+            ))
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+
+        fn pricing(&self) -> Pricing {
+            Pricing {
+                input_per_1k: 0.0,
+                output_per_1k: 0.0,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropped_stream_is_retried_and_progress_resets() {
+        let provider = MockStreamingProvider {
+            call_count: Arc::new(AtomicUsize::new(0)),
+            drop_times: 1,
+        };
+        let call_count = provider.call_count.clone();
+        let retry_config = RetryConfig {
+            max_retries: 3,
+            initial_delay_ms: 1,
+            max_delay_ms: 10,
+            jitter: false,
+        };
+        let client = LLMClient::with_retry_config(Box::new(provider), retry_config);
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        client.set_token_progress(Some(Arc::new(move |tokens| {
+            sink.lock().unwrap().push(tokens);
+        })));
+
+        let result = client.complete(&[], &CompletionOptions::default()).await;
+
+        assert_eq!(result.unwrap().content, "partial response");
+        assert_eq!(call_count.load(Ordering::SeqCst), 2);
+        // The dropped attempt's tokens are taken back out; the final report is
+        // the provider's exact count
+        assert_eq!(*reported.lock().unwrap(), vec![2, 4, 0, 2, 4, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_token_progress_accumulates_across_requests() {
+        let provider = MockStreamingProvider {
+            call_count: Arc::new(AtomicUsize::new(0)),
+            drop_times: 0,
+        };
+        let client = LLMClient::new(Box::new(provider));
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        client.set_token_progress(Some(Arc::new(move |tokens| {
+            sink.lock().unwrap().push(tokens);
+        })));
+
+        let options = CompletionOptions::default();
+        let (first, second) = tokio::join!(
+            client.complete(&[], &options),
+            client.complete(&[], &options)
+        );
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(reported.lock().unwrap().last(), Some(&6));
+
+        // Setting a new callback starts the count over
+        client.set_token_progress(Some(Arc::new(|_| {})));
+        assert_eq!(client.received_tokens.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cleared_token_progress_uses_complete() {
+        let provider = MockProvider::new(0, MockErrorType::NetworkError);
+        let client = LLMClient::new(Box::new(provider));
+        client.set_token_progress(Some(Arc::new(|_| {})));
+        client.set_token_progress(None);
+
+        let result = client.complete(&[], &CompletionOptions::default()).await;
+
+        assert_eq!(result.unwrap().content, "Success");
+    }
+
+    #[test]
+    fn test_pricing_override_replaces_provider_pricing() {
+        let provider = MockProvider::new(0, MockErrorType::NetworkError);
+        let client = LLMClient::new(Box::new(provider));
+        assert_eq!(client.pricing().input_per_1k, 0.0);
+
+        let client = client.with_pricing(Pricing {
+            input_per_1k: 0.002,
+            output_per_1k: 0.008,
+        });
+        assert_eq!(client.pricing().input_per_1k, 0.002);
+        assert_eq!(client.pricing().output_per_1k, 0.008);
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_serves_repeated_requests() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let provider = MockProvider::new(0, MockErrorType::NetworkError);
+        let call_count = Arc::clone(&provider.call_count);
+        let cache = ResponseCache::new(temp.path(), "mock", Duration::from_secs(3600), u64::MAX);
+        let client = LLMClient::new(Box::new(provider)).with_response_cache(cache);
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }];
+        let options = CompletionOptions::default();
+
+        let first = client.complete(&messages, &options).await.unwrap();
+        let second = client.complete(&messages, &options).await.unwrap();
+
+        assert!(!first.cached);
+        assert!(second.cached);
+        assert_eq!(second.content, "Success");
+        assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    }
 }
@@ -37,7 +37,7 @@
 //! let breakdown = tracker.breakdown();
 //! ```
 
-use crate::llm::provider::Pricing;
+use crate::llm::provider::{CompletionResponse, Pricing};
 use serde::{Deserialize, Serialize};
 
 /// Cost estimate for a single LLM operation.
@@ -96,6 +96,9 @@ pub struct CostBreakdown {
     pub output_tokens: usize,
     /// Cost of this operation in dollars.
     pub cost: f64,
+    /// Whether the response came from the response cache (always $0).
+    #[serde(default)]
+    pub cached: bool,
 }
 
 /// Calculator for LLM costs based on provider pricing.
@@ -286,9 +289,39 @@ pub fn add_operation(
             input_tokens,
             output_tokens,
             cost,
+            cached: false,
         });
     }
 
+    /// Adds an operation answered from the response cache.
+    ///
+    /// The token counts are those of the original request; the operation is
+    /// recorded at no cost.
+    pub fn add_cached_operation(
+        &mut self,
+        name: impl Into<String>,
+        input_tokens: usize,
+        output_tokens: usize,
+    ) {
+        self.operations.push(CostBreakdown {
+            operation: name.into(),
+            input_tokens,
+            output_tokens,
+            cost: 0.0,
+            cached: true,
+        });
+    }
+
+    /// Adds an operation for a completion response, at no cost if it was
+    /// served from the response cache.
+    pub fn add_completion(&mut self, name: impl Into<String>, response: &CompletionResponse) {
+        if response.cached {
+            self.add_cached_operation(name, response.prompt_tokens, response.completion_tokens);
+        } else {
+            self.add_operation(name, response.prompt_tokens, response.completion_tokens);
+        }
+    }
+
     /// Returns the total cost of all tracked operations.
     #[must_use]
     pub fn total_cost(&self) -> f64 {
@@ -319,6 +352,12 @@ pub fn operation_count(&self) -> usize {
         self.operations.len()
     }
 
+    /// Returns the number of operations answered from the response cache.
+    #[must_use]
+    pub fn cache_hits(&self) -> usize {
+        self.operations.iter().filter(|op| op.cached).count()
+    }
+
     /// Returns the breakdown of costs by operation.
     ///
     /// The returned vector contains the operations in the order they were added.
@@ -354,6 +393,7 @@ pub fn summary(&self) -> CostSummary {
             total_input_tokens: self.total_input_tokens(),
             total_output_tokens: self.total_output_tokens(),
             operation_count: self.operation_count(),
+            cache_hits: self.cache_hits(),
             operations: self.operations.clone(),
         }
     }
@@ -372,6 +412,9 @@ pub struct CostSummary {
     pub total_output_tokens: usize,
     /// Number of operations tracked.
     pub operation_count: usize,
+    /// Number of operations answered from the response cache.
+    #[serde(default)]
+    pub cache_hits: usize,
     /// Breakdown by operation.
     pub operations: Vec<CostBreakdown>,
 }
@@ -592,4 +635,23 @@ fn test_free_provider_zero_cost() {
         let cost = calc.calculate_cost(100_000, 50_000);
         assert!((cost - 0.0).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_cached_completion_recorded_at_no_cost() {
+        let mut tracker = CostTracker::from_pricing(anthropic_pricing());
+        let fresh = CompletionResponse::new("a".to_string(), 1000, 500);
+        let mut cached = CompletionResponse::new("b".to_string(), 1000, 500);
+        cached.cached = true;
+
+        tracker.add_completion("fresh", &fresh);
+        tracker.add_completion("cached", &cached);
+
+        let summary = tracker.summary();
+        assert_eq!(summary.operation_count, 2);
+        assert_eq!(summary.cache_hits, 1);
+        assert!((summary.total_cost - 0.0105).abs() < 0.0001);
+        assert!(summary.operations[1].cached);
+        assert!((summary.operations[1].cost - 0.0).abs() < f64::EPSILON);
+        assert_eq!(summary.total_input_tokens, 2000);
+    }
 }
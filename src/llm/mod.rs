@@ -5,11 +5,24 @@
 pub mod chunker;
 pub mod client;
 pub mod cost;
+pub mod models;
 pub mod provider;
 pub mod providers;
+pub mod response_cache;
+pub mod selection;
+#[cfg(any(
+    feature = "anthropic",
+    feature = "openai",
+    feature = "openrouter",
+    feature = "ollama"
+))]
+pub(crate) mod stream;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod tokenizer;
 
 pub use cost::{CostBreakdown, CostCalculator, CostEstimate, CostSummary, CostTracker};
+pub use models::{ModelInfo, ModelRegistry};
 pub use tokenizer::{
     AnthropicTokenizer, TiktokenTokenizer, Tokenizer, TokenizerModel, calculate_tokens,
 };
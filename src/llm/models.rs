@@ -0,0 +1,383 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-model limits, pricing and tokenizer choice.
+//!
+//! The [`ModelRegistry`] answers "how big is this model's context window, how
+//! much does it cost and how should its tokens be counted?" for a provider and
+//! model name. It combines a built-in table of well-known models with
+//! `[models."name"]` overrides from `ruley.toml`, and falls back to
+//! provider-level defaults for models it does not know.
+//!
+//! # Example
+//!
+//! ```
+//! use ruley::llm::models::ModelRegistry;
+//!
+//! let registry = ModelRegistry::new();
+//! let info = registry.resolve("openai", Some("gpt-4.1-mini"));
+//! assert_eq!(info.context_window, 1_047_576);
+//! ```
+
+use crate::cli::config::ModelConfig;
+use crate::llm::provider::Pricing;
+use crate::llm::tokenizer::TokenizerModel;
+use crate::utils::error::RuleyError;
+use std::collections::HashMap;
+
+/// Output tokens assumed for models without a known limit.
+///
+/// Matches the `max_tokens` providers request when none is configured.
+const DEFAULT_MAX_OUTPUT_TOKENS: usize = 4096;
+
+/// Resolved limits, pricing and tokenizer for a model.
+#[derive(Debug, Clone)]
+pub struct ModelInfo {
+    /// Context window in tokens (input and output combined).
+    pub context_window: usize,
+    /// Maximum tokens the model can generate in one response.
+    pub max_output_tokens: usize,
+    /// Pricing in USD per 1K tokens.
+    pub pricing: Pricing,
+    /// Tokenizer used to count tokens for this model.
+    pub tokenizer: TokenizerModel,
+}
+
+/// A built-in registry entry.
+struct BuiltinModel {
+    /// Provider that serves this model directly (and charges the listed price).
+    provider: &'static str,
+    /// Model name; dated or suffixed variants (e.g. `-20250929`) match it too.
+    name: &'static str,
+    context_window: usize,
+    max_output_tokens: usize,
+    input_per_1k: f64,
+    output_per_1k: f64,
+    tokenizer: TokenizerModel,
+}
+
+const fn model(
+    provider: &'static str,
+    name: &'static str,
+    context_window: usize,
+    max_output_tokens: usize,
+    input_per_1k: f64,
+    output_per_1k: f64,
+    tokenizer: TokenizerModel,
+) -> BuiltinModel {
+    BuiltinModel {
+        provider,
+        name,
+        context_window,
+        max_output_tokens,
+        input_per_1k,
+        output_per_1k,
+        tokenizer,
+    }
+}
+
+use TokenizerModel::{Claude, Gpt4, Gpt4o};
+
+/// Well-known models. Prices are list prices at the time of writing; override
+/// them in `ruley.toml` if they drift.
+#[rustfmt::skip]
+const BUILTIN_MODELS: &[BuiltinModel] = &[
+    // Anthropic
+    model("anthropic", "claude-opus-4-1",   200_000, 32_000, 0.015,   0.075,   Claude),
+    model("anthropic", "claude-opus-4",     200_000, 32_000, 0.015,   0.075,   Claude),
+    model("anthropic", "claude-sonnet-4-5", 200_000, 64_000, 0.003,   0.015,   Claude),
+    model("anthropic", "claude-sonnet-4",   200_000, 64_000, 0.003,   0.015,   Claude),
+    model("anthropic", "claude-haiku-4-5",  200_000, 64_000, 0.001,   0.005,   Claude),
+    model("anthropic", "claude-3-7-sonnet", 200_000, 64_000, 0.003,   0.015,   Claude),
+    model("anthropic", "claude-3-5-sonnet", 200_000, 8_192,  0.003,   0.015,   Claude),
+    model("anthropic", "claude-3-5-haiku",  200_000, 8_192,  0.0008,  0.004,   Claude),
+    model("anthropic", "claude-3-opus",     200_000, 4_096,  0.015,   0.075,   Claude),
+    model("anthropic", "claude-3-haiku",    200_000, 4_096,  0.00025, 0.00125, Claude),
+    // OpenAI
+    model("openai", "gpt-5",         400_000,   128_000, 0.00125, 0.010,  Gpt4o),
+    model("openai", "gpt-5-mini",    400_000,   128_000, 0.00025, 0.002,  Gpt4o),
+    model("openai", "gpt-5-nano",    400_000,   128_000, 0.00005, 0.0004, Gpt4o),
+    model("openai", "gpt-4.1",       1_047_576, 32_768,  0.002,   0.008,  Gpt4o),
+    model("openai", "gpt-4.1-mini",  1_047_576, 32_768,  0.0004,  0.0016, Gpt4o),
+    model("openai", "gpt-4.1-nano",  1_047_576, 32_768,  0.0001,  0.0004, Gpt4o),
+    model("openai", "gpt-4o",        128_000,   16_384,  0.0025,  0.010,  Gpt4o),
+    model("openai", "gpt-4o-mini",   128_000,   16_384,  0.00015, 0.0006, Gpt4o),
+    model("openai", "o1",            200_000,   100_000, 0.015,   0.060,  Gpt4o),
+    model("openai", "o1-mini",       128_000,   65_536,  0.0011,  0.0044, Gpt4o),
+    model("openai", "o3",            200_000,   100_000, 0.002,   0.008,  Gpt4o),
+    model("openai", "o3-mini",       200_000,   100_000, 0.0011,  0.0044, Gpt4o),
+    model("openai", "o4-mini",       200_000,   100_000, 0.0011,  0.0044, Gpt4o),
+    model("openai", "gpt-4-turbo",   128_000,   4_096,   0.010,   0.030,  Gpt4),
+    model("openai", "gpt-4",         8_192,     8_192,   0.030,   0.060,  Gpt4),
+    model("openai", "gpt-3.5-turbo", 16_385,    4_096,   0.0005,  0.0015, Gpt4),
+    // xAI
+    model("xai", "grok-4",      256_000, 64_000, 0.003,  0.015,  Gpt4o),
+    model("xai", "grok-3",      131_072, 16_384, 0.003,  0.015,  Gpt4o),
+    model("xai", "grok-3-mini", 131_072, 16_384, 0.0003, 0.0005, Gpt4o),
+    // Groq
+    model("groq", "llama-3.3-70b-versatile", 131_072, 32_768, 0.00059, 0.00079, Gpt4),
+    model("groq", "llama-3.1-8b-instant",    131_072, 8_192,  0.00005, 0.00008, Gpt4),
+    // Gemini
+    model("gemini", "gemini-2.5-pro",        1_048_576, 65_536, 0.00125, 0.010,  Gpt4o),
+    model("gemini", "gemini-2.5-flash",      1_048_576, 65_536, 0.0003,  0.0025, Gpt4o),
+    model("gemini", "gemini-2.5-flash-lite", 1_048_576, 65_536, 0.0001,  0.0004, Gpt4o),
+    model("gemini", "gemini-2.0-flash",      1_048_576, 8_192,  0.0001,  0.0004, Gpt4o),
+];
+
+/// Registry of model limits, pricing and tokenizers.
+///
+/// Resolution order for each field:
+///
+/// 1. A `[models."name"]` entry from `ruley.toml`
+/// 2. The built-in entry for the model
+/// 3. The provider's defaults
+///
+/// Built-in prices only apply when the model is called through its own vendor;
+/// the same model served through OpenRouter, Ollama or an OpenAI-compatible
+/// endpoint keeps that provider's pricing, but still uses the built-in context
+/// window, output limit and tokenizer.
+#[derive(Debug, Clone, Default)]
+pub struct ModelRegistry {
+    overrides: HashMap<String, ModelConfig>,
+}
+
+impl ModelRegistry {
+    /// Creates a registry containing only the built-in models.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with `[models."name"]` overrides from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an override names an unknown tokenizer encoding.
+    pub fn with_overrides(overrides: HashMap<String, ModelConfig>) -> Result<Self, RuleyError> {
+        for (name, config) in &overrides {
+            if let Some(ref encoding) = config.tokenizer
+                && TokenizerModel::from_encoding_name(encoding).is_none()
+            {
+                return Err(RuleyError::Config(format!(
+                    "Unknown tokenizer '{}' for model '{}'. Supported encodings: cl100k_base, o200k_base",
+                    encoding, name
+                )));
+            }
+        }
+        Ok(Self { overrides })
+    }
+
+    /// Resolves the model info for a provider and (optional) model name.
+    #[must_use]
+    pub fn resolve(&self, provider: &str, model: Option<&str>) -> ModelInfo {
+        let provider = provider.to_lowercase();
+        let mut info = provider_defaults(&provider, model);
+
+        let Some(model) = model else {
+            return info;
+        };
+
+        if let Some(builtin) = find_builtin(model) {
+            info.context_window = builtin.context_window;
+            info.max_output_tokens = builtin.max_output_tokens;
+            info.tokenizer = builtin.tokenizer;
+            if builtin.provider == provider {
+                info.pricing = Pricing {
+                    input_per_1k: builtin.input_per_1k,
+                    output_per_1k: builtin.output_per_1k,
+                };
+            }
+        }
+
+        if let Some(overrides) = self.overrides.get(model) {
+            if let Some(context_window) = overrides.context_window {
+                info.context_window = context_window;
+            }
+            if let Some(max_output_tokens) = overrides.max_output_tokens {
+                info.max_output_tokens = max_output_tokens;
+            }
+            if let Some(input_per_1k) = overrides.input_per_1k {
+                info.pricing.input_per_1k = input_per_1k;
+            }
+            if let Some(output_per_1k) = overrides.output_per_1k {
+                info.pricing.output_per_1k = output_per_1k;
+            }
+            if let Some(tokenizer) = overrides
+                .tokenizer
+                .as_deref()
+                .and_then(TokenizerModel::from_encoding_name)
+            {
+                info.tokenizer = tokenizer;
+            }
+        }
+
+        info
+    }
+
+    /// Returns whether `ruley.toml` sets pricing for the given model.
+    #[must_use]
+    pub fn has_pricing_override(&self, model: &str) -> bool {
+        self.overrides
+            .get(model)
+            .is_some_and(|m| m.input_per_1k.is_some() || m.output_per_1k.is_some())
+    }
+}
+
+/// Finds the built-in entry for a model name.
+///
+/// Matching ignores case, a leading `vendor/` namespace (as used by
+/// OpenRouter) and the `.`/`-` spelling of version numbers, so
+/// `anthropic/claude-3.5-sonnet` matches `claude-3-5-sonnet`. Dated and
+/// tagged variants match their base entry; the longest matching name wins.
+fn find_builtin(model: &str) -> Option<&'static BuiltinModel> {
+    let name = normalize(model.rsplit('/').next().unwrap_or(model));
+
+    BUILTIN_MODELS
+        .iter()
+        .filter(|entry| {
+            let entry_name = normalize(entry.name);
+            name.strip_prefix(&entry_name)
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_alphanumeric()))
+        })
+        .max_by_key(|entry| entry.name.len())
+}
+
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace('.', "-")
+}
+
+/// Provider-level defaults used for models missing from the registry.
+fn provider_defaults(provider: &str, model: Option<&str>) -> ModelInfo {
+    let (context_window, input_per_1k, output_per_1k, tokenizer) = match provider {
+        "anthropic" => (200_000, 0.003, 0.015, Claude),
+        "openai" | "openai-compatible" => {
+            let tokenizer = model.map_or(Gpt4o, TokenizerModel::from_model_name);
+            if provider == "openai" {
+                (128_000, 0.0025, 0.010, tokenizer)
+            } else {
+                // Self-hosted by default, so free unless configured
+                (100_000, 0.0, 0.0, tokenizer)
+            }
+        }
+        "ollama" => (100_000, 0.0, 0.0, Gpt4o),
+        // Varies by model; live pricing is fetched from the OpenRouter API
+        "openrouter" => (128_000, 0.003, 0.015, Gpt4o),
+        "xai" => (256_000, 0.003, 0.015, Gpt4o),
+        "groq" => (128_000, 0.00059, 0.00079, Gpt4),
+        "gemini" => (1_000_000, 0.00125, 0.010, Gpt4o),
+        _ => (100_000, 0.003, 0.015, Gpt4),
+    };
+
+    ModelInfo {
+        context_window,
+        max_output_tokens: DEFAULT_MAX_OUTPUT_TOKENS,
+        pricing: Pricing {
+            input_per_1k,
+            output_per_1k,
+        },
+        tokenizer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_model_matches_dated_variant() {
+        let info = ModelRegistry::new().resolve("anthropic", Some("claude-sonnet-4-5-20250929"));
+        assert_eq!(info.context_window, 200_000);
+        assert_eq!(info.max_output_tokens, 64_000);
+        assert_eq!(info.pricing.input_per_1k, 0.003);
+        assert_eq!(info.tokenizer, TokenizerModel::Claude);
+    }
+
+    #[test]
+    fn test_longest_name_wins() {
+        let registry = ModelRegistry::new();
+        assert_eq!(
+            registry
+                .resolve("openai", Some("gpt-4o-mini"))
+                .pricing
+                .input_per_1k,
+            0.00015
+        );
+        assert_eq!(
+            registry
+                .resolve("openai", Some("gpt-4o-2024-08-06"))
+                .pricing
+                .input_per_1k,
+            0.0025
+        );
+        // "gpt-4" must not swallow "gpt-4o" or "gpt-4.1"
+        assert_eq!(
+            registry.resolve("openai", Some("gpt-4.1")).context_window,
+            1_047_576
+        );
+        assert_eq!(
+            registry.resolve("openai", Some("gpt-4")).context_window,
+            8_192
+        );
+    }
+
+    #[test]
+    fn test_other_provider_keeps_its_pricing() {
+        let info = ModelRegistry::new().resolve("openrouter", Some("anthropic/claude-3.5-sonnet"));
+        assert_eq!(info.context_window, 200_000);
+        assert_eq!(info.max_output_tokens, 8_192);
+        assert_eq!(info.tokenizer, TokenizerModel::Claude);
+        // OpenRouter default, not Anthropic's list price
+        assert_eq!(info.pricing.input_per_1k, 0.003);
+
+        let info = ModelRegistry::new().resolve("openai-compatible", Some("gpt-4o"));
+        assert_eq!(info.context_window, 128_000);
+        assert_eq!(info.pricing.input_per_1k, 0.0);
+    }
+
+    #[test]
+    fn test_unknown_model_uses_provider_defaults() {
+        let info = ModelRegistry::new().resolve("ollama", Some("llama3.1:70b"));
+        assert_eq!(info.context_window, 100_000);
+        assert_eq!(info.max_output_tokens, DEFAULT_MAX_OUTPUT_TOKENS);
+        assert_eq!(info.pricing.output_per_1k, 0.0);
+
+        let info = ModelRegistry::new().resolve("gemini", None);
+        assert_eq!(info.context_window, 1_000_000);
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let overrides = HashMap::from([(
+            "gpt-4o".to_string(),
+            ModelConfig {
+                context_window: Some(64_000),
+                input_per_1k: Some(0.001),
+                tokenizer: Some("cl100k_base".to_string()),
+                ..ModelConfig::default()
+            },
+        )]);
+        let registry = ModelRegistry::with_overrides(overrides).unwrap();
+
+        let info = registry.resolve("openai", Some("gpt-4o"));
+        assert_eq!(info.context_window, 64_000);
+        assert_eq!(info.max_output_tokens, 16_384);
+        assert_eq!(info.pricing.input_per_1k, 0.001);
+        assert_eq!(info.pricing.output_per_1k, 0.010);
+        assert_eq!(info.tokenizer, TokenizerModel::Gpt4);
+        assert!(registry.has_pricing_override("gpt-4o"));
+        assert!(!registry.has_pricing_override("gpt-4o-mini"));
+    }
+
+    #[test]
+    fn test_unknown_tokenizer_is_rejected() {
+        let overrides = HashMap::from([(
+            "my-model".to_string(),
+            ModelConfig {
+                tokenizer: Some("sentencepiece".to_string()),
+                ..ModelConfig::default()
+            },
+        )]);
+        let err = ModelRegistry::with_overrides(overrides).unwrap_err();
+        assert!(err.to_string().contains("sentencepiece"));
+    }
+}
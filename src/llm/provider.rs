@@ -26,6 +26,9 @@ pub struct CompletionResponse {
     pub completion_tokens: usize,
     /// Total tokens used (prompt + completion). Prefer using prompt_tokens + completion_tokens directly.
     pub tokens_used: usize,
+    /// Whether this response was served from the response cache instead of
+    /// the provider. Token counts are those of the original request.
+    pub cached: bool,
 }
 
 impl CompletionResponse {
@@ -36,6 +39,7 @@ pub fn new(content: String, prompt_tokens: usize, completion_tokens: usize) -> S
             prompt_tokens,
             completion_tokens,
             tokens_used: prompt_tokens + completion_tokens,
+            cached: false,
         }
     }
 }
@@ -46,6 +50,9 @@ pub struct Pricing {
     pub output_per_1k: f64,
 }
 
+/// Callback receiving each fragment of text as a streamed completion arrives.
+pub type StreamCallback<'a> = dyn Fn(&str) + Send + Sync + 'a;
+
 #[async_trait]
 pub trait LLMProvider: Send + Sync {
     async fn complete(
@@ -54,7 +61,33 @@ async fn complete(
         options: &CompletionOptions,
     ) -> Result<CompletionResponse, RuleyError>;
 
+    /// Streams a completion, passing each fragment of generated text to `on_delta`
+    /// as it arrives.
+    ///
+    /// Returns the same assembled response as [`complete`](Self::complete). If the
+    /// stream drops before the provider signals completion, a retryable
+    /// [`RuleyError::NetworkError`] is returned and the partial output is discarded.
+    ///
+    /// The default implementation does not stream: it calls `complete` and reports
+    /// the whole content as a single fragment.
+    async fn complete_stream(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let response = self.complete(messages, options).await?;
+        on_delta(&response.content);
+        Ok(response)
+    }
+
     fn model(&self) -> &str;
 
     fn pricing(&self) -> Pricing;
+
+    /// Base URL of a user-configured endpoint, for providers that can point
+    /// at different servers. Cached responses are keyed by it.
+    fn endpoint(&self) -> Option<&str> {
+        None
+    }
 }
@@ -1,7 +1,10 @@
 // Copyright (c) 2025-2026 the ruley contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing};
+use crate::llm::provider::{
+    CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing, StreamCallback,
+};
+use crate::llm::stream::{for_each_sse_event, stream_interrupted};
 use crate::utils::error::RuleyError;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -11,6 +14,8 @@
 const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 const DEFAULT_MAX_TOKENS: usize = 4096;
+/// Deadline for non-streaming requests, and the idle timeout between streamed chunks.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 
 pub struct AnthropicProvider {
     api_key: String,
@@ -28,6 +33,8 @@ struct AnthropicRequest<'a> {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     system: Option<&'a str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 /// A message in the Anthropic format.
@@ -72,15 +79,117 @@ struct ErrorDetail {
     message: String,
 }
 
+/// A streamed Messages API event (the `data` payload of each SSE event).
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    MessageStart {
+        message: StreamMessage,
+    },
+    ContentBlockDelta {
+        delta: StreamDelta,
+    },
+    MessageDelta {
+        usage: Option<DeltaUsage>,
+    },
+    MessageStop,
+    Error {
+        error: ErrorDetail,
+    },
+    /// `ping`, `content_block_start`, `content_block_stop` and future event types.
+    #[serde(other)]
+    Other,
+}
+
+/// The message envelope sent in `message_start`, carrying input token usage.
+#[derive(Debug, Deserialize)]
+struct StreamMessage {
+    usage: Usage,
+}
+
+/// A content block delta; only text deltas contribute to the response.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamDelta {
+    TextDelta {
+        text: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Cumulative output token count sent in `message_delta`.
+#[derive(Debug, Deserialize)]
+struct DeltaUsage {
+    output_tokens: usize,
+}
+
+/// Assembles a completion from Messages API stream events.
+#[derive(Debug, Default)]
+struct AnthropicStream {
+    content: String,
+    input_tokens: usize,
+    output_tokens: usize,
+}
+
+impl AnthropicStream {
+    /// Handles one SSE `data` payload, returning `true` on `message_stop`.
+    ///
+    /// `overloaded_error` and `api_error` events are transient server-side
+    /// failures and are reported as retryable network errors.
+    fn handle(&mut self, data: &str, on_delta: &StreamCallback<'_>) -> Result<bool, RuleyError> {
+        let event: StreamEvent = serde_json::from_str(data).map_err(|e| RuleyError::Provider {
+            provider: "anthropic".to_string(),
+            message: format!("Failed to parse stream event: {}", e),
+        })?;
+
+        match event {
+            StreamEvent::MessageStart { message } => {
+                self.input_tokens = message.usage.input_tokens;
+                self.output_tokens = message.usage.output_tokens;
+            }
+            StreamEvent::ContentBlockDelta {
+                delta: StreamDelta::TextDelta { text },
+            } => {
+                on_delta(&text);
+                self.content.push_str(&text);
+            }
+            StreamEvent::MessageDelta { usage: Some(usage) } => {
+                self.output_tokens = usage.output_tokens;
+            }
+            StreamEvent::MessageStop => return Ok(true),
+            StreamEvent::Error { error } => {
+                let message = format!("{}: {}", error.error_type, error.message);
+                return Err(match error.error_type.as_str() {
+                    "overloaded_error" | "api_error" => RuleyError::NetworkError {
+                        message: format!("anthropic stream interrupted ({})", message),
+                        source: None,
+                    },
+                    _ => RuleyError::Provider {
+                        provider: "anthropic".to_string(),
+                        message,
+                    },
+                });
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+}
+
 impl AnthropicProvider {
     /// Creates a new Anthropic provider with the given API key and model.
     ///
+    /// Non-streaming requests time out after 120 seconds; streamed requests
+    /// only time out if no data arrives for 120 seconds.
+    ///
     /// # Errors
     ///
     /// Returns an error if the HTTP client cannot be created.
     pub fn new(api_key: String, model: String) -> Result<Self, RuleyError> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(120))
+            .read_timeout(REQUEST_TIMEOUT)
             .build()
             .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
         Ok(Self {
@@ -113,15 +222,14 @@ fn extract_system_prompt(messages: &[Message]) -> (Option<&str>, &[Message]) {
         }
         (None, messages)
     }
-}
 
-#[async_trait]
-impl LLMProvider for AnthropicProvider {
-    async fn complete(
-        &self,
-        messages: &[Message],
+    /// Builds a Messages API request body, extracting any system prompt.
+    fn build_request<'a>(
+        &'a self,
+        messages: &'a [Message],
         options: &CompletionOptions,
-    ) -> Result<CompletionResponse, RuleyError> {
+        stream: bool,
+    ) -> AnthropicRequest<'a> {
         let (system_prompt, user_messages) = Self::extract_system_prompt(messages);
 
         // Convert messages to Anthropic format
@@ -133,23 +241,27 @@ async fn complete(
             })
             .collect();
 
-        let request_body = AnthropicRequest {
+        AnthropicRequest {
             model: &self.model,
             max_tokens: options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
             messages: anthropic_messages,
             temperature: options.temperature,
             system: system_prompt,
-        };
+            stream,
+        }
+    }
 
-        let response = self
-            .client
+    /// Starts a POST to the Messages API with authentication headers applied.
+    fn post(&self) -> reqwest::RequestBuilder {
+        self.client
             .post(ANTHROPIC_API_URL)
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", ANTHROPIC_VERSION)
             .header("content-type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
+    }
+
+    /// Returns the response if it succeeded, or the matching error otherwise.
+    async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, RuleyError> {
         let status = response.status();
 
         // Handle rate limiting
@@ -185,6 +297,27 @@ async fn complete(
             });
         }
 
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for AnthropicProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let request_body = self.build_request(messages, options, false);
+
+        let response = self
+            .post()
+            .timeout(REQUEST_TIMEOUT)
+            .json(&request_body)
+            .send()
+            .await?;
+        let response = Self::check_status(response).await?;
+
         // Parse successful response
         let response_body: AnthropicResponse = response.json().await?;
 
@@ -209,6 +342,31 @@ async fn complete(
         ))
     }
 
+    async fn complete_stream(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let request_body = self.build_request(messages, options, true);
+
+        let response = self.post().json(&request_body).send().await?;
+        let response = Self::check_status(response).await?;
+
+        let mut stream = AnthropicStream::default();
+        let done =
+            for_each_sse_event(response, |event| stream.handle(&event.data, on_delta)).await?;
+        if !done {
+            return Err(stream_interrupted("anthropic"));
+        }
+
+        Ok(CompletionResponse::new(
+            stream.content,
+            stream.input_tokens,
+            stream.output_tokens,
+        ))
+    }
+
     fn model(&self) -> &str {
         &self.model
     }
@@ -263,4 +421,54 @@ fn test_extract_system_prompt_empty() {
         assert!(system.is_none());
         assert!(remaining.is_empty());
     }
+
+    #[test]
+    fn test_stream_assembles_text_and_usage() {
+        let deltas = std::sync::Mutex::new(Vec::new());
+        let on_delta = |d: &str| deltas.lock().unwrap().push(d.to_string());
+        let mut stream = AnthropicStream::default();
+
+        let events = [
+            r#"{"type":"message_start","message":{"usage":{"input_tokens":25,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"ping"}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":" world"}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":15}}"#,
+        ];
+        for event in events {
+            assert!(!stream.handle(event, &on_delta).unwrap());
+        }
+        assert!(
+            stream
+                .handle(r#"{"type":"message_stop"}"#, &on_delta)
+                .unwrap()
+        );
+
+        assert_eq!(stream.content, "Hello world");
+        assert_eq!(stream.input_tokens, 25);
+        assert_eq!(stream.output_tokens, 15);
+        assert_eq!(*deltas.lock().unwrap(), vec!["Hello", " world"]);
+    }
+
+    #[test]
+    fn test_stream_overloaded_error_is_retryable() {
+        let mut stream = AnthropicStream::default();
+        let err = stream
+            .handle(
+                r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+                &|_| {},
+            )
+            .unwrap_err();
+        assert!(matches!(err, RuleyError::NetworkError { .. }));
+
+        let err = stream
+            .handle(
+                r#"{"type":"error","error":{"type":"invalid_request_error","message":"bad"}}"#,
+                &|_| {},
+            )
+            .unwrap_err();
+        assert!(matches!(err, RuleyError::Provider { .. }));
+    }
 }
@@ -0,0 +1,415 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing};
+use crate::utils::error::RuleyError;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+const DEFAULT_MAX_TOKENS: usize = 4096;
+
+/// Google Gemini LLM provider.
+///
+/// Uses the Gemini `generateContent` API at
+/// `https://generativelanguage.googleapis.com/v1beta/models/{model}:generateContent`.
+/// Supports rate limiting (429 handling) and 120-second timeout like cloud providers.
+///
+/// # Configuration
+///
+/// - `GEMINI_API_KEY` env var is required for authentication
+/// - Config file: `[providers.gemini] model = "..."` sets the default model
+///
+/// # Examples
+///
+/// ```no_run
+/// use ruley::llm::providers::gemini::GeminiProvider;
+///
+/// let provider = GeminiProvider::new(
+///     "your-api-key".to_string(),
+///     "gemini-2.5-pro".to_string(),
+/// ).expect("Failed to create provider");
+/// ```
+pub struct GeminiProvider {
+    api_key: String,
+    model: String,
+    base_url: String,
+    client: Client,
+}
+
+/// Request body for the Gemini `generateContent` API.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest<'a> {
+    contents: Vec<GeminiContent<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<SystemInstruction<'a>>,
+    generation_config: GenerationConfig,
+}
+
+/// A single conversation turn in the Gemini format.
+#[derive(Debug, Serialize)]
+struct GeminiContent<'a> {
+    role: &'a str,
+    parts: Vec<RequestPart<'a>>,
+}
+
+/// System instruction, passed separately from the conversation turns.
+#[derive(Debug, Serialize)]
+struct SystemInstruction<'a> {
+    parts: Vec<RequestPart<'a>>,
+}
+
+/// A text part of a request turn.
+#[derive(Debug, Serialize)]
+struct RequestPart<'a> {
+    text: &'a str,
+}
+
+/// Generation parameters for the request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    max_output_tokens: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+/// Response from the Gemini `generateContent` API.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    usage_metadata: Option<UsageMetadata>,
+}
+
+/// A response candidate.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    content: Option<CandidateContent>,
+    finish_reason: Option<String>,
+}
+
+/// The content of a response candidate.
+#[derive(Debug, Deserialize)]
+struct CandidateContent {
+    #[serde(default)]
+    parts: Vec<ResponsePart>,
+}
+
+/// A part of a response candidate.
+#[derive(Debug, Deserialize)]
+struct ResponsePart {
+    text: Option<String>,
+}
+
+/// Token usage information from Gemini.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: usize,
+    #[serde(default)]
+    candidates_token_count: usize,
+    /// Reasoning tokens for thinking models, billed as output tokens.
+    #[serde(default)]
+    thoughts_token_count: usize,
+}
+
+/// Error response from the Gemini API.
+#[derive(Debug, Deserialize)]
+struct GeminiError {
+    error: ErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorDetail {
+    message: String,
+    status: Option<String>,
+}
+
+impl GeminiProvider {
+    /// Creates a new Gemini provider with the given API key and model.
+    ///
+    /// Uses a 120-second timeout consistent with other cloud providers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(api_key: String, model: String) -> Result<Self, RuleyError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self {
+            api_key,
+            model,
+            base_url: GEMINI_BASE_URL.to_string(),
+            client,
+        })
+    }
+
+    /// Creates a new Gemini provider from environment variables.
+    ///
+    /// Reads the `GEMINI_API_KEY` environment variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is not set or if the HTTP client cannot be created.
+    pub fn from_env() -> Result<Self, RuleyError> {
+        let api_key =
+            std::env::var("GEMINI_API_KEY").map_err(|_| RuleyError::missing_api_key("gemini"))?;
+        Self::new(api_key, "gemini-2.5-pro".to_string())
+    }
+
+    /// Overrides the API base URL (default: `https://generativelanguage.googleapis.com`).
+    ///
+    /// The `/v1beta/models/{model}:generateContent` path is appended to this URL.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Returns the full `generateContent` endpoint URL for the configured model.
+    fn generate_url(&self) -> String {
+        format!(
+            "{}/v1beta/models/{}:generateContent",
+            self.base_url.trim_end_matches('/'),
+            self.model
+        )
+    }
+
+    /// Split messages into a system instruction and Gemini conversation turns.
+    ///
+    /// Gemini takes system prompts via `systemInstruction` and names the
+    /// assistant role `model` instead of `assistant`.
+    fn convert_messages(
+        messages: &[Message],
+    ) -> (Option<SystemInstruction<'_>>, Vec<GeminiContent<'_>>) {
+        let system_parts: Vec<RequestPart<'_>> = messages
+            .iter()
+            .filter(|m| m.role == "system")
+            .map(|m| RequestPart { text: &m.content })
+            .collect();
+
+        let contents = messages
+            .iter()
+            .filter(|m| m.role != "system")
+            .map(|m| GeminiContent {
+                role: if m.role == "assistant" {
+                    "model"
+                } else {
+                    "user"
+                },
+                parts: vec![RequestPart { text: &m.content }],
+            })
+            .collect();
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(SystemInstruction {
+                parts: system_parts,
+            })
+        };
+
+        (system_instruction, contents)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for GeminiProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let (system_instruction, contents) = Self::convert_messages(messages);
+
+        let request_body = GeminiRequest {
+            contents,
+            system_instruction,
+            generation_config: GenerationConfig {
+                max_output_tokens: options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+                temperature: options.temperature,
+            },
+        };
+
+        let response = self
+            .client
+            .post(self.generate_url())
+            .header("x-goog-api-key", &self.api_key)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        // Handle rate limiting
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs);
+
+            return Err(RuleyError::RateLimited {
+                provider: "gemini".to_string(),
+                retry_after,
+            });
+        }
+
+        // Handle other HTTP errors
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+
+            // Try to parse the error response
+            if let Ok(error) = serde_json::from_str::<GeminiError>(&error_text) {
+                let error_type = error
+                    .error
+                    .status
+                    .unwrap_or_else(|| format!("HTTP {}", status));
+                return Err(RuleyError::Provider {
+                    provider: "gemini".to_string(),
+                    message: format!("{}: {}", error_type, error.error.message),
+                });
+            }
+
+            return Err(RuleyError::Provider {
+                provider: "gemini".to_string(),
+                message: format!("HTTP {}: {}", status, error_text),
+            });
+        }
+
+        // Parse successful response
+        let response_body: GeminiResponse = response.json().await?;
+
+        let candidate = response_body.candidates.into_iter().next();
+        let finish_reason = candidate
+            .as_ref()
+            .and_then(|c| c.finish_reason.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let content = candidate
+            .and_then(|c| c.content)
+            .map(|c| {
+                c.parts
+                    .into_iter()
+                    .filter_map(|part| part.text)
+                    .collect::<Vec<_>>()
+                    .join("")
+            })
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| RuleyError::Provider {
+                provider: "gemini".to_string(),
+                message: format!(
+                    "LLM returned empty response content (finish reason: {})",
+                    finish_reason
+                ),
+            })?;
+
+        let (prompt_tokens, completion_tokens) = match response_body.usage_metadata {
+            Some(u) => (
+                u.prompt_token_count,
+                u.candidates_token_count + u.thoughts_token_count,
+            ),
+            None => {
+                tracing::warn!("Gemini response missing usage data; token counts will be zero");
+                (0, 0)
+            }
+        };
+
+        Ok(CompletionResponse::new(
+            content,
+            prompt_tokens,
+            completion_tokens,
+        ))
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
+
+    fn pricing(&self) -> Pricing {
+        Pricing {
+            input_per_1k: 0.00125, // $0.00125 per 1K input tokens
+            output_per_1k: 0.010,  // $0.010 per 1K output tokens
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_messages_maps_roles() {
+        let messages = vec![
+            Message {
+                role: "system".to_string(),
+                content: "You are helpful".to_string(),
+            },
+            Message {
+                role: "user".to_string(),
+                content: "Hello".to_string(),
+            },
+            Message {
+                role: "assistant".to_string(),
+                content: "Hi there".to_string(),
+            },
+        ];
+
+        let (system, contents) = GeminiProvider::convert_messages(&messages);
+        let system = system.expect("system instruction should be extracted");
+        assert_eq!(system.parts.len(), 1);
+        assert_eq!(system.parts[0].text, "You are helpful");
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents[0].role, "user");
+        assert_eq!(contents[1].role, "model");
+    }
+
+    #[test]
+    fn test_request_serialization_uses_camel_case() {
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }];
+        let (system_instruction, contents) = GeminiProvider::convert_messages(&messages);
+
+        let request = GeminiRequest {
+            contents,
+            system_instruction,
+            generation_config: GenerationConfig {
+                max_output_tokens: 1024,
+                temperature: Some(0.5),
+            },
+        };
+
+        let json = serde_json::to_string(&request).expect("serialization should succeed");
+        assert!(json.contains("\"generationConfig\""));
+        assert!(json.contains("\"maxOutputTokens\":1024"));
+        assert!(json.contains("\"temperature\":0.5"));
+        assert!(!json.contains("systemInstruction"));
+    }
+
+    #[test]
+    fn test_generate_url_includes_model() {
+        let provider = GeminiProvider::new("test-key".to_string(), "gemini-2.5-flash".to_string())
+            .expect("should create provider");
+        assert_eq!(
+            provider.generate_url(),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash:generateContent"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing};
+use crate::utils::error::RuleyError;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const GROQ_BASE_URL: &str = "https://api.groq.com/openai/v1";
+const DEFAULT_MAX_TOKENS: usize = 4096;
+
+/// Groq LLM provider for low-latency inference of open-weight models.
+///
+/// Uses the OpenAI-compatible API at `https://api.groq.com/openai/v1/chat/completions`.
+/// Supports rate limiting (429 handling) and 120-second timeout like cloud providers.
+///
+/// # Configuration
+///
+/// - `GROQ_API_KEY` env var is required for authentication
+/// - Config file: `[providers.groq] model = "..."` sets the default model
+///
+/// # Examples
+///
+/// ```no_run
+/// use ruley::llm::providers::groq::GroqProvider;
+///
+/// let provider = GroqProvider::new(
+///     "your-api-key".to_string(),
+///     "llama-3.3-70b-versatile".to_string(),
+/// ).expect("Failed to create provider");
+/// ```
+pub struct GroqProvider {
+    api_key: String,
+    model: String,
+    base_url: String,
+    client: Client,
+}
+
+/// Request body for the Groq Chat Completions API (OpenAI-compatible).
+#[derive(Debug, Serialize)]
+struct GroqRequest<'a> {
+    model: &'a str,
+    messages: Vec<GroqMessage<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+/// A message in the OpenAI-compatible format.
+#[derive(Debug, Serialize)]
+struct GroqMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// Response from the Groq Chat Completions API.
+#[derive(Debug, Deserialize)]
+struct GroqResponse {
+    choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+/// A choice in the response.
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+/// The message content in a choice.
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    content: Option<String>,
+}
+
+/// Token usage information.
+#[derive(Debug, Deserialize)]
+struct Usage {
+    prompt_tokens: usize,
+    completion_tokens: usize,
+}
+
+/// Error response from the Groq API.
+#[derive(Debug, Deserialize)]
+struct GroqError {
+    error: ErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorDetail {
+    #[serde(rename = "type")]
+    error_type: Option<String>,
+    message: String,
+    code: Option<String>,
+}
+
+impl GroqProvider {
+    /// Creates a new Groq provider with the given API key and model.
+    ///
+    /// Uses a 120-second timeout consistent with other cloud providers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(api_key: String, model: String) -> Result<Self, RuleyError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self {
+            api_key,
+            model,
+            base_url: GROQ_BASE_URL.to_string(),
+            client,
+        })
+    }
+
+    /// Creates a new Groq provider from environment variables.
+    ///
+    /// Reads the `GROQ_API_KEY` environment variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is not set or if the HTTP client cannot be created.
+    pub fn from_env() -> Result<Self, RuleyError> {
+        let api_key =
+            std::env::var("GROQ_API_KEY").map_err(|_| RuleyError::missing_api_key("groq"))?;
+        Self::new(api_key, "llama-3.3-70b-versatile".to_string())
+    }
+
+    /// Overrides the API base URL (default: `https://api.groq.com/openai/v1`).
+    ///
+    /// The `/chat/completions` path is appended to this URL.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Returns the full chat completions endpoint URL.
+    fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+}
+
+#[async_trait]
+impl LLMProvider for GroqProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let groq_messages: Vec<GroqMessage<'_>> = messages
+            .iter()
+            .map(|m| GroqMessage {
+                role: &m.role,
+                content: &m.content,
+            })
+            .collect();
+
+        let request_body = GroqRequest {
+            model: &self.model,
+            messages: groq_messages,
+            max_tokens: Some(options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
+            temperature: options.temperature,
+        };
+
+        let response = self
+            .client
+            .post(self.completions_url())
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        // Handle rate limiting
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs);
+
+            return Err(RuleyError::RateLimited {
+                provider: "groq".to_string(),
+                retry_after,
+            });
+        }
+
+        // Handle other HTTP errors
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+
+            // Try to parse the error response
+            if let Ok(error) = serde_json::from_str::<GroqError>(&error_text) {
+                let error_type = error
+                    .error
+                    .error_type
+                    .or(error.error.code)
+                    .unwrap_or_else(|| "unknown".to_string());
+                return Err(RuleyError::Provider {
+                    provider: "groq".to_string(),
+                    message: format!("{}: {}", error_type, error.error.message),
+                });
+            }
+
+            return Err(RuleyError::Provider {
+                provider: "groq".to_string(),
+                message: format!("HTTP {}: {}", status, error_text),
+            });
+        }
+
+        // Parse successful response
+        let response_body: GroqResponse = response.json().await?;
+
+        let content = response_body
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| RuleyError::Provider {
+                provider: "groq".to_string(),
+                message: "LLM returned empty response content".to_string(),
+            })?;
+
+        let (prompt_tokens, completion_tokens) = match response_body.usage {
+            Some(u) => (u.prompt_tokens, u.completion_tokens),
+            None => {
+                tracing::warn!("Groq response missing usage data; token counts will be zero");
+                (0, 0)
+            }
+        };
+
+        Ok(CompletionResponse::new(
+            content,
+            prompt_tokens,
+            completion_tokens,
+        ))
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
+
+    fn pricing(&self) -> Pricing {
+        Pricing {
+            input_per_1k: 0.00059,  // $0.00059 per 1K input tokens
+            output_per_1k: 0.00079, // $0.00079 per 1K output tokens
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_serialization_without_optional_fields() {
+        let messages = vec![GroqMessage {
+            role: "user",
+            content: "Hello",
+        }];
+
+        let request = GroqRequest {
+            model: "llama-3.3-70b-versatile",
+            messages,
+            max_tokens: None,
+            temperature: None,
+        };
+
+        let json = serde_json::to_string(&request).expect("serialization should succeed");
+        assert!(json.contains("\"model\":\"llama-3.3-70b-versatile\""));
+        assert!(!json.contains("max_tokens"));
+        assert!(!json.contains("temperature"));
+    }
+
+    #[test]
+    fn test_completions_url_includes_openai_prefix() {
+        let provider = GroqProvider::new(
+            "test-key".to_string(),
+            "llama-3.3-70b-versatile".to_string(),
+        )
+        .expect("should create provider");
+        assert_eq!(
+            provider.completions_url(),
+            "https://api.groq.com/openai/v1/chat/completions"
+        );
+    }
+}
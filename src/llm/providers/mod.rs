@@ -7,8 +7,20 @@
 #[cfg(feature = "openai")]
 pub mod openai;
 
+#[cfg(feature = "openai")]
+pub mod openai_compatible;
+
 #[cfg(feature = "ollama")]
 pub mod ollama;
 
 #[cfg(feature = "openrouter")]
 pub mod openrouter;
+
+#[cfg(feature = "xai")]
+pub mod xai;
+
+#[cfg(feature = "groq")]
+pub mod groq;
+
+#[cfg(feature = "gemini")]
+pub mod gemini;
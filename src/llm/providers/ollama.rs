@@ -1,7 +1,10 @@
 // Copyright (c) 2025-2026 the ruley contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing};
+use crate::llm::provider::{
+    CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing, StreamCallback,
+};
+use crate::llm::stream::{for_each_ndjson_line, stream_interrupted};
 use crate::utils::error::RuleyError;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -9,10 +12,13 @@
 use std::time::Duration;
 
 const DEFAULT_MAX_TOKENS: usize = 4096;
+/// Deadline for non-streaming requests, and the idle timeout between streamed chunks.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Ollama LLM provider for local model inference.
 ///
-/// Uses the OpenAI-compatible API endpoint at `{host}/v1/chat/completions`.
+/// Uses the OpenAI-compatible API endpoint at `{host}/v1/chat/completions`, and
+/// Ollama's native `{host}/api/chat` endpoint for streamed (NDJSON) completions.
 /// Optimized for local use with a shorter timeout (30s) and no rate limiting.
 ///
 /// # Configuration
@@ -80,6 +86,84 @@ struct Usage {
     completion_tokens: usize,
 }
 
+/// Request body for the native Ollama `/api/chat` endpoint.
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<OllamaMessage<'a>>,
+    stream: bool,
+    options: OllamaChatOptions,
+}
+
+/// Model options for the native `/api/chat` endpoint.
+#[derive(Debug, Serialize)]
+struct OllamaChatOptions {
+    num_predict: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+/// One line of a streamed `/api/chat` response.
+///
+/// Intermediate lines carry a content fragment; the final line has `done: true`
+/// and the token counts. A failure mid-stream is reported as an `error` line.
+#[derive(Debug, Deserialize)]
+struct OllamaChatChunk {
+    message: Option<ChunkMessage>,
+    #[serde(default)]
+    done: bool,
+    prompt_eval_count: Option<usize>,
+    eval_count: Option<usize>,
+    error: Option<String>,
+}
+
+/// The message fragment in a streamed chunk.
+#[derive(Debug, Deserialize)]
+struct ChunkMessage {
+    #[serde(default)]
+    content: String,
+}
+
+/// Assembles a completion from `/api/chat` NDJSON lines.
+#[derive(Debug, Default)]
+struct OllamaStream {
+    content: String,
+    prompt_tokens: usize,
+    completion_tokens: usize,
+}
+
+impl OllamaStream {
+    /// Handles one NDJSON line, returning `true` on the final `done` line.
+    fn handle(&mut self, line: &str, on_delta: &StreamCallback<'_>) -> Result<bool, RuleyError> {
+        let chunk: OllamaChatChunk =
+            serde_json::from_str(line).map_err(|e| RuleyError::Provider {
+                provider: "ollama".to_string(),
+                message: format!("Failed to parse stream chunk: {}", e),
+            })?;
+
+        if let Some(error) = chunk.error {
+            return Err(RuleyError::Provider {
+                provider: "ollama".to_string(),
+                message: error,
+            });
+        }
+
+        if let Some(message) = chunk.message
+            && !message.content.is_empty()
+        {
+            on_delta(&message.content);
+            self.content.push_str(&message.content);
+        }
+
+        if chunk.done {
+            self.prompt_tokens = chunk.prompt_eval_count.unwrap_or(0);
+            self.completion_tokens = chunk.eval_count.unwrap_or(0);
+        }
+
+        Ok(chunk.done)
+    }
+}
+
 /// Response from the Ollama `/api/tags` endpoint listing available models.
 #[derive(Debug, Deserialize)]
 struct OllamaTagsResponse {
@@ -109,14 +193,15 @@ struct ErrorDetail {
 impl OllamaProvider {
     /// Creates a new Ollama provider with the given host and model.
     ///
-    /// Uses a 30-second timeout optimized for local model inference.
+    /// Uses a 30-second timeout optimized for local model inference. Streamed
+    /// requests only time out if no data arrives for 30 seconds.
     ///
     /// # Errors
     ///
     /// Returns an error if the HTTP client cannot be created.
     pub fn new(host: String, model: String) -> Result<Self, RuleyError> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(30))
+            .read_timeout(REQUEST_TIMEOUT)
             .build()
             .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
         Ok(Self {
@@ -132,21 +217,25 @@ pub fn new(host: String, model: String) -> Result<Self, RuleyError> {
     /// response. Returns a `RuleyError::Provider` with a pull suggestion when
     /// the model is missing.
     async fn validate_model(&self) -> Result<(), RuleyError> {
-        let url = format!("{}/api/tags", self.host.trim_end_matches('/'));
-
-        let response = self.client.get(&url).send().await.map_err(|e| {
-            if e.is_connect() {
-                RuleyError::Provider {
-                    provider: "ollama".to_string(),
-                    message: "Ollama server not running. Start with: ollama serve".to_string(),
-                }
-            } else {
-                RuleyError::Provider {
-                    provider: "ollama".to_string(),
-                    message: format!("Failed to query Ollama models: {e}"),
+        let response = self
+            .client
+            .get(self.url("/api/tags"))
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_connect() {
+                    RuleyError::Provider {
+                        provider: "ollama".to_string(),
+                        message: "Ollama server not running. Start with: ollama serve".to_string(),
+                    }
+                } else {
+                    RuleyError::Provider {
+                        provider: "ollama".to_string(),
+                        message: format!("Failed to query Ollama models: {e}"),
+                    }
                 }
-            }
-        })?;
+            })?;
 
         let tags: OllamaTagsResponse = response.json().await.map_err(|e| RuleyError::Provider {
             provider: "ollama".to_string(),
@@ -183,62 +272,38 @@ pub fn from_env() -> Result<Self, RuleyError> {
             std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string());
         Self::new(host, "llama3.1:70b".to_string())
     }
-}
-
-#[async_trait]
-impl LLMProvider for OllamaProvider {
-    async fn complete(
-        &self,
-        messages: &[Message],
-        options: &CompletionOptions,
-    ) -> Result<CompletionResponse, RuleyError> {
-        self.validate_model().await?;
-
-        let ollama_messages: Vec<OllamaMessage<'_>> = messages
-            .iter()
-            .map(|m| OllamaMessage {
-                role: &m.role,
-                content: &m.content,
-            })
-            .collect();
-
-        let request_body = OllamaRequest {
-            model: &self.model,
-            messages: ollama_messages,
-            max_tokens: Some(options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
-            temperature: options.temperature,
-        };
 
-        let url = format!("{}/v1/chat/completions", self.host.trim_end_matches('/'));
+    /// Builds a request URL for the given path on the configured host.
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.host.trim_end_matches('/'), path)
+    }
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_connect() {
-                    RuleyError::Provider {
-                        provider: "ollama".to_string(),
-                        message: "Ollama server not running. Start with: ollama serve".to_string(),
-                    }
-                } else if e.is_timeout() {
-                    RuleyError::Provider {
-                        provider: "ollama".to_string(),
-                        message:
-                            "Local model processing timeout. Try a smaller model or increase timeout."
-                                .to_string(),
-                    }
-                } else {
-                    RuleyError::Provider {
-                        provider: "ollama".to_string(),
-                        message: format!("Failed to connect to Ollama: {}", e),
-                    }
-                }
-            })?;
+    /// Converts a transport error into an actionable provider error.
+    fn send_error(e: reqwest::Error) -> RuleyError {
+        if e.is_connect() {
+            RuleyError::Provider {
+                provider: "ollama".to_string(),
+                message: "Ollama server not running. Start with: ollama serve".to_string(),
+            }
+        } else if e.is_timeout() {
+            RuleyError::Provider {
+                provider: "ollama".to_string(),
+                message: "Local model processing timeout. Try a smaller model or increase timeout."
+                    .to_string(),
+            }
+        } else {
+            RuleyError::Provider {
+                provider: "ollama".to_string(),
+                message: format!("Failed to connect to Ollama: {}", e),
+            }
+        }
+    }
 
+    /// Returns the response if it succeeded, or the matching error otherwise.
+    async fn check_status(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, RuleyError> {
         let status = response.status();
 
         // Handle HTTP errors
@@ -275,6 +340,45 @@ async fn complete(
             });
         }
 
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OllamaProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        self.validate_model().await?;
+
+        let ollama_messages: Vec<OllamaMessage<'_>> = messages
+            .iter()
+            .map(|m| OllamaMessage {
+                role: &m.role,
+                content: &m.content,
+            })
+            .collect();
+
+        let request_body = OllamaRequest {
+            model: &self.model,
+            messages: ollama_messages,
+            max_tokens: Some(options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
+            temperature: options.temperature,
+        };
+
+        let response = self
+            .client
+            .post(self.url("/v1/chat/completions"))
+            .timeout(REQUEST_TIMEOUT)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(Self::send_error)?;
+        let response = self.check_status(response).await?;
+
         // Parse successful response
         let response_body: OllamaResponse =
             response.json().await.map_err(|e| RuleyError::Provider {
@@ -308,10 +412,68 @@ async fn complete(
         ))
     }
 
+    async fn complete_stream(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<CompletionResponse, RuleyError> {
+        self.validate_model().await?;
+
+        let request_body = OllamaChatRequest {
+            model: &self.model,
+            messages: messages
+                .iter()
+                .map(|m| OllamaMessage {
+                    role: &m.role,
+                    content: &m.content,
+                })
+                .collect(),
+            stream: true,
+            options: OllamaChatOptions {
+                num_predict: options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+                temperature: options.temperature,
+            },
+        };
+
+        let response = self
+            .client
+            .post(self.url("/api/chat"))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(Self::send_error)?;
+        let response = self.check_status(response).await?;
+
+        let mut stream = OllamaStream::default();
+        let done = for_each_ndjson_line(response, |line| stream.handle(line, on_delta)).await?;
+        if !done {
+            return Err(stream_interrupted("ollama"));
+        }
+
+        if stream.content.is_empty() {
+            return Err(RuleyError::Provider {
+                provider: "ollama".to_string(),
+                message: "LLM returned empty response content".to_string(),
+            });
+        }
+
+        Ok(CompletionResponse::new(
+            stream.content,
+            stream.prompt_tokens,
+            stream.completion_tokens,
+        ))
+    }
+
     fn model(&self) -> &str {
         &self.model
     }
 
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.host)
+    }
+
     fn pricing(&self) -> Pricing {
         Pricing {
             input_per_1k: 0.0,
@@ -407,4 +569,39 @@ fn test_url_trailing_slash_handling() {
         let url = format!("{}/v1/chat/completions", host.trim_end_matches('/'));
         assert_eq!(url, "http://localhost:11434/v1/chat/completions");
     }
+
+    #[test]
+    fn test_stream_assembles_ndjson_lines() {
+        let mut stream = OllamaStream::default();
+        let lines = [
+            r#"{"model":"llama3.1:70b","message":{"role":"assistant","content":"Hel"},"done":false}"#,
+            r#"{"model":"llama3.1:70b","message":{"role":"assistant","content":"lo"},"done":false}"#,
+        ];
+        for line in lines {
+            assert!(!stream.handle(line, &|_| {}).unwrap());
+        }
+        let done = stream
+            .handle(
+                r#"{"model":"llama3.1:70b","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":12,"eval_count":2}"#,
+                &|_| {},
+            )
+            .unwrap();
+
+        assert!(done);
+        assert_eq!(stream.content, "Hello");
+        assert_eq!(stream.prompt_tokens, 12);
+        assert_eq!(stream.completion_tokens, 2);
+    }
+
+    #[test]
+    fn test_stream_error_line() {
+        let mut stream = OllamaStream::default();
+        let err = stream
+            .handle(
+                r#"{"error":"model runner has unexpectedly stopped"}"#,
+                &|_| {},
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("unexpectedly stopped"));
+    }
 }
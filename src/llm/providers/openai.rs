@@ -1,7 +1,10 @@
 // Copyright (c) 2025-2026 the ruley contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing};
+use crate::llm::provider::{
+    CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing, StreamCallback,
+};
+use crate::llm::stream::read_chat_completions_stream;
 use crate::utils::error::RuleyError;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -10,7 +13,14 @@
 
 const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
 const DEFAULT_MAX_TOKENS: usize = 4096;
-
+/// Deadline for non-streaming requests, and the idle timeout between streamed chunks.
+pub(crate) const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// OpenAI LLM provider using the Chat Completions API.
+///
+/// The request/response types and response handling in this module are shared
+/// with [`OpenAICompatibleProvider`](super::openai_compatible::OpenAICompatibleProvider),
+/// which targets self-hosted servers and gateways speaking the same protocol.
 pub struct OpenAIProvider {
     api_key: String,
     model: String,
@@ -19,13 +29,34 @@ pub struct OpenAIProvider {
 
 /// Request body for the OpenAI Chat Completions API.
 #[derive(Debug, Serialize)]
-struct OpenAIRequest<'a> {
+pub(crate) struct OpenAIRequest<'a> {
     model: &'a str,
     messages: Vec<OpenAIMessage<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+impl OpenAIRequest<'_> {
+    /// Enables SSE streaming, asking for usage data in the final chunk.
+    pub(crate) fn streaming(mut self) -> Self {
+        self.stream = true;
+        self.stream_options = Some(StreamOptions {
+            include_usage: true,
+        });
+        self
+    }
+}
+
+/// Streaming options for the Chat Completions API.
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
 }
 
 /// A message in the OpenAI format.
@@ -39,7 +70,8 @@ struct OpenAIMessage<'a> {
 #[derive(Debug, Deserialize)]
 struct OpenAIResponse {
     choices: Vec<Choice>,
-    usage: Usage,
+    /// Some OpenAI-compatible servers omit usage data, so this is optional.
+    usage: Option<Usage>,
 }
 
 /// A choice in the OpenAI response.
@@ -78,12 +110,15 @@ struct ErrorDetail {
 impl OpenAIProvider {
     /// Creates a new OpenAI provider with the given API key and model.
     ///
+    /// Non-streaming requests time out after 120 seconds; streamed requests
+    /// only time out if no data arrives for 120 seconds.
+    ///
     /// # Errors
     ///
     /// Returns an error if the HTTP client cannot be created.
     pub fn new(api_key: String, model: String) -> Result<Self, RuleyError> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(120))
+            .read_timeout(REQUEST_TIMEOUT)
             .build()
             .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
         Ok(Self {
@@ -107,6 +142,130 @@ pub fn from_env() -> Result<Self, RuleyError> {
     }
 }
 
+/// Builds a Chat Completions request body from provider-agnostic messages.
+pub(crate) fn build_request<'a>(
+    model: &'a str,
+    messages: &'a [Message],
+    options: &CompletionOptions,
+) -> OpenAIRequest<'a> {
+    let openai_messages: Vec<OpenAIMessage<'a>> = messages
+        .iter()
+        .map(|m| OpenAIMessage {
+            role: &m.role,
+            content: &m.content,
+        })
+        .collect();
+
+    OpenAIRequest {
+        model,
+        messages: openai_messages,
+        max_tokens: Some(options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
+        temperature: options.temperature,
+        stream: false,
+        stream_options: None,
+    }
+}
+
+/// Returns the response if it succeeded, or the matching error otherwise.
+///
+/// Handles rate limiting (429 with `retry-after`) and structured and unstructured
+/// error bodies. `provider` is used to label errors so they point at the
+/// provider the user configured.
+async fn check_status(
+    response: reqwest::Response,
+    provider: &str,
+) -> Result<reqwest::Response, RuleyError> {
+    let status = response.status();
+
+    // Handle rate limiting
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_secs);
+
+        return Err(RuleyError::RateLimited {
+            provider: provider.to_string(),
+            retry_after,
+        });
+    }
+
+    // Handle other HTTP errors
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+
+        // Try to parse the error response
+        if let Ok(error) = serde_json::from_str::<OpenAIError>(&error_text) {
+            let error_type = error
+                .error
+                .error_type
+                .or(error.error.code)
+                .unwrap_or_else(|| "unknown".to_string());
+            return Err(RuleyError::Provider {
+                provider: provider.to_string(),
+                message: format!("{}: {}", error_type, error.error.message),
+            });
+        }
+
+        return Err(RuleyError::Provider {
+            provider: provider.to_string(),
+            message: format!("HTTP {}: {}", status, error_text),
+        });
+    }
+
+    Ok(response)
+}
+
+/// Converts a Chat Completions HTTP response into a [`CompletionResponse`].
+///
+/// Tolerates responses without usage data, which some compatible servers omit.
+pub(crate) async fn parse_response(
+    response: reqwest::Response,
+    provider: &str,
+) -> Result<CompletionResponse, RuleyError> {
+    let response = check_status(response, provider).await?;
+
+    // Parse successful response
+    let response_body: OpenAIResponse = response.json().await?;
+
+    // Extract content from the first choice
+    let content = response_body
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.message.content)
+        .unwrap_or_default();
+
+    let (prompt_tokens, completion_tokens) = match response_body.usage {
+        Some(u) => (u.prompt_tokens, u.completion_tokens),
+        None => {
+            tracing::warn!(
+                "{} response missing usage data; token counts will be zero",
+                provider
+            );
+            (0, 0)
+        }
+    };
+
+    Ok(CompletionResponse::new(
+        content,
+        prompt_tokens,
+        completion_tokens,
+    ))
+}
+
+/// Reads a streamed Chat Completions HTTP response into a [`CompletionResponse`].
+pub(crate) async fn parse_stream(
+    response: reqwest::Response,
+    provider: &str,
+    on_delta: &StreamCallback<'_>,
+) -> Result<CompletionResponse, RuleyError> {
+    let response = check_status(response, provider).await?;
+    read_chat_completions_stream(response, provider, on_delta).await
+}
+
 #[async_trait]
 impl LLMProvider for OpenAIProvider {
     async fn complete(
@@ -114,87 +273,39 @@ async fn complete(
         messages: &[Message],
         options: &CompletionOptions,
     ) -> Result<CompletionResponse, RuleyError> {
-        // Convert messages to OpenAI format
-        let openai_messages: Vec<OpenAIMessage<'_>> = messages
-            .iter()
-            .map(|m| OpenAIMessage {
-                role: &m.role,
-                content: &m.content,
-            })
-            .collect();
-
-        let request_body = OpenAIRequest {
-            model: &self.model,
-            messages: openai_messages,
-            max_tokens: Some(options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
-            temperature: options.temperature,
-        };
+        let request_body = build_request(&self.model, messages, options);
 
         let response = self
             .client
             .post(OPENAI_API_URL)
+            .timeout(REQUEST_TIMEOUT)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request_body)
             .send()
             .await?;
 
-        let status = response.status();
-
-        // Handle rate limiting
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse::<u64>().ok())
-                .map(Duration::from_secs);
-
-            return Err(RuleyError::RateLimited {
-                provider: "openai".to_string(),
-                retry_after,
-            });
-        }
+        parse_response(response, "openai").await
+    }
 
-        // Handle other HTTP errors
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-
-            // Try to parse the error response
-            if let Ok(error) = serde_json::from_str::<OpenAIError>(&error_text) {
-                let error_type = error
-                    .error
-                    .error_type
-                    .or(error.error.code)
-                    .unwrap_or_else(|| "unknown".to_string());
-                return Err(RuleyError::Provider {
-                    provider: "openai".to_string(),
-                    message: format!("{}: {}", error_type, error.error.message),
-                });
-            }
+    async fn complete_stream(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let request_body = build_request(&self.model, messages, options).streaming();
 
-            return Err(RuleyError::Provider {
-                provider: "openai".to_string(),
-                message: format!("HTTP {}: {}", status, error_text),
-            });
-        }
+        let response = self
+            .client
+            .post(OPENAI_API_URL)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
 
-        // Parse successful response
-        let response_body: OpenAIResponse = response.json().await?;
-
-        // Extract content from the first choice
-        let content = response_body
-            .choices
-            .into_iter()
-            .next()
-            .and_then(|choice| choice.message.content)
-            .unwrap_or_default();
-
-        Ok(CompletionResponse::new(
-            content,
-            response_body.usage.prompt_tokens,
-            response_body.usage.completion_tokens,
-        ))
+        parse_stream(response, "openai", on_delta).await
     }
 
     fn model(&self) -> &str {
@@ -251,6 +362,8 @@ fn test_request_serialization() {
             messages,
             max_tokens: Some(1024),
             temperature: Some(0.7),
+            stream: false,
+            stream_options: None,
         };
 
         let json = serde_json::to_string(&request).expect("serialization should succeed");
@@ -271,10 +384,27 @@ fn test_request_serialization_without_optional_fields() {
             messages,
             max_tokens: None,
             temperature: None,
+            stream: false,
+            stream_options: None,
         };
 
         let json = serde_json::to_string(&request).expect("serialization should succeed");
         assert!(!json.contains("max_tokens"));
         assert!(!json.contains("temperature"));
+        assert!(!json.contains("stream"));
+    }
+
+    #[test]
+    fn test_streaming_request_requests_usage() {
+        let messages = [Message {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }];
+
+        let request = build_request("gpt-4o", &messages, &CompletionOptions::default()).streaming();
+
+        let json = serde_json::to_string(&request).expect("serialization should succeed");
+        assert!(json.contains("\"stream\":true"));
+        assert!(json.contains("\"stream_options\":{\"include_usage\":true}"));
     }
 }
@@ -0,0 +1,224 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::llm::provider::{
+    CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing, StreamCallback,
+};
+use crate::llm::providers::openai::{REQUEST_TIMEOUT, build_request, parse_response, parse_stream};
+use crate::utils::error::RuleyError;
+use async_trait::async_trait;
+use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::HashMap;
+
+/// Provider name used in error messages and configuration.
+const PROVIDER_NAME: &str = "openai-compatible";
+
+/// Generic provider for any server that speaks the OpenAI Chat Completions protocol.
+///
+/// Targets self-hosted inference servers (vLLM, LM Studio, llama.cpp server) and
+/// internal gateways. Request and response handling is shared with
+/// [`OpenAIProvider`](super::openai::OpenAIProvider); only the endpoint,
+/// authentication, extra headers and pricing differ.
+///
+/// # Configuration
+///
+/// - `[providers.openai-compatible] base_url = "..."` is required
+/// - `api_key_env` names the env var holding the API key; requests are
+///   unauthenticated when it is unset
+/// - `headers`, `pricing` and `context_window` are optional
+///
+/// # Examples
+///
+/// ```no_run
+/// use ruley::llm::providers::openai_compatible::OpenAICompatibleProvider;
+///
+/// let provider = OpenAICompatibleProvider::new(
+///     "http://localhost:8000/v1".to_string(),
+///     "Qwen/Qwen2.5-Coder-32B-Instruct".to_string(),
+/// ).expect("Failed to create provider");
+/// ```
+pub struct OpenAICompatibleProvider {
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+    headers: HeaderMap,
+    pricing: Pricing,
+    client: Client,
+}
+
+impl OpenAICompatibleProvider {
+    /// Creates a new provider for the given base URL and model.
+    ///
+    /// The base URL should include any version prefix (e.g. `http://localhost:8000/v1`);
+    /// `/chat/completions` is appended to it. Pricing defaults to zero, which suits
+    /// self-hosted servers. Timeouts match [`OpenAIProvider`](super::openai::OpenAIProvider).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(base_url: String, model: String) -> Result<Self, RuleyError> {
+        let client = Client::builder()
+            .read_timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self {
+            base_url,
+            model,
+            api_key: None,
+            headers: HeaderMap::new(),
+            pricing: Pricing {
+                input_per_1k: 0.0,
+                output_per_1k: 0.0,
+            },
+            client,
+        })
+    }
+
+    /// Sends the given key as a `Bearer` token in the `Authorization` header.
+    #[must_use]
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Adds extra HTTP headers sent with every request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a header name or value is not valid HTTP.
+    pub fn with_headers(mut self, headers: &HashMap<String, String>) -> Result<Self, RuleyError> {
+        for (name, value) in headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                RuleyError::Config(format!(
+                    "Invalid header name '{}' for {}: {}",
+                    name, PROVIDER_NAME, e
+                ))
+            })?;
+            let header_value = HeaderValue::from_str(value).map_err(|e| {
+                RuleyError::Config(format!(
+                    "Invalid value for header '{}' for {}: {}",
+                    name, PROVIDER_NAME, e
+                ))
+            })?;
+            self.headers.insert(header_name, header_value);
+        }
+        Ok(self)
+    }
+
+    /// Overrides the default (zero) pricing used for cost estimation.
+    #[must_use]
+    pub fn with_pricing(mut self, pricing: Pricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    /// Returns the full chat completions endpoint URL.
+    fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+
+    /// Starts a POST to the completions endpoint with auth and custom headers applied.
+    fn post(&self) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .post(self.completions_url())
+            .headers(self.headers.clone())
+            .header("Content-Type", "application/json");
+
+        match self.api_key {
+            Some(ref api_key) => request.header("Authorization", format!("Bearer {}", api_key)),
+            None => request,
+        }
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OpenAICompatibleProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let request_body = build_request(&self.model, messages, options);
+
+        let response = self
+            .post()
+            .timeout(REQUEST_TIMEOUT)
+            .json(&request_body)
+            .send()
+            .await?;
+
+        parse_response(response, PROVIDER_NAME).await
+    }
+
+    async fn complete_stream(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let request_body = build_request(&self.model, messages, options).streaming();
+
+        let response = self.post().json(&request_body).send().await?;
+
+        parse_stream(response, PROVIDER_NAME, on_delta).await
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
+
+    fn pricing(&self) -> Pricing {
+        self.pricing.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions_url_trailing_slash_handling() {
+        let provider = OpenAICompatibleProvider::new(
+            "http://localhost:1234/v1/".to_string(),
+            "local-model".to_string(),
+        )
+        .expect("should create provider");
+        assert_eq!(
+            provider.completions_url(),
+            "http://localhost:1234/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_with_headers_rejects_invalid_name() {
+        let headers = HashMap::from([("bad header".to_string(), "value".to_string())]);
+        let result = OpenAICompatibleProvider::new(
+            "http://localhost:1234/v1".to_string(),
+            "local-model".to_string(),
+        )
+        .expect("should create provider")
+        .with_headers(&headers);
+
+        let err = result
+            .err()
+            .expect("invalid header name should be rejected");
+        assert!(err.to_string().contains("bad header"));
+    }
+
+    #[test]
+    fn test_default_pricing_is_free() {
+        let provider = OpenAICompatibleProvider::new(
+            "http://localhost:1234/v1".to_string(),
+            "local-model".to_string(),
+        )
+        .expect("should create provider");
+        assert_eq!(provider.pricing().input_per_1k, 0.0);
+        assert_eq!(provider.pricing().output_per_1k, 0.0);
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright (c) 2025-2026 the ruley contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing};
+use crate::llm::provider::{
+    CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing, StreamCallback,
+};
+use crate::llm::stream::read_chat_completions_stream;
 use crate::utils::error::RuleyError;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -12,6 +15,8 @@
 const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
 const OPENROUTER_MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
 const DEFAULT_MAX_TOKENS: usize = 4096;
+/// Deadline for non-streaming requests, and the idle timeout between streamed chunks.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 
 /// OpenRouter LLM provider for accessing multiple models via a unified API.
 ///
@@ -51,6 +56,16 @@ struct OpenRouterRequest<'a> {
     max_tokens: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+/// Streaming options (OpenAI-compatible).
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
 }
 
 /// A message in the OpenAI-compatible format.
@@ -124,14 +139,15 @@ struct ModelPricing {
 impl OpenRouterProvider {
     /// Creates a new OpenRouter provider with the given API key and model.
     ///
-    /// Uses a 120-second timeout consistent with other cloud providers.
+    /// Non-streaming requests time out after 120 seconds; streamed requests
+    /// only time out if no data arrives for 120 seconds.
     ///
     /// # Errors
     ///
     /// Returns an error if the HTTP client cannot be created.
     pub fn new(api_key: String, model: String) -> Result<Self, RuleyError> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(120))
+            .read_timeout(REQUEST_TIMEOUT)
             .build()
             .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
         Ok(Self {
@@ -171,6 +187,7 @@ pub async fn fetch_model_pricing(&self) -> Result<(), RuleyError> {
         let response = self
             .client
             .get(OPENROUTER_MODELS_URL)
+            .timeout(REQUEST_TIMEOUT)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .send()
             .await
@@ -246,15 +263,13 @@ pub async fn fetch_model_pricing(&self) -> Result<(), RuleyError> {
 
         Ok(())
     }
-}
 
-#[async_trait]
-impl LLMProvider for OpenRouterProvider {
-    async fn complete(
-        &self,
-        messages: &[Message],
+    /// Builds a chat completions request body from provider-agnostic messages.
+    fn build_request<'a>(
+        &'a self,
+        messages: &'a [Message],
         options: &CompletionOptions,
-    ) -> Result<CompletionResponse, RuleyError> {
+    ) -> OpenRouterRequest<'a> {
         let openrouter_messages: Vec<OpenRouterMessage<'_>> = messages
             .iter()
             .map(|m| OpenRouterMessage {
@@ -263,24 +278,28 @@ async fn complete(
             })
             .collect();
 
-        let request_body = OpenRouterRequest {
+        OpenRouterRequest {
             model: &self.model,
             messages: openrouter_messages,
             max_tokens: Some(options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
             temperature: options.temperature,
-        };
+            stream: false,
+            stream_options: None,
+        }
+    }
 
-        let response = self
-            .client
+    /// Starts a POST to the chat completions endpoint with auth and attribution headers.
+    fn post(&self) -> reqwest::RequestBuilder {
+        self.client
             .post(OPENROUTER_API_URL)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .header("HTTP-Referer", "https://github.com/ruley-ai/ruley")
             .header("X-Title", "ruley")
-            .json(&request_body)
-            .send()
-            .await?;
+    }
 
+    /// Returns the response if it succeeded, or the matching error otherwise.
+    async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, RuleyError> {
         let status = response.status();
 
         // Handle rate limiting
@@ -325,6 +344,27 @@ async fn complete(
             });
         }
 
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OpenRouterProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let request_body = self.build_request(messages, options);
+
+        let response = self
+            .post()
+            .timeout(REQUEST_TIMEOUT)
+            .json(&request_body)
+            .send()
+            .await?;
+        let response = Self::check_status(response).await?;
+
         // Parse successful response
         let response_body: OpenRouterResponse = response.json().await?;
 
@@ -354,6 +394,32 @@ async fn complete(
         ))
     }
 
+    async fn complete_stream(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let mut request_body = self.build_request(messages, options);
+        request_body.stream = true;
+        request_body.stream_options = Some(StreamOptions {
+            include_usage: true,
+        });
+
+        let response = self.post().json(&request_body).send().await?;
+        let response = Self::check_status(response).await?;
+
+        let completion = read_chat_completions_stream(response, "openrouter", on_delta).await?;
+        if completion.content.is_empty() {
+            return Err(RuleyError::Provider {
+                provider: "openrouter".to_string(),
+                message: "LLM returned empty response content".to_string(),
+            });
+        }
+
+        Ok(completion)
+    }
+
     fn model(&self) -> &str {
         &self.model
     }
@@ -414,6 +480,8 @@ fn test_request_serialization() {
             messages,
             max_tokens: Some(1024),
             temperature: Some(0.7),
+            stream: false,
+            stream_options: None,
         };
 
         let json = serde_json::to_string(&request).expect("serialization should succeed");
@@ -434,6 +502,8 @@ fn test_request_serialization_without_optional_fields() {
             messages,
             max_tokens: None,
             temperature: None,
+            stream: false,
+            stream_options: None,
         };
 
         let json = serde_json::to_string(&request).expect("serialization should succeed");
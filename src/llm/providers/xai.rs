@@ -0,0 +1,325 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::llm::provider::{CompletionOptions, CompletionResponse, LLMProvider, Message, Pricing};
+use crate::utils::error::RuleyError;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const XAI_BASE_URL: &str = "https://api.x.ai/v1";
+const DEFAULT_MAX_TOKENS: usize = 4096;
+
+/// xAI LLM provider for Grok models.
+///
+/// Uses the OpenAI-compatible API at `https://api.x.ai/v1/chat/completions`.
+/// Supports rate limiting (429 handling) and 120-second timeout like cloud providers.
+///
+/// # Configuration
+///
+/// - `XAI_API_KEY` env var is required for authentication
+/// - Config file: `[providers.xai] model = "..."` sets the default model
+///
+/// # Examples
+///
+/// ```no_run
+/// use ruley::llm::providers::xai::XaiProvider;
+///
+/// let provider = XaiProvider::new(
+///     "your-api-key".to_string(),
+///     "grok-4".to_string(),
+/// ).expect("Failed to create provider");
+/// ```
+pub struct XaiProvider {
+    api_key: String,
+    model: String,
+    base_url: String,
+    client: Client,
+}
+
+/// Request body for the xAI Chat Completions API (OpenAI-compatible).
+#[derive(Debug, Serialize)]
+struct XaiRequest<'a> {
+    model: &'a str,
+    messages: Vec<XaiMessage<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+/// A message in the OpenAI-compatible format.
+#[derive(Debug, Serialize)]
+struct XaiMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// Response from the xAI Chat Completions API.
+#[derive(Debug, Deserialize)]
+struct XaiResponse {
+    choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+/// A choice in the response.
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+/// The message content in a choice.
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    content: Option<String>,
+}
+
+/// Token usage information.
+#[derive(Debug, Deserialize)]
+struct Usage {
+    prompt_tokens: usize,
+    completion_tokens: usize,
+}
+
+/// Error response from the xAI API.
+///
+/// xAI returns either an OpenAI-style `{"error": {...}}` object or a flat
+/// `{"code": "...", "error": "..."}` object depending on the failure.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum XaiError {
+    Nested { error: ErrorDetail },
+    Flat { code: Option<String>, error: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorDetail {
+    #[serde(rename = "type")]
+    error_type: Option<String>,
+    message: String,
+    code: Option<String>,
+}
+
+impl XaiProvider {
+    /// Creates a new xAI provider with the given API key and model.
+    ///
+    /// Uses a 120-second timeout consistent with other cloud providers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(api_key: String, model: String) -> Result<Self, RuleyError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| RuleyError::Config(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self {
+            api_key,
+            model,
+            base_url: XAI_BASE_URL.to_string(),
+            client,
+        })
+    }
+
+    /// Creates a new xAI provider from environment variables.
+    ///
+    /// Reads the `XAI_API_KEY` environment variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key is not set or if the HTTP client cannot be created.
+    pub fn from_env() -> Result<Self, RuleyError> {
+        let api_key =
+            std::env::var("XAI_API_KEY").map_err(|_| RuleyError::missing_api_key("xai"))?;
+        Self::new(api_key, "grok-4".to_string())
+    }
+
+    /// Overrides the API base URL (default: `https://api.x.ai/v1`).
+    ///
+    /// The `/chat/completions` path is appended to this URL.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Returns the full chat completions endpoint URL.
+    fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+}
+
+#[async_trait]
+impl LLMProvider for XaiProvider {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        options: &CompletionOptions,
+    ) -> Result<CompletionResponse, RuleyError> {
+        let xai_messages: Vec<XaiMessage<'_>> = messages
+            .iter()
+            .map(|m| XaiMessage {
+                role: &m.role,
+                content: &m.content,
+            })
+            .collect();
+
+        let request_body = XaiRequest {
+            model: &self.model,
+            messages: xai_messages,
+            max_tokens: Some(options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)),
+            temperature: options.temperature,
+        };
+
+        let response = self
+            .client
+            .post(self.completions_url())
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        // Handle rate limiting
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs);
+
+            return Err(RuleyError::RateLimited {
+                provider: "xai".to_string(),
+                retry_after,
+            });
+        }
+
+        // Handle other HTTP errors
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+
+            // Try to parse structured error response
+            if let Ok(error) = serde_json::from_str::<XaiError>(&error_text) {
+                let message = match error {
+                    XaiError::Nested { error } => {
+                        let error_type = error
+                            .error_type
+                            .or(error.code)
+                            .unwrap_or_else(|| "unknown".to_string());
+                        format!("{}: {}", error_type, error.message)
+                    }
+                    XaiError::Flat { code, error } => {
+                        format!("{}: {}", code.unwrap_or_else(|| status.to_string()), error)
+                    }
+                };
+                return Err(RuleyError::Provider {
+                    provider: "xai".to_string(),
+                    message,
+                });
+            }
+
+            return Err(RuleyError::Provider {
+                provider: "xai".to_string(),
+                message: format!("HTTP {}: {}", status, error_text),
+            });
+        }
+
+        // Parse successful response
+        let response_body: XaiResponse = response.json().await?;
+
+        let content = response_body
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| RuleyError::Provider {
+                provider: "xai".to_string(),
+                message: "LLM returned empty response content".to_string(),
+            })?;
+
+        let (prompt_tokens, completion_tokens) = match response_body.usage {
+            Some(u) => (u.prompt_tokens, u.completion_tokens),
+            None => {
+                tracing::warn!("xAI response missing usage data; token counts will be zero");
+                (0, 0)
+            }
+        };
+
+        Ok(CompletionResponse::new(
+            content,
+            prompt_tokens,
+            completion_tokens,
+        ))
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
+
+    fn pricing(&self) -> Pricing {
+        Pricing {
+            input_per_1k: 0.003,  // $0.003 per 1K input tokens
+            output_per_1k: 0.015, // $0.015 per 1K output tokens
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_serialization() {
+        let messages = vec![XaiMessage {
+            role: "user",
+            content: "Hello",
+        }];
+
+        let request = XaiRequest {
+            model: "grok-4",
+            messages,
+            max_tokens: Some(1024),
+            temperature: Some(0.7),
+        };
+
+        let json = serde_json::to_string(&request).expect("serialization should succeed");
+        assert!(json.contains("\"model\":\"grok-4\""));
+        assert!(json.contains("\"max_tokens\":1024"));
+        assert!(json.contains("\"temperature\":0.7"));
+    }
+
+    #[test]
+    fn test_completions_url_trailing_slash_handling() {
+        let provider = XaiProvider::new("test-key".to_string(), "grok-4".to_string())
+            .expect("should create provider")
+            .with_base_url("http://localhost:8080/v1/");
+        assert_eq!(
+            provider.completions_url(),
+            "http://localhost:8080/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_error_parse_both_shapes() {
+        let nested = r#"{"error":{"type":"invalid_request_error","message":"bad model"}}"#;
+        assert!(matches!(
+            serde_json::from_str::<XaiError>(nested),
+            Ok(XaiError::Nested { .. })
+        ));
+
+        let flat = r#"{"code":"Client specified an invalid argument","error":"Incorrect API key"}"#;
+        assert!(matches!(
+            serde_json::from_str::<XaiError>(flat),
+            Ok(XaiError::Flat { .. })
+        ));
+    }
+}
@@ -0,0 +1,374 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Content-addressed cache of LLM responses.
+//!
+//! Responses are stored as `{key}.json` files in `.ruley/cache/`, where the key
+//! is a SHA-256 digest of the provider, endpoint, model, completion options and
+//! messages.
+//! An identical request made by a later run is answered from the cache instead
+//! of the provider. Entries expire after a TTL, and the oldest entries are
+//! evicted once the directory exceeds its size limit.
+//!
+//! The cache is best-effort: read and write failures are logged and the
+//! request falls through to the provider.
+
+use crate::llm::provider::{CompletionOptions, CompletionResponse, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// A response as stored in `{key}.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    content: String,
+    prompt_tokens: usize,
+    completion_tokens: usize,
+}
+
+/// Disk-backed cache of completion responses.
+///
+/// # Example
+///
+/// ```no_run
+/// use ruley::llm::response_cache::ResponseCache;
+/// use std::time::Duration;
+///
+/// let cache = ResponseCache::new(
+///     ".ruley/cache",
+///     "anthropic",
+///     Duration::from_secs(7 * 24 * 60 * 60),
+///     100 * 1024 * 1024,
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    provider: String,
+    ttl: Duration,
+    max_bytes: u64,
+}
+
+impl ResponseCache {
+    /// Create a response cache.
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the cached responses (created on first write)
+    /// * `provider` - Provider name, included in every key
+    /// * `ttl` - How long an entry stays valid after it is written
+    /// * `max_bytes` - Size limit for the directory; oldest entries are evicted first
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        provider: impl Into<String>,
+        ttl: Duration,
+        max_bytes: u64,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            provider: provider.into(),
+            ttl,
+            max_bytes,
+        }
+    }
+
+    /// Returns the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Compute the cache key for a request.
+    ///
+    /// The key is a SHA-256 hex digest of the provider, the endpoint (for
+    /// providers with a configurable base URL), model, completion options and
+    /// every message's role and content.
+    pub fn key(
+        &self,
+        endpoint: Option<&str>,
+        model: &str,
+        options: &CompletionOptions,
+        messages: &[Message],
+    ) -> String {
+        let max_tokens = options
+            .max_tokens
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        let temperature = options
+            .temperature
+            .map(|t| t.to_string())
+            .unwrap_or_default();
+
+        let mut hasher = Sha256::new();
+        let header = [
+            self.provider.as_str(),
+            endpoint.unwrap_or_default(),
+            model,
+            max_tokens.as_str(),
+            temperature.as_str(),
+        ];
+        let body = messages
+            .iter()
+            .flat_map(|m| [m.role.as_str(), m.content.as_str()]);
+        for part in header.into_iter().chain(body) {
+            // Length-prefix each part so boundaries can't shift between them
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// Look up a cached response.
+    ///
+    /// Returns `None` if there is no entry, or the entry has expired or cannot
+    /// be read. Expired entries are removed. Hits are marked
+    /// [`CompletionResponse::cached`].
+    pub fn get(&self, key: &str) -> Option<CompletionResponse> {
+        let path = self.entry_path(key);
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        if self.is_expired(modified) {
+            tracing::debug!(key, "Cached response expired");
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+
+        let json = std::fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<CachedResponse>(&json) {
+            Ok(cached) => {
+                let mut response = CompletionResponse::new(
+                    cached.content,
+                    cached.prompt_tokens,
+                    cached.completion_tokens,
+                );
+                response.cached = true;
+                Some(response)
+            }
+            Err(e) => {
+                tracing::warn!("Ignoring corrupt cached response {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Store a response, then evict old entries if the cache is over its limits.
+    pub fn put(&self, key: &str, response: &CompletionResponse) {
+        let cached = CachedResponse {
+            content: response.content.clone(),
+            prompt_tokens: response.prompt_tokens,
+            completion_tokens: response.completion_tokens,
+        };
+        let path = self.entry_path(key);
+        let written = std::fs::create_dir_all(&self.dir)
+            .map_err(|e| e.to_string())
+            .and_then(|()| serde_json::to_string(&cached).map_err(|e| e.to_string()))
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            tracing::warn!("Failed to cache response in {}: {}", path.display(), e);
+            return;
+        }
+
+        self.evict();
+    }
+
+    fn is_expired(&self, modified: SystemTime) -> bool {
+        SystemTime::now()
+            .duration_since(modified)
+            .is_ok_and(|age| age > self.ttl)
+    }
+
+    /// Remove expired entries, then the oldest entries until the cache fits
+    /// within `max_bytes`.
+    fn evict(&self) {
+        let Ok(read_dir) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut entries: Vec<(PathBuf, SystemTime, u64)> = Vec::new();
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+            if self.is_expired(modified) {
+                // Another request may have removed it already
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+            entries.push((path, modified, metadata.len()));
+        }
+
+        let mut total: u64 = entries.iter().map(|(_, _, len)| len).sum();
+        if total <= self.max_bytes {
+            return;
+        }
+
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        for (path, _, len) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            tracing::debug!("Evicting cached response {}", path.display());
+            let _ = std::fs::remove_file(&path);
+            total = total.saturating_sub(len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn messages(content: &str) -> Vec<Message> {
+        vec![Message {
+            role: "user".to_string(),
+            content: content.to_string(),
+        }]
+    }
+
+    fn cache(dir: &TempDir) -> ResponseCache {
+        ResponseCache::new(
+            dir.path().join("cache"),
+            "anthropic",
+            Duration::from_secs(3600),
+            u64::MAX,
+        )
+    }
+
+    fn set_age(path: &Path, age: Duration) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn test_key_covers_provider_endpoint_model_options_and_messages() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache(&temp);
+        let options = CompletionOptions::default();
+        let key = cache.key(None, "model-a", &options, &messages("hello"));
+
+        assert_eq!(
+            key,
+            cache.key(None, "model-a", &options, &messages("hello"))
+        );
+        assert_ne!(
+            key,
+            cache.key(None, "model-b", &options, &messages("hello"))
+        );
+        assert_ne!(
+            key,
+            cache.key(None, "model-a", &options, &messages("hello!"))
+        );
+
+        let options_with_limit = CompletionOptions {
+            max_tokens: Some(100),
+            temperature: None,
+        };
+        assert_ne!(
+            key,
+            cache.key(None, "model-a", &options_with_limit, &messages("hello"))
+        );
+
+        let other_provider = ResponseCache::new(
+            temp.path().join("cache"),
+            "openai",
+            Duration::from_secs(3600),
+            u64::MAX,
+        );
+        assert_ne!(
+            key,
+            other_provider.key(None, "model-a", &options, &messages("hello"))
+        );
+
+        // Two openai-compatible servers serving the same model name
+        let local = cache.key(
+            Some("http://localhost:8000/v1"),
+            "model-a",
+            &options,
+            &messages("hello"),
+        );
+        let remote = cache.key(
+            Some("http://gpu-box:8000/v1"),
+            "model-a",
+            &options,
+            &messages("hello"),
+        );
+        assert_ne!(key, local);
+        assert_ne!(local, remote);
+    }
+
+    #[test]
+    fn test_put_then_get_returns_cached_response() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache(&temp);
+
+        assert!(cache.get("abc").is_none());
+
+        cache.put("abc", &CompletionResponse::new("Hi".to_string(), 10, 2));
+        let response = cache.get("abc").expect("entry should be cached");
+
+        assert_eq!(response.content, "Hi");
+        assert_eq!(response.prompt_tokens, 10);
+        assert_eq!(response.completion_tokens, 2);
+        assert!(response.cached);
+    }
+
+    #[test]
+    fn test_expired_entry_is_removed() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache(&temp);
+
+        cache.put("abc", &CompletionResponse::new("Hi".to_string(), 10, 2));
+        let path = cache.entry_path("abc");
+        set_age(&path, Duration::from_secs(7200));
+
+        assert!(cache.get("abc").is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_oldest_entries_evicted_over_size_limit() {
+        let temp = TempDir::new().unwrap();
+        let unbounded = cache(&temp);
+        let response = CompletionResponse::new("x".repeat(100), 1, 1);
+
+        unbounded.put("old", &response);
+        set_age(&unbounded.entry_path("old"), Duration::from_secs(60));
+        unbounded.put("mid", &response);
+        set_age(&unbounded.entry_path("mid"), Duration::from_secs(30));
+
+        let entry_size = std::fs::metadata(unbounded.entry_path("mid"))
+            .unwrap()
+            .len();
+        let bounded = ResponseCache::new(
+            temp.path().join("cache"),
+            "anthropic",
+            Duration::from_secs(3600),
+            entry_size * 2,
+        );
+        bounded.put("new", &response);
+
+        assert!(!bounded.entry_path("old").exists());
+        assert!(bounded.entry_path("mid").exists());
+        assert!(bounded.entry_path("new").exists());
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_a_miss() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache(&temp);
+        std::fs::create_dir_all(cache.dir()).unwrap();
+        std::fs::write(cache.entry_path("abc"), "not json").unwrap();
+
+        assert!(cache.get("abc").is_none());
+    }
+}
@@ -0,0 +1,483 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Token-budget file selection for codebases that don't fit in one request.
+//!
+//! Instead of chunking every file, [`select_files`] ranks files by how much
+//! they reveal about the project and keeps the highest-ranked files that fit
+//! in the budget. Manifests, entry points and configs rank highest, followed
+//! by the most informative file of each directory and files that many other
+//! files refer to.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ruley::llm::selection::select_files;
+//!
+//! let selection = select_files(&compressed_codebase, 150_000, tokenizer.as_ref());
+//! let codebase = selection.apply(compressed_codebase);
+//! ```
+
+use crate::packer::{CompressedCodebase, CompressedFile};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::tokenizer::Tokenizer;
+
+/// Score for package and build manifests.
+const MANIFEST_SCORE: usize = 100;
+
+/// Score for program and library entry points.
+const ENTRY_POINT_SCORE: usize = 80;
+
+/// Score for tooling and CI configuration.
+const CONFIG_SCORE: usize = 60;
+
+/// Score for the best-ranked file of each directory, so every directory is
+/// represented before any directory gets a second file.
+const REPRESENTATIVE_SCORE: usize = 40;
+
+/// Score per file that refers to this one.
+const REFERENCE_SCORE: usize = 10;
+
+/// Cap on the reference score, so a heavily used utility module doesn't
+/// outrank manifests and entry points.
+const MAX_REFERENCE_SCORE: usize = 100;
+
+/// Module names shorter than this are too likely to match unrelated words.
+const MIN_MODULE_NAME_LEN: usize = 3;
+
+/// Package and build manifests, matched by file name.
+const MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "requirements.txt",
+    "Pipfile",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+    "Gemfile",
+    "composer.json",
+    "CMakeLists.txt",
+    "Makefile",
+    "meson.build",
+    "mix.exs",
+    "Package.swift",
+    "deno.json",
+];
+
+/// Extensions of manifests whose file name is the project name.
+const MANIFEST_EXTENSIONS: &[&str] = &["csproj", "fsproj", "vbproj", "gemspec", "cabal"];
+
+/// File stems of entry points (`main.rs`, `index.ts`, `__main__.py`, ...).
+const ENTRY_POINT_STEMS: &[&str] = &[
+    "main", "lib", "index", "app", "server", "__main__", "program",
+];
+
+/// Tooling configuration, matched by file name.
+const CONFIG_FILES: &[&str] = &[
+    "tsconfig.json",
+    "Dockerfile",
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    ".editorconfig",
+    ".eslintrc",
+    ".eslintrc.json",
+    ".eslintrc.js",
+    ".prettierrc",
+    "rustfmt.toml",
+    "clippy.toml",
+    "deny.toml",
+    "ruff.toml",
+    "tox.ini",
+    "mypy.ini",
+    ".golangci.yml",
+    "build.rs",
+    "ruley.toml",
+];
+
+/// Stems that name their directory rather than themselves
+/// (`packer/mod.rs` is referred to as `packer`).
+const DIRECTORY_MODULE_STEMS: &[&str] = &["mod", "index", "__init__", "main", "lib"];
+
+/// Why a file ranked where it did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionReason {
+    /// Package or build manifest
+    Manifest,
+    /// Program or library entry point
+    EntryPoint,
+    /// Tooling or CI configuration
+    Config,
+    /// Best-ranked file of its directory
+    Representative,
+    /// Referred to by this many other files
+    Referenced(usize),
+}
+
+impl fmt::Display for SelectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Manifest => write!(f, "manifest"),
+            Self::EntryPoint => write!(f, "entry point"),
+            Self::Config => write!(f, "config"),
+            Self::Representative => write!(f, "representative"),
+            Self::Referenced(1) => write!(f, "1 reference"),
+            Self::Referenced(count) => write!(f, "{count} references"),
+        }
+    }
+}
+
+/// A file with its token count and ranking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedFile {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Tokens of the path and compressed content
+    pub tokens: usize,
+    /// Sum of the weights of `reasons`
+    pub score: usize,
+    /// Why the file ranked where it did (empty for unremarkable files)
+    pub reasons: Vec<SelectionReason>,
+}
+
+/// The outcome of fitting a codebase into a token budget.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileSelection {
+    /// Token budget the selection was made for
+    pub budget: usize,
+    /// Files that fit, highest-ranked first
+    pub included: Vec<RankedFile>,
+    /// Files left out, highest-ranked first
+    pub dropped: Vec<RankedFile>,
+}
+
+impl FileSelection {
+    /// Total tokens of the included files.
+    pub fn included_tokens(&self) -> usize {
+        self.included.iter().map(|f| f.tokens).sum()
+    }
+
+    /// Total tokens of the dropped files.
+    pub fn dropped_tokens(&self) -> usize {
+        self.dropped.iter().map(|f| f.tokens).sum()
+    }
+
+    /// Keep only the included files of `codebase`, in their original order,
+    /// and recompute its metadata.
+    pub fn apply(&self, codebase: CompressedCodebase) -> CompressedCodebase {
+        if self.dropped.is_empty() {
+            return codebase;
+        }
+
+        let included: HashSet<&Path> = self.included.iter().map(|f| f.path.as_path()).collect();
+        let files = codebase
+            .files
+            .into_iter()
+            .filter(|f| included.contains(f.path.as_path()))
+            .collect();
+        CompressedCodebase::new(files)
+    }
+}
+
+/// Pick the most informative files of `codebase` that fit in `budget` tokens.
+///
+/// Files are ranked by score, then by size (smaller first, so more files fit),
+/// then by path. Files are taken in rank order, skipping any that would exceed
+/// the budget; smaller files further down the ranking may still fit.
+///
+/// Token counts cover the path and compressed content of each file, matching
+/// [`calculate_tokens`](super::tokenizer::calculate_tokens).
+pub fn select_files(
+    codebase: &CompressedCodebase,
+    budget: usize,
+    tokenizer: &dyn Tokenizer,
+) -> FileSelection {
+    let mut ranked = rank_files(&codebase.files, tokenizer);
+    ranked.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.tokens.cmp(&b.tokens))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let mut selection = FileSelection {
+        budget,
+        ..Default::default()
+    };
+    let mut used = 0;
+    for file in ranked {
+        if used + file.tokens <= budget {
+            used += file.tokens;
+            selection.included.push(file);
+        } else {
+            selection.dropped.push(file);
+        }
+    }
+    selection
+}
+
+/// Count tokens and score every file.
+fn rank_files(files: &[CompressedFile], tokenizer: &dyn Tokenizer) -> Vec<RankedFile> {
+    let references = count_references(files);
+
+    let mut ranked: Vec<RankedFile> = files
+        .iter()
+        .zip(references)
+        .map(|(file, references)| {
+            let mut reasons = Vec::new();
+            if is_manifest(&file.path) {
+                reasons.push(SelectionReason::Manifest);
+            }
+            if is_entry_point(&file.path) {
+                reasons.push(SelectionReason::EntryPoint);
+            }
+            if is_config(&file.path) {
+                reasons.push(SelectionReason::Config);
+            }
+            if references > 0 {
+                reasons.push(SelectionReason::Referenced(references));
+            }
+
+            RankedFile {
+                path: file.path.clone(),
+                tokens: tokenizer.count_tokens(&file.path.to_string_lossy())
+                    + tokenizer.count_tokens(&file.compressed_content),
+                score: reasons.iter().map(|&r| reason_score(r)).sum(),
+                reasons,
+            }
+        })
+        .collect();
+
+    // The best file of each directory becomes its representative
+    let mut best: HashMap<&Path, usize> = HashMap::new();
+    for (i, file) in ranked.iter().enumerate() {
+        let dir = file.path.parent().unwrap_or(Path::new(""));
+        best.entry(dir)
+            .and_modify(|current| {
+                let other = &ranked[*current];
+                let better = (file.score, std::cmp::Reverse(file.tokens))
+                    > (other.score, std::cmp::Reverse(other.tokens));
+                if better {
+                    *current = i;
+                }
+            })
+            .or_insert(i);
+    }
+    let representatives: Vec<usize> = best.into_values().collect();
+    for i in representatives {
+        let file = &mut ranked[i];
+        file.reasons.push(SelectionReason::Representative);
+        file.score += REPRESENTATIVE_SCORE;
+    }
+
+    ranked
+}
+
+fn reason_score(reason: SelectionReason) -> usize {
+    match reason {
+        SelectionReason::Manifest => MANIFEST_SCORE,
+        SelectionReason::EntryPoint => ENTRY_POINT_SCORE,
+        SelectionReason::Config => CONFIG_SCORE,
+        SelectionReason::Representative => REPRESENTATIVE_SCORE,
+        SelectionReason::Referenced(count) => (count * REFERENCE_SCORE).min(MAX_REFERENCE_SCORE),
+    }
+}
+
+/// For each file, the number of other files that mention its module name
+/// (`walker` for `src/packer/walker.rs`, `packer` for `src/packer/mod.rs`).
+fn count_references(files: &[CompressedFile]) -> Vec<usize> {
+    let names: Vec<Option<String>> = files.iter().map(|f| module_name(&f.path)).collect();
+
+    let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, name) in names.iter().enumerate() {
+        if let Some(name) = name {
+            by_name.entry(name.as_str()).or_default().push(i);
+        }
+    }
+
+    let mut counts = vec![0; files.len()];
+    for (i, file) in files.iter().enumerate() {
+        let words: HashSet<&str> = file
+            .compressed_content
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|w| w.len() >= MIN_MODULE_NAME_LEN)
+            .collect();
+        for word in words {
+            // A file doesn't refer to itself or to its namesakes
+            if names[i].as_deref() == Some(word) {
+                continue;
+            }
+            if let Some(targets) = by_name.get(word) {
+                for &target in targets {
+                    counts[target] += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Name other files use to refer to `path`, if it is distinctive enough.
+fn module_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let name = if DIRECTORY_MODULE_STEMS.contains(&stem) {
+        path.parent()?.file_name()?.to_str()?
+    } else {
+        stem
+    };
+    (name.len() >= MIN_MODULE_NAME_LEN).then(|| name.to_string())
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or("")
+}
+
+fn is_manifest(path: &Path) -> bool {
+    MANIFESTS.contains(&file_name(path))
+        || path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| MANIFEST_EXTENSIONS.contains(&ext))
+}
+
+fn is_entry_point(path: &Path) -> bool {
+    !is_manifest(path)
+        && !is_config(path)
+        && path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|stem| ENTRY_POINT_STEMS.contains(&stem.to_lowercase().as_str()))
+}
+
+fn is_config(path: &Path) -> bool {
+    let name = file_name(path);
+    CONFIG_FILES.contains(&name)
+        // vite.config.ts, jest.config.js, ...
+        || name.contains(".config.")
+        || path
+            .parent()
+            .is_some_and(|dir| dir.ends_with(".github/workflows"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::test_support::{WordTokenizer, create_test_codebase};
+
+    fn paths(files: &[RankedFile]) -> Vec<&str> {
+        files.iter().map(|f| f.path.to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_everything_fits() {
+        let codebase = create_test_codebase(vec![("src/a.rs", "one two"), ("src/b.rs", "three")]);
+        let selection = select_files(&codebase, 100, &WordTokenizer);
+
+        assert_eq!(selection.included.len(), 2);
+        assert!(selection.dropped.is_empty());
+        assert_eq!(selection.included_tokens(), 5);
+        assert_eq!(selection.apply(codebase).metadata.total_files, 2);
+    }
+
+    #[test]
+    fn test_classification() {
+        assert!(is_manifest(Path::new("Cargo.toml")));
+        assert!(is_manifest(Path::new("web/package.json")));
+        assert!(is_manifest(Path::new("App/App.csproj")));
+        assert!(is_entry_point(Path::new("src/main.rs")));
+        assert!(is_entry_point(Path::new("src/index.ts")));
+        assert!(is_entry_point(Path::new("pkg/__main__.py")));
+        assert!(is_config(Path::new("tsconfig.json")));
+        assert!(is_config(Path::new("vite.config.ts")));
+        assert!(is_config(Path::new(".github/workflows/ci.yml")));
+        assert!(!is_entry_point(Path::new("src/domain.rs")));
+        assert!(!is_config(Path::new("src/config.rs")));
+    }
+
+    #[test]
+    fn test_count_references() {
+        let codebase = create_test_codebase(vec![
+            ("src/walker.rs", "fn scan() {}"),
+            ("src/packer/mod.rs", "mod walker;"),
+            ("src/lib.rs", "use packer::walker;"),
+            ("src/other.rs", "let walkers = 1;"),
+        ]);
+        let counts = count_references(&codebase.files);
+
+        // walker.rs is named by packer/mod.rs and lib.rs, not by `walkers`
+        assert_eq!(counts, vec![2, 1, 0, 0]);
+        assert_eq!(
+            module_name(Path::new("src/packer/mod.rs")).as_deref(),
+            Some("packer")
+        );
+        assert_eq!(module_name(Path::new("src/io.rs")), None);
+    }
+
+    #[test]
+    fn test_budget_prefers_informative_files() {
+        let codebase = create_test_codebase(vec![
+            ("Cargo.toml", "name = ruley"),
+            ("src/main.rs", "fn main() { helpers::run() }"),
+            ("src/helpers.rs", "pub fn run() {}"),
+            ("src/zzz.rs", "a b c d e f g h i j"),
+            ("src/aaa.rs", "a b c d e f g h i j"),
+            ("docs/guide.md", "read me"),
+        ]);
+        let selection = select_files(&codebase, 20, &WordTokenizer);
+
+        assert_eq!(
+            paths(&selection.included),
+            vec![
+                "Cargo.toml",
+                "src/main.rs",
+                "docs/guide.md",
+                "src/helpers.rs"
+            ]
+        );
+        assert_eq!(paths(&selection.dropped), vec!["src/aaa.rs", "src/zzz.rs"]);
+        assert!(selection.included_tokens() <= 20);
+        assert_eq!(selection.dropped_tokens(), 22);
+
+        let main = &selection.included[1];
+        assert_eq!(
+            main.reasons,
+            vec![SelectionReason::EntryPoint, SelectionReason::Representative]
+        );
+        assert_eq!(
+            selection.included[3].reasons,
+            vec![SelectionReason::Referenced(1)]
+        );
+
+        let selected = selection.apply(codebase);
+        assert_eq!(selected.metadata.total_files, 4);
+        assert_eq!(
+            selected.files[0].path,
+            PathBuf::from("Cargo.toml"),
+            "original order is kept"
+        );
+    }
+
+    #[test]
+    fn test_smaller_files_fill_remaining_budget() {
+        let codebase = create_test_codebase(vec![
+            ("src/main.rs", "a b c d e f g h"),
+            ("src/big.rs", "a b c d e f g h i j k l"),
+            ("src/small.rs", "a"),
+        ]);
+        let selection = select_files(&codebase, 12, &WordTokenizer);
+
+        assert_eq!(
+            paths(&selection.included),
+            vec!["src/main.rs", "src/small.rs"]
+        );
+        assert_eq!(paths(&selection.dropped), vec!["src/big.rs"]);
+    }
+}
@@ -0,0 +1,445 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Incremental parsers for streamed LLM responses.
+//!
+//! Cloud providers stream completions as Server-Sent Events (SSE), while
+//! Ollama's native API streams newline-delimited JSON (NDJSON). Both parsers
+//! accept arbitrary byte chunks as they arrive from the network, buffering any
+//! partial line (including split UTF-8 sequences) until the rest arrives.
+
+use crate::utils::error::RuleyError;
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+use {
+    crate::llm::provider::{CompletionResponse, StreamCallback},
+    serde::Deserialize,
+};
+
+/// Splits a byte stream into complete lines.
+///
+/// Trailing `\r` is stripped so CRLF and LF line endings are handled alike.
+#[derive(Debug, Default)]
+pub(crate) struct LineBuffer {
+    buffer: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Appends a chunk and returns every line completed by it.
+    pub(crate) fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line[..line.len() - 1]);
+            lines.push(line.trim_end_matches('\r').to_string());
+        }
+        lines
+    }
+
+    /// Returns any buffered text not terminated by a newline.
+    #[cfg(any(feature = "ollama", test))]
+    pub(crate) fn remainder(&mut self) -> Option<String> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let rest = String::from_utf8_lossy(&self.buffer).trim().to_string();
+        self.buffer.clear();
+        if rest.is_empty() { None } else { Some(rest) }
+    }
+}
+
+/// A single Server-Sent Event.
+#[cfg(any(feature = "anthropic", feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SseEvent {
+    /// The `event:` field, if the server sent one.
+    pub(crate) event: Option<String>,
+    /// The `data:` field; multiple data lines are joined with `\n`.
+    pub(crate) data: String,
+}
+
+/// Incremental Server-Sent Events parser.
+///
+/// Implements the subset of the SSE format used by LLM APIs: `event:` and
+/// `data:` fields, comment lines, and blank-line event dispatch.
+#[cfg(any(feature = "anthropic", feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Default)]
+pub(crate) struct SseParser {
+    lines: LineBuffer,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+#[cfg(any(feature = "anthropic", feature = "openai", feature = "openrouter"))]
+impl SseParser {
+    /// Appends a chunk and returns every event completed by it.
+    pub(crate) fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+
+        for line in self.lines.feed(chunk) {
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event: self.event.take(),
+                        data: self.data.join("\n"),
+                    });
+                    self.data.clear();
+                }
+                self.event = None;
+                continue;
+            }
+
+            // Comment lines (e.g. OpenRouter's ": OPENROUTER PROCESSING" keep-alives)
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line.as_str(), ""),
+            };
+
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        events
+    }
+}
+
+/// Feeds a response body through an [`SseParser`], calling `on_event` for each event.
+///
+/// Stops as soon as `on_event` returns `Ok(true)`. Returns whether the handler
+/// signalled completion; `false` means the body ended first. Transport errors
+/// while reading the body surface as retryable [`RuleyError::NetworkError`]s.
+#[cfg(any(feature = "anthropic", feature = "openai", feature = "openrouter"))]
+pub(crate) async fn for_each_sse_event(
+    mut response: reqwest::Response,
+    mut on_event: impl FnMut(SseEvent) -> Result<bool, RuleyError>,
+) -> Result<bool, RuleyError> {
+    let mut parser = SseParser::default();
+    while let Some(chunk) = response.chunk().await? {
+        for event in parser.feed(&chunk) {
+            if on_event(event)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Feeds a newline-delimited JSON body to `on_line` one line at a time.
+///
+/// Same stopping and error semantics as [`for_each_sse_event`]. Blank lines are
+/// skipped, and a final line without a trailing newline is still delivered.
+#[cfg(feature = "ollama")]
+pub(crate) async fn for_each_ndjson_line(
+    mut response: reqwest::Response,
+    mut on_line: impl FnMut(&str) -> Result<bool, RuleyError>,
+) -> Result<bool, RuleyError> {
+    let mut lines = LineBuffer::default();
+    while let Some(chunk) = response.chunk().await? {
+        for line in lines.feed(&chunk) {
+            if !line.trim().is_empty() && on_line(&line)? {
+                return Ok(true);
+            }
+        }
+    }
+    match lines.remainder() {
+        Some(line) => on_line(&line),
+        None => Ok(false),
+    }
+}
+
+/// A chunk of an OpenAI-format Chat Completions stream.
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    usage: Option<ChunkUsage>,
+    /// Set by gateways (e.g. OpenRouter) that report failures mid-stream.
+    error: Option<ChunkError>,
+}
+
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    #[serde(default)]
+    delta: ChunkDelta,
+    finish_reason: Option<String>,
+}
+
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Default, Deserialize)]
+struct ChunkDelta {
+    content: Option<String>,
+}
+
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Deserialize)]
+struct ChunkUsage {
+    prompt_tokens: usize,
+    completion_tokens: usize,
+}
+
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Deserialize)]
+struct ChunkError {
+    message: Option<String>,
+    /// Numeric for some gateways, a string for others.
+    code: Option<serde_json::Value>,
+}
+
+/// Assembles a completion from OpenAI-format Chat Completions stream events.
+///
+/// Used by every provider that speaks the Chat Completions protocol. Usage data
+/// arrives in the final chunk when the request sets `stream_options.include_usage`.
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+#[derive(Debug, Default)]
+pub(crate) struct ChatCompletionsStream {
+    content: String,
+    usage: Option<(usize, usize)>,
+    finish_reason: Option<String>,
+}
+
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+impl ChatCompletionsStream {
+    /// Handles one SSE `data` payload, returning `true` once the `[DONE]` sentinel arrives.
+    pub(crate) fn handle(
+        &mut self,
+        data: &str,
+        provider: &str,
+        on_delta: &StreamCallback<'_>,
+    ) -> Result<bool, RuleyError> {
+        if data.trim() == "[DONE]" {
+            return Ok(true);
+        }
+
+        let chunk: ChatCompletionChunk =
+            serde_json::from_str(data).map_err(|e| RuleyError::Provider {
+                provider: provider.to_string(),
+                message: format!("Failed to parse stream chunk: {}", e),
+            })?;
+
+        if let Some(error) = chunk.error {
+            let code = error
+                .code
+                .map(|c| c.as_str().map_or_else(|| c.to_string(), str::to_string))
+                .unwrap_or_else(|| "unknown".to_string());
+            return Err(RuleyError::Provider {
+                provider: provider.to_string(),
+                message: format!(
+                    "{}: {}",
+                    code,
+                    error
+                        .message
+                        .unwrap_or_else(|| "Unknown stream error".to_string())
+                ),
+            });
+        }
+
+        for choice in chunk.choices {
+            if let Some(text) = choice.delta.content.filter(|t| !t.is_empty()) {
+                on_delta(&text);
+                self.content.push_str(&text);
+            }
+            if choice.finish_reason.is_some() {
+                self.finish_reason = choice.finish_reason;
+            }
+        }
+
+        if let Some(usage) = chunk.usage {
+            self.usage = Some((usage.prompt_tokens, usage.completion_tokens));
+        }
+
+        Ok(false)
+    }
+
+    /// Builds the final response once the stream has ended.
+    ///
+    /// A stream that closed without `[DONE]` or a finish reason was cut off,
+    /// and is reported as [`stream_interrupted`] so the request is retried.
+    pub(crate) fn finish(
+        self,
+        provider: &str,
+        done: bool,
+    ) -> Result<CompletionResponse, RuleyError> {
+        if !done && self.finish_reason.is_none() {
+            return Err(stream_interrupted(provider));
+        }
+
+        let (prompt_tokens, completion_tokens) = self.usage.unwrap_or_else(|| {
+            tracing::warn!(
+                "{} stream missing usage data; token counts will be zero",
+                provider
+            );
+            (0, 0)
+        });
+
+        Ok(CompletionResponse::new(
+            self.content,
+            prompt_tokens,
+            completion_tokens,
+        ))
+    }
+}
+
+/// Reads an OpenAI-format Chat Completions SSE stream to completion.
+#[cfg(any(feature = "openai", feature = "openrouter"))]
+pub(crate) async fn read_chat_completions_stream(
+    response: reqwest::Response,
+    provider: &str,
+    on_delta: &StreamCallback<'_>,
+) -> Result<CompletionResponse, RuleyError> {
+    let mut stream = ChatCompletionsStream::default();
+    let done = for_each_sse_event(response, |event| {
+        stream.handle(&event.data, provider, on_delta)
+    })
+    .await?;
+    stream.finish(provider, done)
+}
+
+/// Error returned when a stream closes before the provider signalled completion.
+///
+/// Reported as a network error so [`LLMClient`](crate::llm::client::LLMClient)
+/// retries the request.
+pub(crate) fn stream_interrupted(provider: &str) -> RuleyError {
+    RuleyError::NetworkError {
+        message: format!("{} stream ended before the response was complete", provider),
+        source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_handles_split_lines_and_crlf() {
+        let mut buffer = LineBuffer::default();
+        assert!(buffer.feed(b"{\"a\":").is_empty());
+        assert_eq!(
+            buffer.feed(b"1}\r\n{\"b\":2}\n{\"c\""),
+            vec!["{\"a\":1}", "{\"b\":2}"]
+        );
+        assert_eq!(buffer.remainder().as_deref(), Some("{\"c\""));
+        assert!(buffer.remainder().is_none());
+    }
+
+    #[test]
+    fn test_line_buffer_handles_split_utf8() {
+        let mut buffer = LineBuffer::default();
+        let text = "héllo\n".as_bytes();
+        // Split inside the two-byte 'é'
+        assert!(buffer.feed(&text[..2]).is_empty());
+        assert_eq!(buffer.feed(&text[2..]), vec!["héllo"]);
+    }
+
+    #[cfg(any(feature = "anthropic", feature = "openai", feature = "openrouter"))]
+    #[test]
+    fn test_sse_parser_dispatches_on_blank_line() {
+        let mut parser = SseParser::default();
+        let events = parser.feed(b"event: message_start\ndata: {\"x\":1}\n\ndata: {\"y\"");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: Some("message_start".to_string()),
+                data: "{\"x\":1}".to_string(),
+            }]
+        );
+
+        let events = parser.feed(b":2}\n\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, None);
+        assert_eq!(events[0].data, "{\"y\":2}");
+    }
+
+    #[cfg(any(feature = "anthropic", feature = "openai", feature = "openrouter"))]
+    #[test]
+    fn test_sse_parser_ignores_comments_and_joins_data_lines() {
+        let mut parser = SseParser::default();
+        let events = parser.feed(b": keep-alive\n\ndata: line one\ndata: line two\n\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "line one\nline two");
+    }
+
+    #[cfg(any(feature = "openai", feature = "openrouter"))]
+    fn collect_deltas() -> (
+        std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+        impl Fn(&str) + Send + Sync,
+    ) {
+        let deltas = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = deltas.clone();
+        (deltas, move |d: &str| {
+            sink.lock().unwrap().push(d.to_string())
+        })
+    }
+
+    #[cfg(any(feature = "openai", feature = "openrouter"))]
+    #[test]
+    fn test_chat_completions_stream_assembles_content_and_usage() {
+        let (deltas, on_delta) = collect_deltas();
+        let mut stream = ChatCompletionsStream::default();
+
+        let events = [
+            r#"{"choices":[{"delta":{"role":"assistant","content":""}}]}"#,
+            r#"{"choices":[{"delta":{"content":"Hello"}}]}"#,
+            r#"{"choices":[{"delta":{"content":" world"},"finish_reason":"stop"}]}"#,
+            r#"{"choices":[],"usage":{"prompt_tokens":10,"completion_tokens":2}}"#,
+        ];
+        for data in events {
+            assert!(!stream.handle(data, "openai", &on_delta).unwrap());
+        }
+        assert!(stream.handle("[DONE]", "openai", &on_delta).unwrap());
+
+        let response = stream.finish("openai", true).unwrap();
+        assert_eq!(response.content, "Hello world");
+        assert_eq!(response.prompt_tokens, 10);
+        assert_eq!(response.completion_tokens, 2);
+        assert_eq!(*deltas.lock().unwrap(), vec!["Hello", " world"]);
+    }
+
+    #[cfg(any(feature = "openai", feature = "openrouter"))]
+    #[test]
+    fn test_chat_completions_stream_cut_off_is_retryable() {
+        let (_, on_delta) = collect_deltas();
+        let mut stream = ChatCompletionsStream::default();
+        stream
+            .handle(
+                r#"{"choices":[{"delta":{"content":"partial"}}]}"#,
+                "openai",
+                &on_delta,
+            )
+            .unwrap();
+
+        let err = stream.finish("openai", false).unwrap_err();
+        assert!(matches!(err, RuleyError::NetworkError { .. }));
+    }
+
+    #[cfg(any(feature = "openai", feature = "openrouter"))]
+    #[test]
+    fn test_chat_completions_stream_error_chunk() {
+        let (_, on_delta) = collect_deltas();
+        let mut stream = ChatCompletionsStream::default();
+        let err = stream
+            .handle(
+                r#"{"error":{"code":502,"message":"Provider disconnected"},"choices":[{"delta":{"content":""},"finish_reason":"error"}]}"#,
+                "openrouter",
+                &on_delta,
+            )
+            .unwrap_err();
+
+        match err {
+            RuleyError::Provider { provider, message } => {
+                assert_eq!(provider, "openrouter");
+                assert_eq!(message, "502: Provider disconnected");
+            }
+            other => panic!("Expected Provider error, got {:?}", other),
+        }
+    }
+}
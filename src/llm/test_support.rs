@@ -0,0 +1,35 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tokenizer and codebase fixtures shared by unit tests.
+
+use crate::llm::tokenizer::Tokenizer;
+use crate::packer::{CompressedCodebase, CompressedFile, CompressionMethod};
+use std::path::PathBuf;
+
+/// Simple tokenizer for testing that counts words as tokens.
+pub(crate) struct WordTokenizer;
+
+impl Tokenizer for WordTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+}
+
+/// Build an uncompressed codebase from `(path, content)` pairs.
+pub(crate) fn create_test_codebase(files: Vec<(&str, &str)>) -> CompressedCodebase {
+    let compressed_files: Vec<_> = files
+        .into_iter()
+        .map(|(path, content)| CompressedFile {
+            path: PathBuf::from(path),
+            original_content: content.to_string(),
+            compressed_content: content.to_string(),
+            compression_method: CompressionMethod::None,
+            original_size: content.len(),
+            compressed_size: content.len(),
+            language: None,
+        })
+        .collect();
+
+    CompressedCodebase::new(compressed_files)
+}
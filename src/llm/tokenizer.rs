@@ -78,6 +78,25 @@ pub fn from_model_name(model: &str) -> Self {
         // Default to Gpt4 (cl100k_base) for unknown models
         Self::Gpt4
     }
+
+    /// Create from a tiktoken encoding name ("cl100k_base" or "o200k_base").
+    ///
+    /// Returns `None` for unknown encodings.
+    pub fn from_encoding_name(encoding: &str) -> Option<Self> {
+        match encoding {
+            "cl100k_base" => Some(Self::Gpt4),
+            "o200k_base" => Some(Self::Gpt4o),
+            _ => None,
+        }
+    }
+
+    /// Returns the tiktoken encoding name used by this model type.
+    pub fn encoding_name(&self) -> &'static str {
+        match self {
+            Self::Gpt4 | Self::Claude => "cl100k_base",
+            Self::Gpt4o => "o200k_base",
+        }
+    }
 }
 
 /// Tokenizer using tiktoken for OpenAI models.
@@ -20,7 +20,10 @@ async fn main() {
 
     if let Err(e) = run_main().await {
         display_error(&e, verbose);
-        std::process::exit(1);
+        let code = e
+            .downcast_ref::<RuleyError>()
+            .map_or(1, RuleyError::exit_code);
+        std::process::exit(code);
     }
 }
 
@@ -40,3 +40,133 @@ fn default_directory(&self) -> &str {
         ".cursor/rules"
     }
 }
+
+/// Scope a Cursor rule to one package of a workspace by prefixing every glob
+/// in its frontmatter with `scope` (`src/**/*.ts` -> `apps/web/src/**/*.ts`).
+///
+/// Rules of workspace packages all live in the root `.cursor/rules/`, so
+/// unscoped globs would match files of every package. Globs are written back
+/// comma-separated, the way Cursor itself writes them. A rule without globs
+/// gets `<scope>/**`, and a rule without frontmatter gets one. A leading
+/// `<!-- ... -->` metadata header is kept above the frontmatter.
+pub fn scope_globs(content: &str, scope: &str) -> String {
+    if content.starts_with("<!--")
+        && let Some(end) = content.find("-->\n")
+    {
+        let (header, rest) = content.split_at(end + "-->\n".len());
+        return format!("{header}{}", scope_globs(rest, scope));
+    }
+
+    let scope = scope.trim_end_matches('/');
+    let default_globs = format!("globs: {scope}/**");
+
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return format!(
+            "---\ndescription: Rules for the {scope} package\n{default_globs}\nalwaysApply: false\n---\n\n{content}"
+        );
+    };
+    let Some(end) = rest.find("\n---") else {
+        return content.to_string();
+    };
+    let (frontmatter, body) = rest.split_at(end);
+
+    let mut lines = Vec::new();
+    let mut has_globs = false;
+    let mut list = frontmatter.lines().peekable();
+    while let Some(line) = list.next() {
+        let Some(value) = line.strip_prefix("globs:") else {
+            lines.push(line.to_string());
+            continue;
+        };
+        has_globs = true;
+
+        let mut globs: Vec<String> = value
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|g| g.trim().trim_matches(['"', '\'']).to_string())
+            .filter(|g| !g.is_empty())
+            .collect();
+        // Block sequence form: `globs:` followed by `  - pattern` lines
+        while let Some(item) = list
+            .peek()
+            .and_then(|next| next.trim_start().strip_prefix("- "))
+        {
+            globs.push(item.trim().trim_matches(['"', '\'']).to_string());
+            list.next();
+        }
+
+        if globs.is_empty() {
+            lines.push(default_globs.clone());
+        } else {
+            let scoped: Vec<String> = globs
+                .iter()
+                .map(|glob| {
+                    let glob = glob.trim_start_matches("./").trim_start_matches('/');
+                    format!("{scope}/{glob}")
+                })
+                .collect();
+            lines.push(format!("globs: {}", scoped.join(",")));
+        }
+    }
+    if !has_globs {
+        lines.push(default_globs);
+    }
+
+    format!("---\n{}{}", lines.join("\n"), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_globs_forms() {
+        let inline = "---\ndescription: Web rules\nglobs: src/**/*.ts, \"**/*.tsx\"\nalwaysApply: false\n---\n\n# Rules\n";
+        assert_eq!(
+            scope_globs(inline, "apps/web/"),
+            "---\ndescription: Web rules\nglobs: apps/web/src/**/*.ts,apps/web/**/*.tsx\nalwaysApply: false\n---\n\n# Rules\n"
+        );
+
+        let list = "---\nglobs:\n  - \"./src/**\"\n  - '*.json'\nalwaysApply: true\n---\nBody";
+        assert_eq!(
+            scope_globs(list, "pkg"),
+            "---\nglobs: pkg/src/**,pkg/*.json\nalwaysApply: true\n---\nBody"
+        );
+
+        let flow = "---\nglobs: [\"**/*.rs\"]\n---\n";
+        assert_eq!(
+            scope_globs(flow, "crates/core"),
+            "---\nglobs: crates/core/**/*.rs\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_scope_globs_adds_missing_globs() {
+        assert_eq!(
+            scope_globs("---\ndescription: x\nglobs:\n---\nBody", "pkg"),
+            "---\ndescription: x\nglobs: pkg/**\n---\nBody"
+        );
+        assert_eq!(
+            scope_globs("---\ndescription: x\n---\nBody", "pkg"),
+            "---\ndescription: x\nglobs: pkg/**\n---\nBody"
+        );
+        assert_eq!(
+            scope_globs("# Rules\n", "pkg"),
+            "---\ndescription: Rules for the pkg package\nglobs: pkg/**\nalwaysApply: false\n---\n\n# Rules\n"
+        );
+    }
+
+    #[test]
+    fn test_scope_globs_finalized_content() {
+        let header = "<!-- Generated by ruley v0.1.0 | 2026-01-29T12:00:00Z | anthropic/m | tokens: 1/1 | cost: $0.0000 -->\n";
+        let finalized = format!("{header}---\nglobs: src/**\nalwaysApply: false\n---\n\n# Rules\n");
+        let scoped = scope_globs(&finalized, "apps/web");
+        assert_eq!(
+            scoped,
+            format!("{header}---\nglobs: apps/web/src/**\nalwaysApply: false\n---\n\n# Rules\n")
+        );
+        assert_eq!(scoped.matches("---\n").count(), 2);
+    }
+}
@@ -34,6 +34,7 @@
 pub mod generic;
 pub mod json;
 pub mod windsurf;
+pub mod workspace;
 mod writer;
 
 pub use aider::AiderFormatter;
@@ -43,6 +44,7 @@
 pub use generic::GenericFormatter;
 pub use json::JsonFormatter;
 pub use windsurf::WindsurfFormatter;
+pub use workspace::{PackageOutcome, SUMMARY_FILENAME, render_workspace_summary};
 pub use writer::{ConflictStrategy, OutputResult, WriteOptions, write_output};
 
 use crate::generator::rules::GeneratedRules;
@@ -0,0 +1,124 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Root summary of the rules generated for each package of a workspace.
+
+use crate::packer::{Workspace, WorkspaceMember};
+use std::path::{Path, PathBuf};
+
+/// File name of the summary written to the workspace output root.
+pub const SUMMARY_FILENAME: &str = "WORKSPACE_RULES.md";
+
+/// Outcome of generating rules for one workspace package.
+#[derive(Debug, Clone)]
+pub struct PackageOutcome {
+    /// The package the pipeline ran for
+    pub member: WorkspaceMember,
+    /// Rule files written for the package (skipped files are not listed)
+    pub files: Vec<PathBuf>,
+    /// Error message if the package's pipeline failed
+    pub error: Option<String>,
+}
+
+/// Render the markdown summary listing every package and its rule files.
+///
+/// File paths are shown relative to `root` when they are below it.
+pub fn render_workspace_summary(
+    workspace: &Workspace,
+    outcomes: &[PackageOutcome],
+    root: &Path,
+) -> String {
+    let mut out = String::from("# Workspace Rules\n\n");
+    out.push_str(&format!(
+        "Rules generated by ruley for each package of this {} workspace.\n\n",
+        workspace.kind
+    ));
+    out.push_str("| Package | Path | Rule files |\n");
+    out.push_str("| --- | --- | --- |\n");
+
+    for outcome in outcomes {
+        let files = match outcome.error {
+            Some(ref error) => format!("failed: {}", escape_cell(error)),
+            None if outcome.files.is_empty() => "none written".to_string(),
+            None => outcome
+                .files
+                .iter()
+                .map(|file| {
+                    format!(
+                        "`{}`",
+                        display_path(file.strip_prefix(root).unwrap_or(file))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        out.push_str(&format!(
+            "| {} | `{}` | {} |\n",
+            escape_cell(&outcome.member.name),
+            display_path(&outcome.member.path),
+            files
+        ));
+    }
+
+    out
+}
+
+/// Path with `/` separators, as shown in markdown on every platform.
+fn display_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Keep a value on one line and from splitting the table row.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packer::WorkspaceKind;
+
+    fn member(name: &str, path: &str) -> WorkspaceMember {
+        WorkspaceMember {
+            name: name.to_string(),
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn test_render_workspace_summary() {
+        let workspace = Workspace {
+            kind: WorkspaceKind::Cargo,
+            members: vec![member("core", "crates/core"), member("cli", "crates/cli")],
+        };
+        let outcomes = vec![
+            PackageOutcome {
+                member: member("core", "crates/core"),
+                files: vec![
+                    PathBuf::from("/repo/crates/core/CLAUDE.md"),
+                    PathBuf::from("/repo/.cursor/rules/core.mdc"),
+                ],
+                error: None,
+            },
+            PackageOutcome {
+                member: member("cli", "crates/cli"),
+                files: Vec::new(),
+                error: Some("LLM error: rate limited | retry\nlater".to_string()),
+            },
+        ];
+
+        let summary = render_workspace_summary(&workspace, &outcomes, Path::new("/repo"));
+        assert!(summary.contains("this Cargo workspace"));
+        assert!(summary.contains(
+            "| core | `crates/core` | `crates/core/CLAUDE.md`, `.cursor/rules/core.mdc` |"
+        ));
+        assert!(
+            summary.contains(
+                "| cli | `crates/cli` | failed: LLM error: rate limited \\| retry later |"
+            )
+        );
+    }
+}
@@ -625,10 +625,9 @@ async fn smart_merge_file(
 
     // Track cost
     if let Some(tracker) = cost_tracker {
-        tracker.add_operation(
+        tracker.add_completion(
             format!("smart_merge_{}", existing_path.display()),
-            response.prompt_tokens,
-            response.completion_tokens,
+            &response,
         );
     }
 
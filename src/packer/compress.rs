@@ -1,9 +1,13 @@
 // Copyright (c) 2025-2026 the ruley contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use super::CompressionLevel;
+use super::query::CompressionQuery;
 use crate::{MergedConfig, utils::error::RuleyError};
 use regex::Regex;
-use std::sync::LazyLock;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Language {
@@ -17,10 +21,15 @@ pub enum Language {
     Rust,
     Go,
     Java,
+    /// C# (.cs files)
+    CSharp,
+    Kotlin,
     C,
     Cpp,
     Ruby,
     Php,
+    /// Shell scripts (.sh/.bash files and files with a shell shebang)
+    Shell,
     /// Unknown language - used when language detection fails or file type is not recognized
     Unknown,
 }
@@ -36,16 +45,45 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Self::Rust => "rust",
             Self::Go => "go",
             Self::Java => "java",
+            Self::CSharp => "csharp",
+            Self::Kotlin => "kotlin",
             Self::C => "c",
             Self::Cpp => "cpp",
             Self::Ruby => "ruby",
             Self::Php => "php",
+            Self::Shell => "shell",
             Self::Unknown => "unknown",
         };
         write!(f, "{}", name)
     }
 }
 
+impl std::str::FromStr for Language {
+    type Err = String;
+
+    /// Parse the lowercase name printed by `Display` (e.g. `rust`, `csharp`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "typescript" => Ok(Self::TypeScript),
+            "tsx" => Ok(Self::Tsx),
+            "javascript" => Ok(Self::JavaScript),
+            "jsx" => Ok(Self::Jsx),
+            "python" => Ok(Self::Python),
+            "rust" => Ok(Self::Rust),
+            "go" => Ok(Self::Go),
+            "java" => Ok(Self::Java),
+            "csharp" => Ok(Self::CSharp),
+            "kotlin" => Ok(Self::Kotlin),
+            "c" => Ok(Self::C),
+            "cpp" => Ok(Self::Cpp),
+            "ruby" => Ok(Self::Ruby),
+            "php" => Ok(Self::Php),
+            "shell" => Ok(Self::Shell),
+            _ => Err(format!("unknown language: '{}'", s)),
+        }
+    }
+}
+
 pub trait Compressor {
     fn compress(&self, source: &str, language: Language) -> Result<String, RuleyError>;
     fn compression_ratio(&self) -> f32;
@@ -81,7 +119,101 @@ fn compression_ratio(&self) -> f32 {
     }
 }
 
-pub struct TreeSitterCompressor;
+/// Tree-sitter compressor: keeps declarations and signatures, drops bodies.
+///
+/// Each language uses its built-in extractor unless a custom query was
+/// registered for it with [`TreeSitterCompressor::with_query`].
+#[derive(Debug, Clone, Default)]
+pub struct TreeSitterCompressor {
+    queries: HashMap<Language, Arc<CompressionQuery>>,
+}
+
+impl TreeSitterCompressor {
+    /// Use `query` instead of the built-in extractor for its language.
+    pub fn with_query(mut self, query: CompressionQuery) -> Self {
+        self.queries.insert(query.language(), Arc::new(query));
+        self
+    }
+
+    /// Build a compressor with the query files from `[compression.queries]`.
+    ///
+    /// Relative paths are resolved against `root` (the repository).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key is not a known language, a file cannot be
+    /// read, or a query is invalid.
+    pub fn from_config(
+        queries: &HashMap<String, PathBuf>,
+        root: &Path,
+    ) -> Result<Self, RuleyError> {
+        let mut compressor = Self::default();
+        // Sorted so the first error reported is deterministic
+        let mut entries: Vec<_> = queries.iter().collect();
+        entries.sort();
+        for (name, path) in entries {
+            let language: Language = name
+                .parse()
+                .map_err(|e| RuleyError::Config(format!("[compression.queries] {}", e)))?;
+            let path = root.join(path);
+            let source = std::fs::read_to_string(&path).map_err(|e| {
+                RuleyError::Config(format!(
+                    "Failed to read {} query {}: {}",
+                    language,
+                    path.display(),
+                    e
+                ))
+            })?;
+            let query = CompressionQuery::new(language, &source).map_err(|e| match e {
+                RuleyError::Compression { language, message } => RuleyError::Compression {
+                    language,
+                    message: format!("invalid query {}: {}", path.display(), message),
+                },
+                other => other,
+            })?;
+            compressor = compressor.with_query(query);
+        }
+        Ok(compressor)
+    }
+}
+
+/// Returns the tree-sitter grammar for `language`, if its feature is enabled.
+///
+/// JavaScript shares the TypeScript grammar and JSX the TSX grammar, as in
+/// [`TreeSitterCompressor::compress`](Compressor::compress).
+pub(crate) fn grammar(language: Language) -> Option<tree_sitter::Language> {
+    match language {
+        #[cfg(feature = "compression-typescript")]
+        Language::TypeScript | Language::JavaScript => {
+            Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+        }
+        #[cfg(feature = "compression-typescript")]
+        Language::Tsx | Language::Jsx => Some(tree_sitter_typescript::LANGUAGE_TSX.into()),
+        #[cfg(feature = "compression-python")]
+        Language::Python => Some(tree_sitter_python::LANGUAGE.into()),
+        #[cfg(feature = "compression-rust")]
+        Language::Rust => Some(tree_sitter_rust::LANGUAGE.into()),
+        #[cfg(feature = "compression-go")]
+        Language::Go => Some(tree_sitter_go::LANGUAGE.into()),
+        #[cfg(feature = "compression-java")]
+        Language::Java => Some(tree_sitter_java::LANGUAGE.into()),
+        #[cfg(feature = "compression-csharp")]
+        Language::CSharp => Some(tree_sitter_c_sharp::LANGUAGE.into()),
+        #[cfg(feature = "compression-kotlin")]
+        Language::Kotlin => Some(tree_sitter_kotlin_ng::LANGUAGE.into()),
+        #[cfg(feature = "compression-c")]
+        Language::C => Some(tree_sitter_c::LANGUAGE.into()),
+        #[cfg(feature = "compression-cpp")]
+        Language::Cpp => Some(tree_sitter_cpp::LANGUAGE.into()),
+        #[cfg(feature = "compression-ruby")]
+        Language::Ruby => Some(tree_sitter_ruby::LANGUAGE.into()),
+        #[cfg(feature = "compression-php")]
+        Language::Php => Some(tree_sitter_php::LANGUAGE_PHP.into()),
+        #[cfg(feature = "compression-shell")]
+        Language::Shell => Some(tree_sitter_bash::LANGUAGE.into()),
+        _ => None,
+    }
+}
 
 #[cfg(feature = "compression-typescript")]
 impl TreeSitterCompressor {
@@ -295,227 +427,956 @@ pub fn compress_go(source: &str) -> Result<String, RuleyError> {
     }
 }
 
-impl Compressor for TreeSitterCompressor {
-    fn compress(&self, source: &str, language: Language) -> Result<String, RuleyError> {
-        match language {
-            Language::TypeScript => Self::compress_typescript(source, false),
-            Language::Tsx => Self::compress_typescript(source, true),
-            Language::JavaScript | Language::Jsx => {
-                // JavaScript uses the TypeScript grammar (a superset of JavaScript).
-                // JSX uses the TSX grammar to handle JSX syntax correctly.
-                let is_jsx = matches!(language, Language::Jsx);
-                Self::compress_typescript(source, is_jsx)
-            }
-            Language::Python => Self::compress_python(source),
-            Language::Rust => Self::compress_rust(source),
-            Language::Go => Self::compress_go(source),
-            _ => Err(RuleyError::Compression {
-                language: format!("{:?}", language),
-                message: "Tree-sitter compression not available for this language".to_string(),
-            }),
-        }
+#[cfg(feature = "compression-java")]
+impl TreeSitterCompressor {
+    /// Compress Java source code using tree-sitter
+    pub fn compress_java(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
+
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_java::LANGUAGE.into();
+
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "Java".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "Java".to_string(),
+                message: "Failed to parse Java source code".to_string(),
+            })?;
+
+        let mut result = String::new();
+        extract_java_nodes(source, tree.root_node(), &mut result);
+
+        Ok(result)
     }
+}
 
-    fn compression_ratio(&self) -> f32 {
-        0.7 // Target ~70% token reduction
+#[cfg(not(feature = "compression-java"))]
+impl TreeSitterCompressor {
+    /// Compression for Java is not available (feature disabled)
+    pub fn compress_java(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "Java".to_string(),
+            message: "Java compression feature is not enabled. Enable 'compression-java' feature."
+                .to_string(),
+        })
     }
 }
 
-// Helper functions for node extraction (feature-gated implementations)
+#[cfg(feature = "compression-csharp")]
+impl TreeSitterCompressor {
+    /// Compress C# source code using tree-sitter
+    pub fn compress_csharp(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
 
-#[cfg(feature = "compression-typescript")]
-fn extract_typescript_nodes(
-    source: &str,
-    node: tree_sitter::Node,
-    _cursor: &mut tree_sitter::TreeCursor,
-    result: &mut String,
-) {
-    let kind = node.kind();
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_c_sharp::LANGUAGE.into();
 
-    match kind {
-        "function_declaration" => {
-            // Extract signature: from start to just before body
-            // Try by field name first, then search for statement_block child
-            let mut body = node.child_by_field_name("body");
-            if body.is_none() {
-                let child_count: u32 = node.child_count().try_into().unwrap_or(0);
-                let mut i = 0u32;
-                while i < child_count {
-                    if let Some(child) = node.child(i) {
-                        let kind = child.kind();
-                        if kind == "statement_block" || kind == "block" {
-                            body = Some(child);
-                            break;
-                        }
-                    }
-                    i += 1;
-                }
-            }
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "C#".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
 
-            if let Some(body) = body {
-                result.push_str(&source[node.start_byte()..body.start_byte()]);
-                result.push_str("{ /* ... */ }\n");
-            } else {
-                result.push_str(&source[node.start_byte()..node.end_byte()]);
-                result.push('\n');
-            }
-        }
-        "arrow_function" => {
-            // Extract signature: from start to just before body
-            let mut body = node.child_by_field_name("body");
-            if body.is_none() {
-                let child_count: u32 = node.child_count().try_into().unwrap_or(0);
-                let mut i = 0u32;
-                while i < child_count {
-                    if let Some(child) = node.child(i) {
-                        let kind = child.kind();
-                        if kind == "statement_block" || kind == "block" {
-                            body = Some(child);
-                            break;
-                        }
-                    }
-                    i += 1;
-                }
-            }
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "C#".to_string(),
+                message: "Failed to parse C# source code".to_string(),
+            })?;
 
-            if let Some(body) = body {
-                result.push_str(&source[node.start_byte()..body.start_byte()]);
-                result.push_str("=> { /* ... */ }\n");
-            } else {
-                result.push_str(&source[node.start_byte()..node.end_byte()]);
-                result.push('\n');
-            }
-        }
-        "method_definition" => {
-            // Extract method signature only
-            // Try by field name first, then search for statement_block child
-            let mut body = node.child_by_field_name("body");
-            if body.is_none() {
-                let child_count: u32 = node.child_count().try_into().unwrap_or(0);
-                let mut i = 0u32;
-                while i < child_count {
-                    if let Some(child) = node.child(i) {
-                        let kind = child.kind();
-                        if kind == "statement_block" || kind == "block" {
-                            body = Some(child);
-                            break;
-                        }
-                    }
-                    i += 1;
-                }
-            }
+        let mut result = String::new();
+        extract_csharp_nodes(source, tree.root_node(), &mut result);
 
-            if let Some(body) = body {
-                result.push_str(&source[node.start_byte()..body.start_byte()]);
-                result.push_str("{ /* ... */ }\n");
-            } else {
-                result.push_str(&source[node.start_byte()..node.end_byte()]);
-                result.push('\n');
-            }
-        }
-        "type_alias_declaration" | "interface_declaration" | "enum_declaration" => {
-            result.push_str(&source[node.start_byte()..node.end_byte()]);
-            result.push('\n');
-        }
-        "field_definition" | "public_field_definition" => {
-            // Extract field declaration, keeping the type but optionally removing initializer
-            result.push_str(&source[node.start_byte()..node.end_byte()]);
-            result.push('\n');
-        }
-        "class_declaration" => {
-            // Extract class header and method signatures only. We avoid using the
-            // tree cursor here and instead recurse using the node API directly
-            // to keep behaviour stable across tree-sitter versions.
-            if let Some(body) = node.child_by_field_name("body") {
-                // Class header up to the opening brace
-                result.push_str(&source[node.start_byte()..body.start_byte()]);
-                result.push_str("{\n");
+        Ok(result)
+    }
+}
 
-                // Walk children of the class body to extract method and field
-                // signatures. We intentionally drop method bodies to achieve a
-                // strong compression ratio while preserving structure.
-                let child_count: u32 = body.child_count().try_into().unwrap_or(0);
-                let mut body_cursor = body.walk();
-                for i in 0..child_count {
-                    if let Some(child) = body.child(i) {
-                        match child.kind() {
-                            "method_definition"
-                            | "method_declaration"
-                            | "public_field_definition"
-                            | "field_definition" => {
-                                result.push_str("  ");
-                                extract_typescript_nodes(source, child, &mut body_cursor, result);
-                            }
-                            _ => {
-                                // Recurse into nested nodes to pick up any
-                                // method-like constructs we care about.
-                                extract_typescript_nodes(source, child, &mut body_cursor, result);
-                            }
-                        }
-                    }
-                }
+#[cfg(not(feature = "compression-csharp"))]
+impl TreeSitterCompressor {
+    /// Compression for C# is not available (feature disabled)
+    pub fn compress_csharp(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "C#".to_string(),
+            message: "C# compression feature is not enabled. Enable 'compression-csharp' feature."
+                .to_string(),
+        })
+    }
+}
 
-                result.push_str("}\n");
-            } else {
-                // Fallback: if there is no explicit body field, keep the
-                // declaration as-is.
-                result.push_str(&source[node.start_byte()..node.end_byte()]);
-                result.push('\n');
-            }
-        }
-        "import_statement" => {
-            result.push_str(&source[node.start_byte()..node.end_byte()]);
-            result.push('\n');
-        }
-        "export_statement" => {
-            // For exports containing class/function declarations, recurse to
-            // compress the inner declaration (drop method bodies, etc.).
-            // For simple exports (constants, re-exports), preserve as-is.
-            let child_count: u32 = node.child_count().try_into().unwrap_or(0);
-            let mut handled = false;
-            let mut node_cursor = node.walk();
-            for i in 0..child_count {
-                if let Some(child) = node.child(i) {
-                    match child.kind() {
-                        "class_declaration"
-                        | "function_declaration"
-                        | "generator_function_declaration"
-                        | "abstract_class_declaration" => {
-                            // Preserve "export " (or "export default ") prefix
-                            result.push_str(&source[node.start_byte()..child.start_byte()]);
-                            extract_typescript_nodes(source, child, &mut node_cursor, result);
-                            handled = true;
-                            break;
-                        }
-                        _ => {}
-                    }
-                }
-            }
-            if !handled {
-                result.push_str(&source[node.start_byte()..node.end_byte()]);
-                result.push('\n');
+#[cfg(feature = "compression-kotlin")]
+impl TreeSitterCompressor {
+    /// Compress Kotlin source code using tree-sitter
+    pub fn compress_kotlin(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
+
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_kotlin_ng::LANGUAGE.into();
+
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "Kotlin".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "Kotlin".to_string(),
+                message: "Failed to parse Kotlin source code".to_string(),
+            })?;
+
+        let mut result = String::new();
+        extract_kotlin_nodes(source, tree.root_node(), &mut result);
+
+        Ok(result)
+    }
+}
+
+#[cfg(not(feature = "compression-kotlin"))]
+impl TreeSitterCompressor {
+    /// Compression for Kotlin is not available (feature disabled)
+    pub fn compress_kotlin(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "Kotlin".to_string(),
+            message:
+                "Kotlin compression feature is not enabled. Enable 'compression-kotlin' feature."
+                    .to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "compression-c")]
+impl TreeSitterCompressor {
+    /// Compress C source code using tree-sitter
+    ///
+    /// Regions the grammar cannot parse (typically macro tricks) are kept
+    /// verbatim and reported with a warning.
+    pub fn compress_c(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
+
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_c::LANGUAGE.into();
+
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "C".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "C".to_string(),
+                message: "Failed to parse C source code".to_string(),
+            })?;
+
+        let root_node = tree.root_node();
+        warn_unparsed_regions("C", root_node);
+
+        let mut result = String::new();
+        extract_c_family_nodes(source, root_node, &mut result);
+
+        Ok(result)
+    }
+}
+
+#[cfg(not(feature = "compression-c"))]
+impl TreeSitterCompressor {
+    /// Compression for C is not available (feature disabled)
+    pub fn compress_c(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "C".to_string(),
+            message: "C compression feature is not enabled. Enable 'compression-c' feature."
+                .to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "compression-cpp")]
+impl TreeSitterCompressor {
+    /// Compress C++ source code using tree-sitter
+    ///
+    /// Regions the grammar cannot parse (typically macro tricks) are kept
+    /// verbatim and reported with a warning.
+    pub fn compress_cpp(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
+
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_cpp::LANGUAGE.into();
+
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "C++".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "C++".to_string(),
+                message: "Failed to parse C++ source code".to_string(),
+            })?;
+
+        let root_node = tree.root_node();
+        warn_unparsed_regions("C++", root_node);
+
+        let mut result = String::new();
+        extract_c_family_nodes(source, root_node, &mut result);
+
+        Ok(result)
+    }
+}
+
+#[cfg(not(feature = "compression-cpp"))]
+impl TreeSitterCompressor {
+    /// Compression for C++ is not available (feature disabled)
+    pub fn compress_cpp(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "C++".to_string(),
+            message: "C++ compression feature is not enabled. Enable 'compression-cpp' feature."
+                .to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "compression-ruby")]
+impl TreeSitterCompressor {
+    /// Compress Ruby source code using tree-sitter
+    pub fn compress_ruby(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
+
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_ruby::LANGUAGE.into();
+
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "Ruby".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "Ruby".to_string(),
+                message: "Failed to parse Ruby source code".to_string(),
+            })?;
+
+        let mut result = String::new();
+        extract_ruby_nodes(source, tree.root_node(), &mut result);
+
+        Ok(result)
+    }
+}
+
+#[cfg(not(feature = "compression-ruby"))]
+impl TreeSitterCompressor {
+    /// Compression for Ruby is not available (feature disabled)
+    pub fn compress_ruby(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "Ruby".to_string(),
+            message: "Ruby compression feature is not enabled. Enable 'compression-ruby' feature."
+                .to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "compression-php")]
+impl TreeSitterCompressor {
+    /// Compress PHP source code using tree-sitter
+    pub fn compress_php(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
+
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_php::LANGUAGE_PHP.into();
+
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "PHP".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "PHP".to_string(),
+                message: "Failed to parse PHP source code".to_string(),
+            })?;
+
+        let mut result = String::new();
+        extract_php_nodes(source, tree.root_node(), &mut result);
+
+        Ok(result)
+    }
+}
+
+#[cfg(not(feature = "compression-php"))]
+impl TreeSitterCompressor {
+    /// Compression for PHP is not available (feature disabled)
+    pub fn compress_php(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "PHP".to_string(),
+            message: "PHP compression feature is not enabled. Enable 'compression-php' feature."
+                .to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "compression-shell")]
+impl TreeSitterCompressor {
+    /// Compress shell script using tree-sitter
+    pub fn compress_shell(source: &str) -> Result<String, RuleyError> {
+        use tree_sitter::Parser;
+
+        let mut parser = Parser::new();
+        let language: tree_sitter::Language = tree_sitter_bash::LANGUAGE.into();
+
+        parser
+            .set_language(&language)
+            .map_err(|_| RuleyError::Compression {
+                language: "Shell".to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: "Shell".to_string(),
+                message: "Failed to parse shell script".to_string(),
+            })?;
+
+        let mut result = String::new();
+        extract_shell_nodes(source, tree.root_node(), &mut result);
+
+        Ok(result)
+    }
+}
+
+#[cfg(not(feature = "compression-shell"))]
+impl TreeSitterCompressor {
+    /// Compression for Shell is not available (feature disabled)
+    pub fn compress_shell(source: &str) -> Result<String, RuleyError> {
+        let _ = source;
+        Err(RuleyError::Compression {
+            language: "Shell".to_string(),
+            message:
+                "Shell compression feature is not enabled. Enable 'compression-shell' feature."
+                    .to_string(),
+        })
+    }
+}
+
+impl Compressor for TreeSitterCompressor {
+    fn compress(&self, source: &str, language: Language) -> Result<String, RuleyError> {
+        if let Some(query) = self.queries.get(&language) {
+            return query.apply(source);
+        }
+
+        match language {
+            Language::TypeScript => Self::compress_typescript(source, false),
+            Language::Tsx => Self::compress_typescript(source, true),
+            Language::JavaScript | Language::Jsx => {
+                // JavaScript uses the TypeScript grammar (a superset of JavaScript).
+                // JSX uses the TSX grammar to handle JSX syntax correctly.
+                let is_jsx = matches!(language, Language::Jsx);
+                Self::compress_typescript(source, is_jsx)
+            }
+            Language::Python => Self::compress_python(source),
+            Language::Rust => Self::compress_rust(source),
+            Language::Go => Self::compress_go(source),
+            Language::Java => Self::compress_java(source),
+            Language::CSharp => Self::compress_csharp(source),
+            Language::Kotlin => Self::compress_kotlin(source),
+            Language::C => Self::compress_c(source),
+            Language::Cpp => Self::compress_cpp(source),
+            Language::Ruby => Self::compress_ruby(source),
+            Language::Php => Self::compress_php(source),
+            Language::Shell => Self::compress_shell(source),
+            _ => Err(RuleyError::Compression {
+                language: format!("{:?}", language),
+                message: "Tree-sitter compression not available for this language".to_string(),
+            }),
+        }
+    }
+
+    fn compression_ratio(&self) -> f32 {
+        0.7 // Target ~70% token reduction
+    }
+}
+
+/// Function-like node kinds whose bodies [`TruncatingCompressor`] shortens.
+const FUNCTION_KINDS: &[&str] = &[
+    "arrow_function",
+    "constructor_declaration",
+    "func_literal",
+    "function_declaration",
+    "function_definition",
+    "function_expression",
+    "function_item",
+    "local_function_statement",
+    "method",
+    "method_declaration",
+    "method_definition",
+    "singleton_method",
+];
+
+/// Default number of body lines kept by [`TruncatingCompressor`].
+pub const DEFAULT_TRUNCATE_LINES: usize = 5;
+
+/// Truncating compressor: keeps the whole file but cuts each function body
+/// to its first `lines` lines, so idioms like error handling stay visible.
+///
+/// The cut is marked with `...` at the body's indentation. A closing line
+/// (`}`, `end`) is kept so the result still reads as balanced code.
+#[derive(Debug, Clone, Copy)]
+pub struct TruncatingCompressor {
+    lines: usize,
+}
+
+impl TruncatingCompressor {
+    /// Create a compressor that keeps `lines` lines of each function body.
+    pub fn new(lines: usize) -> Self {
+        Self { lines }
+    }
+}
+
+impl Default for TruncatingCompressor {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRUNCATE_LINES)
+    }
+}
+
+impl Compressor for TruncatingCompressor {
+    fn compress(&self, source: &str, language: Language) -> Result<String, RuleyError> {
+        let grammar = grammar(language).ok_or_else(|| RuleyError::Compression {
+            language: language.to_string(),
+            message: "Tree-sitter compression not available for this language".to_string(),
+        })?;
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&grammar)
+            .map_err(|_| RuleyError::Compression {
+                language: language.to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: language.to_string(),
+                message: format!("Failed to parse {} source code", language),
+            })?;
+
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let mut cuts = Vec::new();
+        collect_truncations(
+            source,
+            &line_starts,
+            tree.root_node(),
+            self.lines,
+            &mut cuts,
+        );
+
+        let mut result = String::with_capacity(source.len());
+        let mut pos = 0;
+        for (range, marker) in cuts {
+            result.push_str(&source[pos..range.start]);
+            result.push_str(&marker);
+            pos = range.end;
+        }
+        result.push_str(&source[pos..]);
+        Ok(result)
+    }
+
+    fn compression_ratio(&self) -> f32 {
+        0.5 // Depends heavily on function length
+    }
+}
+
+/// Record the byte ranges to cut from long function bodies under `node`, in
+/// source order, with the `...` line that replaces each.
+///
+/// Functions nested in a truncated body are cut along with it; functions in
+/// a body that is short enough to keep are visited on their own.
+fn collect_truncations(
+    source: &str,
+    line_starts: &[usize],
+    node: tree_sitter::Node,
+    lines: usize,
+    cuts: &mut Vec<(std::ops::Range<usize>, String)>,
+) {
+    if FUNCTION_KINDS.contains(&node.kind())
+        && let Some(body) = function_body(node)
+        && let Some(cut) = truncation(source, line_starts, node, body, lines)
+    {
+        cuts.push(cut);
+        return;
+    }
+
+    let mut walker = node.walk();
+    for child in node.children(&mut walker) {
+        collect_truncations(source, line_starts, child, lines, cuts);
+    }
+}
+
+/// The body of a function node: its `body` field, or (for grammars without
+/// one, like Ruby and Kotlin) the first child that looks like a body.
+fn function_body(node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    node.child_by_field_name("body").or_else(|| {
+        let mut walker = node.walk();
+        node.named_children(&mut walker).find(|child| {
+            let kind = child.kind();
+            kind.contains("body") || kind.ends_with("block")
+        })
+    })
+}
+
+/// The cut for one function, or `None` if its body fits in `lines` lines.
+fn truncation(
+    source: &str,
+    line_starts: &[usize],
+    function: tree_sitter::Node,
+    body: tree_sitter::Node,
+    lines: usize,
+) -> Option<(std::ops::Range<usize>, String)> {
+    let line = |row: usize| {
+        let start = line_starts[row];
+        let end = line_starts
+            .get(row + 1)
+            .map_or(source.len(), |next| next - 1);
+        &source[start..end]
+    };
+
+    // Bodies that open with a delimiter (`{`, `:`, `=`) start on the
+    // signature's last line; Python and Ruby bodies start on the next one.
+    let body_row = body.start_position().row;
+    let opens_inline = body_row == function.start_position().row
+        || source[body.start_byte()..].starts_with(['{', ':', '=', '(']);
+    let header_row = if opens_inline {
+        body_row
+    } else {
+        body_row.saturating_sub(1)
+    };
+
+    // Keep a closing line such as `}` or `end`, but not a Python statement
+    let end_row = function.end_position().row;
+    let closing = line(end_row).trim();
+    let has_footer =
+        end_row > header_row && (closing == "end" || closing.starts_with(['}', ')', ']']));
+    let last_body_row = if has_footer { end_row - 1 } else { end_row };
+
+    if last_body_row <= header_row + lines {
+        return None;
+    }
+
+    let first_cut_row = header_row + lines + 1;
+    let indent_source = line(header_row + 1);
+    let indent = &indent_source[..indent_source.len() - indent_source.trim_start().len()];
+    let start = line_starts[first_cut_row];
+    let (end, marker) = if has_footer {
+        (line_starts[end_row], format!("{}...\n", indent))
+    } else {
+        (function.end_byte(), format!("{}...", indent))
+    };
+    Some((start..end, marker))
+}
+
+// Helper functions for node extraction (feature-gated implementations)
+
+#[cfg(feature = "compression-typescript")]
+fn extract_typescript_nodes(
+    source: &str,
+    node: tree_sitter::Node,
+    _cursor: &mut tree_sitter::TreeCursor,
+    result: &mut String,
+) {
+    let kind = node.kind();
+
+    match kind {
+        "function_declaration" => {
+            // Extract signature: from start to just before body
+            // Try by field name first, then search for statement_block child
+            let mut body = node.child_by_field_name("body");
+            if body.is_none() {
+                let child_count: u32 = node.child_count().try_into().unwrap_or(0);
+                let mut i = 0u32;
+                while i < child_count {
+                    if let Some(child) = node.child(i) {
+                        let kind = child.kind();
+                        if kind == "statement_block" || kind == "block" {
+                            body = Some(child);
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+            }
+
+            if let Some(body) = body {
+                result.push_str(&source[node.start_byte()..body.start_byte()]);
+                result.push_str("{ /* ... */ }\n");
+            } else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "arrow_function" => {
+            // Extract signature: from start to just before body
+            let mut body = node.child_by_field_name("body");
+            if body.is_none() {
+                let child_count: u32 = node.child_count().try_into().unwrap_or(0);
+                let mut i = 0u32;
+                while i < child_count {
+                    if let Some(child) = node.child(i) {
+                        let kind = child.kind();
+                        if kind == "statement_block" || kind == "block" {
+                            body = Some(child);
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+            }
+
+            if let Some(body) = body {
+                result.push_str(&source[node.start_byte()..body.start_byte()]);
+                result.push_str("=> { /* ... */ }\n");
+            } else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "method_definition" => {
+            // Extract method signature only
+            // Try by field name first, then search for statement_block child
+            let mut body = node.child_by_field_name("body");
+            if body.is_none() {
+                let child_count: u32 = node.child_count().try_into().unwrap_or(0);
+                let mut i = 0u32;
+                while i < child_count {
+                    if let Some(child) = node.child(i) {
+                        let kind = child.kind();
+                        if kind == "statement_block" || kind == "block" {
+                            body = Some(child);
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+            }
+
+            if let Some(body) = body {
+                result.push_str(&source[node.start_byte()..body.start_byte()]);
+                result.push_str("{ /* ... */ }\n");
+            } else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "type_alias_declaration" | "interface_declaration" | "enum_declaration" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "field_definition" | "public_field_definition" => {
+            // Extract field declaration, keeping the type but optionally removing initializer
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "class_declaration" => {
+            // Extract class header and method signatures only. We avoid using the
+            // tree cursor here and instead recurse using the node API directly
+            // to keep behaviour stable across tree-sitter versions.
+            if let Some(body) = node.child_by_field_name("body") {
+                // Class header up to the opening brace
+                result.push_str(&source[node.start_byte()..body.start_byte()]);
+                result.push_str("{\n");
+
+                // Walk children of the class body to extract method and field
+                // signatures. We intentionally drop method bodies to achieve a
+                // strong compression ratio while preserving structure.
+                let child_count: u32 = body.child_count().try_into().unwrap_or(0);
+                let mut body_cursor = body.walk();
+                for i in 0..child_count {
+                    if let Some(child) = body.child(i) {
+                        match child.kind() {
+                            "method_definition"
+                            | "method_declaration"
+                            | "public_field_definition"
+                            | "field_definition" => {
+                                result.push_str("  ");
+                                extract_typescript_nodes(source, child, &mut body_cursor, result);
+                            }
+                            _ => {
+                                // Recurse into nested nodes to pick up any
+                                // method-like constructs we care about.
+                                extract_typescript_nodes(source, child, &mut body_cursor, result);
+                            }
+                        }
+                    }
+                }
+
+                result.push_str("}\n");
+            } else {
+                // Fallback: if there is no explicit body field, keep the
+                // declaration as-is.
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "import_statement" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "export_statement" => {
+            // For exports containing class/function declarations, recurse to
+            // compress the inner declaration (drop method bodies, etc.).
+            // For simple exports (constants, re-exports), preserve as-is.
+            let child_count: u32 = node.child_count().try_into().unwrap_or(0);
+            let mut handled = false;
+            let mut node_cursor = node.walk();
+            for i in 0..child_count {
+                if let Some(child) = node.child(i) {
+                    match child.kind() {
+                        "class_declaration"
+                        | "function_declaration"
+                        | "generator_function_declaration"
+                        | "abstract_class_declaration" => {
+                            // Preserve "export " (or "export default ") prefix
+                            result.push_str(&source[node.start_byte()..child.start_byte()]);
+                            extract_typescript_nodes(source, child, &mut node_cursor, result);
+                            handled = true;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if !handled {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "comment" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        _ => {
+            // Default: recurse into children using the node API.
+            let child_count = node.child_count();
+            let mut node_cursor = node.walk();
+            for i in 0..child_count {
+                if let Some(child) = node.child(i.try_into().unwrap()) {
+                    extract_typescript_nodes(source, child, &mut node_cursor, result);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "compression-typescript"))]
+#[allow(dead_code)]
+fn extract_typescript_nodes(
+    _source: &str,
+    _node: tree_sitter::Node,
+    _cursor: &mut tree_sitter::TreeCursor,
+    _result: &mut String,
+) {
+}
+
+#[cfg(feature = "compression-python")]
+fn extract_python_nodes(
+    source: &str,
+    node: tree_sitter::Node,
+    cursor: &mut tree_sitter::TreeCursor,
+    result: &mut String,
+) {
+    let kind = node.kind();
+
+    match kind {
+        "function_definition" => {
+            // Extract signature: from 'def' to just before body
+            if let Some(body) = node.child_by_field_name("body") {
+                result.push_str(&source[node.start_byte()..body.start_byte()]);
+                result.push_str("pass\n");
+            } else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "decorated_definition" => {
+            // For decorated functions, extract decorators and signature
+            for i in 0..node.child_count() {
+                if let Some(child) = node.child(i as u32) {
+                    if child.kind() == "decorator" {
+                        result.push_str(&source[child.start_byte()..child.end_byte()]);
+                        result.push('\n');
+                    } else if child.kind() == "function_definition" {
+                        extract_python_nodes(source, child, cursor, result);
+                    }
+                }
+            }
+        }
+        "class_definition" => {
+            // Extract class header and method signatures only
+            if let Some(name) = node.child_by_field_name("name") {
+                result.push_str("class ");
+                result.push_str(&source[name.start_byte()..name.end_byte()]);
+
+                // Add superclasses if present
+                if let Some(superclasses) = node.child_by_field_name("superclasses") {
+                    result.push_str(&source[superclasses.start_byte()..superclasses.end_byte()]);
+                }
+
+                result.push_str(":\n");
+
+                // Extract method signatures from class body
+                if let Some(body) = node.child_by_field_name("body") {
+                    let mut body_cursor = body.walk();
+                    if body_cursor.goto_first_child() {
+                        loop {
+                            let child = body_cursor.node();
+                            if child.kind() == "function_definition"
+                                || child.kind() == "decorated_definition"
+                            {
+                                result.push_str("    ");
+                                extract_python_nodes(source, child, &mut body_cursor, result);
+                            }
+                            if !body_cursor.goto_next_sibling() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        "import_statement" | "import_from_statement" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "comment" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        _ => {
+            if node.child_count() > 0 {
+                cursor.goto_first_child();
+                loop {
+                    extract_python_nodes(source, cursor.node(), cursor, result);
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
+                }
+                cursor.goto_parent();
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "compression-python"))]
+#[allow(dead_code)]
+fn extract_python_nodes(
+    _source: &str,
+    _node: tree_sitter::Node,
+    _cursor: &mut tree_sitter::TreeCursor,
+    _result: &mut String,
+) {
+}
+
+#[cfg(feature = "compression-rust")]
+fn extract_rust_nodes(
+    source: &str,
+    node: tree_sitter::Node,
+    cursor: &mut tree_sitter::TreeCursor,
+    result: &mut String,
+) {
+    let kind = node.kind();
+
+    match kind {
+        "function_item" => {
+            // Extract signature: from start to just before body
+            if let Some(body) = node.child_by_field_name("body") {
+                result.push_str(&source[node.start_byte()..body.start_byte()]);
+                result.push_str("{ /* ... */ }\n");
+            } else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "struct_item" | "enum_item" | "trait_item" => {
+            // Keep full definitions for types/enums/traits
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "impl_item" => {
+            // Extract impl header and method signatures only
+            // Find the opening brace of the impl block
+            let impl_header_end = source[node.start_byte()..node.end_byte()]
+                .find('{')
+                .map(|pos| node.start_byte() + pos)
+                .unwrap_or(node.end_byte());
+
+            result.push_str(&source[node.start_byte()..impl_header_end]);
+            result.push_str("{\n");
+
+            // Extract method signatures
+            for i in 0..node.child_count() {
+                if let Some(child) = node.child(i as u32)
+                    && child.kind() == "function_item"
+                {
+                    result.push_str("    ");
+                    extract_rust_nodes(source, child, cursor, result);
+                }
             }
+
+            result.push_str("}\n");
         }
-        "comment" => {
+        "use_declaration" | "mod_item" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "line_comment" | "block_comment" => {
             result.push_str(&source[node.start_byte()..node.end_byte()]);
             result.push('\n');
         }
         _ => {
-            // Default: recurse into children using the node API.
-            let child_count = node.child_count();
-            let mut node_cursor = node.walk();
-            for i in 0..child_count {
-                if let Some(child) = node.child(i.try_into().unwrap()) {
-                    extract_typescript_nodes(source, child, &mut node_cursor, result);
+            if node.child_count() > 0 {
+                cursor.goto_first_child();
+                loop {
+                    extract_rust_nodes(source, cursor.node(), cursor, result);
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
                 }
+                cursor.goto_parent();
             }
         }
     }
 }
 
-#[cfg(not(feature = "compression-typescript"))]
+#[cfg(not(feature = "compression-rust"))]
 #[allow(dead_code)]
-fn extract_typescript_nodes(
+fn extract_rust_nodes(
     _source: &str,
     _node: tree_sitter::Node,
     _cursor: &mut tree_sitter::TreeCursor,
@@ -523,8 +1384,8 @@ fn extract_typescript_nodes(
 ) {
 }
 
-#[cfg(feature = "compression-python")]
-fn extract_python_nodes(
+#[cfg(feature = "compression-go")]
+fn extract_go_nodes(
     source: &str,
     node: tree_sitter::Node,
     cursor: &mut tree_sitter::TreeCursor,
@@ -532,213 +1393,768 @@ fn extract_python_nodes(
 ) {
     let kind = node.kind();
 
-    match kind {
-        "function_definition" => {
-            // Extract signature: from 'def' to just before body
-            if let Some(body) = node.child_by_field_name("body") {
-                result.push_str(&source[node.start_byte()..body.start_byte()]);
-                result.push_str("pass\n");
-            } else {
+    match kind {
+        "function_declaration" | "method_declaration" => {
+            // Extract signature: from start to just before body
+            if let Some(body) = node.child_by_field_name("body") {
+                result.push_str(&source[node.start_byte()..body.start_byte()]);
+                result.push_str("{ /* ... */ }\n");
+            } else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "type_declaration" => {
+            // Check if this is a struct/interface type with methods
+            let has_methods = source[node.start_byte()..node.end_byte()].contains("struct")
+                || source[node.start_byte()..node.end_byte()].contains("interface");
+
+            if has_methods {
+                // For struct types, extract the type declaration
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            } else {
+                // For simple type aliases, keep full declaration
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "import_declaration" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "comment" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        _ => {
+            if node.child_count() > 0 {
+                cursor.goto_first_child();
+                loop {
+                    extract_go_nodes(source, cursor.node(), cursor, result);
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
+                }
+                cursor.goto_parent();
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "compression-go"))]
+#[allow(dead_code)]
+fn extract_go_nodes(
+    _source: &str,
+    _node: tree_sitter::Node,
+    _cursor: &mut tree_sitter::TreeCursor,
+    _result: &mut String,
+) {
+}
+
+/// Push `node` with its body replaced by a placeholder, or in full if it has no body.
+#[cfg(any(
+    feature = "compression-java",
+    feature = "compression-csharp",
+    feature = "compression-kotlin",
+    feature = "compression-c",
+    feature = "compression-cpp",
+    feature = "compression-php"
+))]
+fn push_signature(
+    source: &str,
+    node: tree_sitter::Node,
+    body: Option<tree_sitter::Node>,
+    result: &mut String,
+) {
+    if let Some(body) = body {
+        result.push_str(source[node.start_byte()..body.start_byte()].trim_end());
+        result.push_str(" { /* ... */ }\n");
+    } else {
+        result.push_str(&source[node.start_byte()..node.end_byte()]);
+        result.push('\n');
+    }
+}
+
+/// Extract a member behind `indent`, leaving no stray indentation if the
+/// extractor skips it.
+#[cfg(any(
+    feature = "compression-java",
+    feature = "compression-csharp",
+    feature = "compression-kotlin",
+    feature = "compression-c",
+    feature = "compression-cpp",
+    feature = "compression-ruby",
+    feature = "compression-php"
+))]
+fn push_indented(result: &mut String, indent: &str, extract: impl FnOnce(&mut String)) {
+    let start = result.len();
+    result.push_str(indent);
+    extract(result);
+    if result.len() == start + indent.len() {
+        result.truncate(start);
+    }
+}
+
+#[cfg(feature = "compression-java")]
+fn extract_java_nodes(source: &str, node: tree_sitter::Node, result: &mut String) {
+    let kind = node.kind();
+
+    match kind {
+        "method_declaration" | "constructor_declaration" | "compact_constructor_declaration" => {
+            // Keep annotations, modifiers, parameters and throws clause; drop the body
+            push_signature(source, node, node.child_by_field_name("body"), result);
+        }
+        "class_declaration"
+        | "interface_declaration"
+        | "enum_declaration"
+        | "record_declaration"
+        | "annotation_type_declaration" => {
+            // Extract the type header (annotations, extends/implements) and its members
+            let Some(body) = node.child_by_field_name("body") else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+                return;
+            };
+
+            result.push_str(&source[node.start_byte()..body.start_byte()]);
+            result.push_str("{\n");
+
+            let mut cursor = body.walk();
+            for member in body.named_children(&mut cursor) {
+                if member.kind() == "enum_body_declarations" {
+                    // Members declared after the enum constants
+                    let mut decl_cursor = member.walk();
+                    for decl in member.named_children(&mut decl_cursor) {
+                        push_indented(result, "    ", |result| {
+                            extract_java_nodes(source, decl, result)
+                        });
+                    }
+                } else {
+                    push_indented(result, "    ", |result| {
+                        extract_java_nodes(source, member, result)
+                    });
+                }
+            }
+
+            result.push_str("}\n");
+        }
+        "field_declaration"
+        | "constant_declaration"
+        | "enum_constant"
+        | "annotation_type_element_declaration" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "package_declaration" | "import_declaration" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "line_comment" | "block_comment" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        // Initializer blocks are implementation detail
+        "static_initializer" | "block" => {}
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                extract_java_nodes(source, child, result);
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "compression-java"))]
+#[allow(dead_code)]
+fn extract_java_nodes(_source: &str, _node: tree_sitter::Node, _result: &mut String) {}
+
+#[cfg(feature = "compression-csharp")]
+fn extract_csharp_nodes(source: &str, node: tree_sitter::Node, result: &mut String) {
+    let kind = node.kind();
+
+    match kind {
+        "method_declaration"
+        | "constructor_declaration"
+        | "destructor_declaration"
+        | "operator_declaration"
+        | "conversion_operator_declaration" => {
+            // Keep attributes, modifiers, parameters and constraints; drop block
+            // and expression bodies
+            push_signature(source, node, node.child_by_field_name("body"), result);
+        }
+        "namespace_declaration"
+        | "class_declaration"
+        | "interface_declaration"
+        | "struct_declaration"
+        | "record_declaration" => {
+            // Extract the header (attributes, base list) and its members
+            let Some(body) = node.child_by_field_name("body") else {
+                // Positional records have no body
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+                return;
+            };
+
+            result.push_str(&source[node.start_byte()..body.start_byte()]);
+            result.push_str("{\n");
+
+            let mut cursor = body.walk();
+            for member in body.named_children(&mut cursor) {
+                push_indented(result, "    ", |result| {
+                    extract_csharp_nodes(source, member, result)
+                });
+            }
+
+            result.push_str("}\n");
+        }
+        "property_declaration" | "indexer_declaration" => {
+            // Keep the declaration and accessor names, not accessor bodies
+            let accessors = node.child_by_field_name("accessors");
+            let body = accessors.or_else(|| node.child_by_field_name("value"));
+            let Some(body) = body else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+                return;
+            };
+
+            result.push_str(source[node.start_byte()..body.start_byte()].trim_end());
+            let mut names = Vec::new();
+            if let Some(accessors) = accessors {
+                let mut cursor = accessors.walk();
+                for accessor in accessors.named_children(&mut cursor) {
+                    let end = accessor
+                        .child_by_field_name("body")
+                        .map_or(accessor.end_byte(), |b| b.start_byte());
+                    names.push(source[accessor.start_byte()..end].trim_end_matches([' ', ';']));
+                }
+            } else {
+                // Expression-bodied properties are read-only
+                names.push("get");
+            }
+            result.push_str(&format!(" {{ {}; }}\n", names.join("; ")));
+        }
+        "field_declaration"
+        | "event_field_declaration"
+        | "event_declaration"
+        | "enum_declaration"
+        | "delegate_declaration" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "using_directive" | "file_scoped_namespace_declaration" | "global_attribute" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "comment" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                extract_csharp_nodes(source, child, result);
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "compression-csharp"))]
+#[allow(dead_code)]
+fn extract_csharp_nodes(_source: &str, _node: tree_sitter::Node, _result: &mut String) {}
+
+/// Returns the first named child of `node` with one of the given kinds.
+#[cfg(feature = "compression-kotlin")]
+fn kotlin_child<'tree>(
+    node: tree_sitter::Node<'tree>,
+    kinds: &[&str],
+) -> Option<tree_sitter::Node<'tree>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .find(|child| kinds.contains(&child.kind()))
+}
+
+#[cfg(feature = "compression-kotlin")]
+fn extract_kotlin_nodes(source: &str, node: tree_sitter::Node, result: &mut String) {
+    let kind = node.kind();
+
+    match kind {
+        "function_declaration" => {
+            // Keep annotations, modifiers, parameters and return type; drop block
+            // and expression bodies
+            push_signature(source, node, kotlin_child(node, &["function_body"]), result);
+        }
+        "secondary_constructor" => {
+            push_signature(source, node, kotlin_child(node, &["block"]), result);
+        }
+        "class_declaration" | "object_declaration" | "companion_object" => {
+            // Extract the header (annotations, primary constructor, supertypes)
+            // and its members
+            let Some(body) = kotlin_child(node, &["class_body", "enum_class_body"]) else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+                return;
+            };
+
+            result.push_str(&source[node.start_byte()..body.start_byte()]);
+            result.push_str("{\n");
+
+            let mut cursor = body.walk();
+            for member in body.named_children(&mut cursor) {
+                push_indented(result, "    ", |result| {
+                    extract_kotlin_nodes(source, member, result)
+                });
+            }
+
+            result.push_str("}\n");
+        }
+        "property_declaration" => {
+            // Keep the declaration and initializer, not custom accessor bodies
+            let end = kotlin_child(node, &["getter", "setter"])
+                .map_or(node.end_byte(), |accessor| accessor.start_byte());
+            result.push_str(source[node.start_byte()..end].trim_end());
+            result.push('\n');
+        }
+        "enum_entry" | "type_alias" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "package_header" | "import" | "file_annotation" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "line_comment" | "block_comment" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        // `init` blocks are implementation detail
+        "anonymous_initializer" => {}
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                extract_kotlin_nodes(source, child, result);
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "compression-kotlin"))]
+#[allow(dead_code)]
+fn extract_kotlin_nodes(_source: &str, _node: tree_sitter::Node, _result: &mut String) {}
+
+/// Warn about regions of `root` that tree-sitter could not parse.
+#[cfg(any(feature = "compression-c", feature = "compression-cpp"))]
+fn warn_unparsed_regions(language: &str, root: tree_sitter::Node) {
+    if !root.has_error() {
+        return;
+    }
+
+    let mut lines = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.is_error() || node.is_missing() {
+            lines.push(node.start_position().row + 1);
+        } else if node.has_error() {
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+    }
+    lines.sort_unstable();
+
+    tracing::warn!(
+        "{} parser could not parse {} region(s) (first at line {}); keeping that text as written",
+        language,
+        lines.len(),
+        lines.first().copied().unwrap_or(1)
+    );
+}
+
+/// Push a declaration with large brace initializers (lookup tables and the
+/// like) replaced by a placeholder.
+#[cfg(any(feature = "compression-c", feature = "compression-cpp"))]
+fn push_declaration(source: &str, node: tree_sitter::Node, result: &mut String) {
+    let mut start = node.start_byte();
+    let mut cursor = node.walk();
+    for declarator in node.children_by_field_name("declarator", &mut cursor) {
+        if let Some(value) = declarator.child_by_field_name("value")
+            && value.kind() == "initializer_list"
+        {
+            result.push_str(&source[start..value.start_byte()]);
+            result.push_str("{ /* ... */ }");
+            start = value.end_byte();
+        }
+    }
+    result.push_str(&source[start..node.end_byte()]);
+    result.push('\n');
+}
+
+/// Node kinds `extract_c_family_nodes` extracts as a unit.
+#[cfg(any(feature = "compression-c", feature = "compression-cpp"))]
+const C_FAMILY_ITEMS: &[&str] = &[
+    "function_definition",
+    "declaration",
+    "type_definition",
+    "struct_specifier",
+    "union_specifier",
+    "class_specifier",
+    "enum_specifier",
+    "namespace_definition",
+    "linkage_specification",
+    "template_declaration",
+    "alias_declaration",
+    "using_declaration",
+    "preproc_include",
+    "preproc_def",
+    "preproc_function_def",
+    "preproc_call",
+    "preproc_if",
+    "preproc_ifdef",
+    "comment",
+];
+
+/// Shared extractor for C and C++ (the C++ grammar extends the C node kinds).
+#[cfg(any(feature = "compression-c", feature = "compression-cpp"))]
+fn extract_c_family_nodes(source: &str, node: tree_sitter::Node, result: &mut String) {
+    let kind = node.kind();
+
+    match kind {
+        "function_definition" => {
+            // Keep return type, declarator, qualifiers and initializer lists; drop the body
+            push_signature(source, node, node.child_by_field_name("body"), result);
+        }
+        "struct_specifier" | "union_specifier" | "class_specifier" => {
+            // Extract the header (name, base classes) and member declarations
+            let Some(body) = node.child_by_field_name("body") else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push_str(";\n");
+                return;
+            };
+
+            result.push_str(&source[node.start_byte()..body.start_byte()]);
+            result.push_str("{\n");
+
+            let mut cursor = body.walk();
+            for member in body.named_children(&mut cursor) {
+                push_indented(result, "    ", |result| {
+                    extract_c_family_nodes(source, member, result)
+                });
+            }
+
+            result.push_str("};\n");
+        }
+        "namespace_definition" | "linkage_specification" => {
+            let Some(body) = node.child_by_field_name("body") else {
                 result.push_str(&source[node.start_byte()..node.end_byte()]);
                 result.push('\n');
+                return;
+            };
+
+            result.push_str(&source[node.start_byte()..body.start_byte()]);
+            if body.kind() == "declaration_list" {
+                result.push_str("{\n");
+                let mut cursor = body.walk();
+                for child in body.named_children(&mut cursor) {
+                    extract_c_family_nodes(source, child, result);
+                }
+                result.push_str("}\n");
+            } else {
+                // `extern "C" int f(void);`
+                extract_c_family_nodes(source, body, result);
             }
         }
-        "decorated_definition" => {
-            // For decorated functions, extract decorators and signature
-            for i in 0..node.child_count() {
-                if let Some(child) = node.child(i as u32) {
-                    if child.kind() == "decorator" {
-                        result.push_str(&source[child.start_byte()..child.end_byte()]);
-                        result.push('\n');
-                    } else if child.kind() == "function_definition" {
-                        extract_python_nodes(source, child, cursor, result);
-                    }
+        "template_declaration" => {
+            // Keep the template parameter list, then extract the templated item
+            let parameters = node.child_by_field_name("parameters");
+            if let Some(parameters) = parameters {
+                result.push_str(&source[node.start_byte()..parameters.end_byte()]);
+                result.push('\n');
+            }
+
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if Some(child) != parameters {
+                    extract_c_family_nodes(source, child, result);
                 }
             }
         }
-        "class_definition" => {
-            // Extract class header and method signatures only
-            if let Some(name) = node.child_by_field_name("name") {
-                result.push_str("class ");
-                result.push_str(&source[name.start_byte()..name.end_byte()]);
+        "preproc_if" | "preproc_ifdef" | "preproc_elif" | "preproc_elifdef" | "preproc_else" => {
+            // Keep conditional compilation structure around the extracted items
+            let condition = node
+                .child_by_field_name("condition")
+                .or_else(|| node.child_by_field_name("name"));
+            let header_end = condition
+                .or_else(|| node.child(0))
+                .map_or(node.end_byte(), |n| n.end_byte());
+            result.push_str(&source[node.start_byte()..header_end]);
+            result.push('\n');
 
-                // Add superclasses if present
-                if let Some(superclasses) = node.child_by_field_name("superclasses") {
-                    result.push_str(&source[superclasses.start_byte()..superclasses.end_byte()]);
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if Some(child) != condition {
+                    extract_c_family_nodes(source, child, result);
                 }
+            }
 
-                result.push_str(":\n");
-
-                // Extract method signatures from class body
-                if let Some(body) = node.child_by_field_name("body") {
-                    let mut body_cursor = body.walk();
-                    if body_cursor.goto_first_child() {
-                        loop {
-                            let child = body_cursor.node();
-                            if child.kind() == "function_definition"
-                                || child.kind() == "decorated_definition"
-                            {
-                                result.push_str("    ");
-                                extract_python_nodes(source, child, &mut body_cursor, result);
-                            }
-                            if !body_cursor.goto_next_sibling() {
-                                break;
-                            }
-                        }
-                    }
-                }
+            if matches!(kind, "preproc_if" | "preproc_ifdef") {
+                result.push_str("#endif\n");
             }
         }
-        "import_statement" | "import_from_statement" => {
+        "preproc_include" | "preproc_def" | "preproc_function_def" | "preproc_call" => {
+            // Directives include their trailing newline
+            result.push_str(source[node.start_byte()..node.end_byte()].trim_end());
+            result.push('\n');
+        }
+        "declaration" | "field_declaration" => {
+            push_declaration(source, node, result);
+        }
+        "type_definition"
+        | "enum_specifier"
+        | "alias_declaration"
+        | "using_declaration"
+        | "namespace_alias_definition"
+        | "static_assert_declaration"
+        | "concept_definition" => {
             result.push_str(&source[node.start_byte()..node.end_byte()]);
+            if kind == "enum_specifier" {
+                result.push(';');
+            }
             result.push('\n');
         }
+        "access_specifier" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push_str(":\n");
+        }
         "comment" => {
             result.push_str(&source[node.start_byte()..node.end_byte()]);
             result.push('\n');
         }
-        _ => {
-            if node.child_count() > 0 {
-                cursor.goto_first_child();
-                loop {
-                    extract_python_nodes(source, cursor.node(), cursor, result);
-                    if !cursor.goto_next_sibling() {
-                        break;
+        "ERROR" => {
+            // Unparseable region (see `warn_unparsed_regions`): extract the items
+            // tree-sitter recovered inside it and keep the tokens around them as written
+            let mut raw: Option<(usize, usize)> = None;
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if C_FAMILY_ITEMS.contains(&child.kind()) {
+                    if let Some((start, end)) = raw.take() {
+                        result.push_str(source[start..end].trim());
+                        result.push('\n');
                     }
+                    extract_c_family_nodes(source, child, result);
+                } else {
+                    let start = raw.map_or(child.start_byte(), |(start, _)| start);
+                    raw = Some((start, child.end_byte()));
                 }
-                cursor.goto_parent();
+            }
+            if let Some((start, end)) = raw {
+                result.push_str(source[start..end].trim());
+                result.push('\n');
+            }
+        }
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                extract_c_family_nodes(source, child, result);
             }
         }
     }
 }
 
-#[cfg(not(feature = "compression-python"))]
+#[cfg(not(any(feature = "compression-c", feature = "compression-cpp")))]
 #[allow(dead_code)]
-fn extract_python_nodes(
-    _source: &str,
-    _node: tree_sitter::Node,
-    _cursor: &mut tree_sitter::TreeCursor,
-    _result: &mut String,
-) {
-}
+fn extract_c_family_nodes(_source: &str, _node: tree_sitter::Node, _result: &mut String) {}
 
-#[cfg(feature = "compression-rust")]
-fn extract_rust_nodes(
-    source: &str,
-    node: tree_sitter::Node,
-    cursor: &mut tree_sitter::TreeCursor,
-    result: &mut String,
-) {
+#[cfg(feature = "compression-ruby")]
+fn extract_ruby_nodes(source: &str, node: tree_sitter::Node, result: &mut String) {
     let kind = node.kind();
 
     match kind {
-        "function_item" => {
-            // Extract signature: from start to just before body
+        "program" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                extract_ruby_nodes(source, child, result);
+            }
+        }
+        "method" | "singleton_method" => {
+            // Keep `def name(params)`; drop the body, including endless `= expr` bodies
             if let Some(body) = node.child_by_field_name("body") {
-                result.push_str(&source[node.start_byte()..body.start_byte()]);
-                result.push_str("{ /* ... */ }\n");
+                let signature = source[node.start_byte()..body.start_byte()].trim_end();
+                result.push_str(signature.trim_end_matches('=').trim_end());
+                result.push_str("; end\n");
             } else {
                 result.push_str(&source[node.start_byte()..node.end_byte()]);
                 result.push('\n');
             }
         }
-        "struct_item" | "enum_item" | "trait_item" => {
-            // Keep full definitions for types/enums/traits
+        "module" | "class" | "singleton_class" => {
+            // Extract the header (name, superclass) and the definitions in the body
+            let Some(body) = node.child_by_field_name("body") else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+                return;
+            };
+
+            result.push_str(source[node.start_byte()..body.start_byte()].trim_end());
+            result.push('\n');
+
+            let mut cursor = body.walk();
+            for member in body.named_children(&mut cursor) {
+                if member.kind() == "identifier" {
+                    // Visibility keywords such as `private`
+                    result.push_str("  ");
+                    result.push_str(&source[member.start_byte()..member.end_byte()]);
+                    result.push('\n');
+                } else {
+                    push_indented(result, "  ", |result| {
+                        extract_ruby_nodes(source, member, result)
+                    });
+                }
+            }
+
+            result.push_str("end\n");
+        }
+        "call" => {
+            // `require`, `include`, `attr_reader`, associations and other
+            // class-level DSL calls; blocks passed to them are dropped
+            let end = node
+                .child_by_field_name("block")
+                .map_or(node.end_byte(), |block| block.start_byte());
+            result.push_str(source[node.start_byte()..end].trim_end());
+            result.push('\n');
+        }
+        "assignment" | "comment" => {
             result.push_str(&source[node.start_byte()..node.end_byte()]);
             result.push('\n');
         }
-        "impl_item" => {
-            // Extract impl header and method signatures only
-            // Find the opening brace of the impl block
-            let impl_header_end = source[node.start_byte()..node.end_byte()]
-                .find('{')
-                .map(|pos| node.start_byte() + pos)
-                .unwrap_or(node.end_byte());
+        // Top-level control flow and expressions are implementation detail
+        _ => {}
+    }
+}
 
-            result.push_str(&source[node.start_byte()..impl_header_end]);
+#[cfg(not(feature = "compression-ruby"))]
+#[allow(dead_code)]
+fn extract_ruby_nodes(_source: &str, _node: tree_sitter::Node, _result: &mut String) {}
+
+#[cfg(feature = "compression-php")]
+fn extract_php_nodes(source: &str, node: tree_sitter::Node, result: &mut String) {
+    let kind = node.kind();
+
+    match kind {
+        "function_definition" | "method_declaration" => {
+            // Keep attributes, modifiers, parameters and return type; drop the body
+            push_signature(source, node, node.child_by_field_name("body"), result);
+        }
+        "class_declaration"
+        | "interface_declaration"
+        | "trait_declaration"
+        | "enum_declaration" => {
+            // Extract the header (attributes, extends/implements) and its members
+            let Some(body) = node.child_by_field_name("body") else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+                return;
+            };
+
+            result.push_str(&source[node.start_byte()..body.start_byte()]);
             result.push_str("{\n");
 
-            // Extract method signatures
-            for i in 0..node.child_count() {
-                if let Some(child) = node.child(i as u32)
-                    && child.kind() == "function_item"
-                {
-                    result.push_str("    ");
-                    extract_rust_nodes(source, child, cursor, result);
-                }
+            let mut cursor = body.walk();
+            for member in body.named_children(&mut cursor) {
+                push_indented(result, "    ", |result| {
+                    extract_php_nodes(source, member, result)
+                });
             }
 
             result.push_str("}\n");
         }
-        "use_declaration" | "mod_item" => {
+        "namespace_definition" => {
+            // `namespace Foo;` or the bracketed `namespace Foo { ... }` form
+            let Some(body) = node.child_by_field_name("body") else {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+                return;
+            };
+
+            result.push_str(&source[node.start_byte()..body.start_byte()]);
+            result.push_str("{\n");
+            let mut cursor = body.walk();
+            for child in body.named_children(&mut cursor) {
+                extract_php_nodes(source, child, result);
+            }
+            result.push_str("}\n");
+        }
+        "property_declaration" | "const_declaration" | "use_declaration" | "enum_case" => {
             result.push_str(&source[node.start_byte()..node.end_byte()]);
             result.push('\n');
         }
-        "line_comment" | "block_comment" => {
+        "namespace_use_declaration" | "declare_statement" => {
             result.push_str(&source[node.start_byte()..node.end_byte()]);
             result.push('\n');
         }
-        _ => {
-            if node.child_count() > 0 {
-                cursor.goto_first_child();
-                loop {
-                    extract_rust_nodes(source, cursor.node(), cursor, result);
-                    if !cursor.goto_next_sibling() {
-                        break;
-                    }
-                }
-                cursor.goto_parent();
+        "expression_statement" => {
+            // Keep top-level `require`/`include`; other statements are implementation detail
+            let is_include = node.named_child(0).is_some_and(|expr| {
+                matches!(
+                    expr.kind(),
+                    "require_expression"
+                        | "require_once_expression"
+                        | "include_expression"
+                        | "include_once_expression"
+                )
+            });
+            if is_include {
+                result.push_str(&source[node.start_byte()..node.end_byte()]);
+                result.push('\n');
+            }
+        }
+        "comment" => {
+            result.push_str(&source[node.start_byte()..node.end_byte()]);
+            result.push('\n');
+        }
+        "program" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                extract_php_nodes(source, child, result);
             }
         }
+        // Inline HTML and top-level statements are implementation detail
+        _ => {}
     }
 }
 
-#[cfg(not(feature = "compression-rust"))]
+#[cfg(not(feature = "compression-php"))]
 #[allow(dead_code)]
-fn extract_rust_nodes(
-    _source: &str,
-    _node: tree_sitter::Node,
-    _cursor: &mut tree_sitter::TreeCursor,
-    _result: &mut String,
-) {
-}
+fn extract_php_nodes(_source: &str, _node: tree_sitter::Node, _result: &mut String) {}
 
-#[cfg(feature = "compression-go")]
-fn extract_go_nodes(
-    source: &str,
-    node: tree_sitter::Node,
-    cursor: &mut tree_sitter::TreeCursor,
-    result: &mut String,
-) {
+#[cfg(feature = "compression-shell")]
+fn extract_shell_nodes(source: &str, node: tree_sitter::Node, result: &mut String) {
     let kind = node.kind();
 
     match kind {
-        "function_declaration" | "method_declaration" => {
-            // Extract signature: from start to just before body
-            if let Some(body) = node.child_by_field_name("body") {
-                result.push_str(&source[node.start_byte()..body.start_byte()]);
-                result.push_str("{ /* ... */ }\n");
-            } else {
-                result.push_str(&source[node.start_byte()..node.end_byte()]);
-                result.push('\n');
+        "program" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                extract_shell_nodes(source, child, result);
             }
         }
-        "type_declaration" => {
-            // Check if this is a struct/interface type with methods
-            let has_methods = source[node.start_byte()..node.end_byte()].contains("struct")
-                || source[node.start_byte()..node.end_byte()].contains("interface");
-
-            if has_methods {
-                // For struct types, extract the type declaration
-                result.push_str(&source[node.start_byte()..node.end_byte()]);
-                result.push('\n');
+        "function_definition" => {
+            // Keep `name()` / `function name`; drop the body
+            if let Some(body) = node.child_by_field_name("body") {
+                result.push_str(source[node.start_byte()..body.start_byte()].trim_end());
+                result.push_str(" { # ...\n}\n");
             } else {
-                // For simple type aliases, keep full declaration
                 result.push_str(&source[node.start_byte()..node.end_byte()]);
                 result.push('\n');
             }
         }
-        "import_declaration" => {
+        "command"
+        | "declaration_command"
+        | "variable_assignment"
+        | "unset_command"
+        | "pipeline"
+        | "list"
+        | "negated_command"
+        | "redirected_statement" => {
+            // Top-level commands: `set` options, sourced files, exports and entry points
             result.push_str(&source[node.start_byte()..node.end_byte()]);
             result.push('\n');
         }
@@ -746,109 +2162,136 @@ fn extract_go_nodes(
             result.push_str(&source[node.start_byte()..node.end_byte()]);
             result.push('\n');
         }
-        _ => {
-            if node.child_count() > 0 {
-                cursor.goto_first_child();
-                loop {
-                    extract_go_nodes(source, cursor.node(), cursor, result);
-                    if !cursor.goto_next_sibling() {
-                        break;
-                    }
-                }
-                cursor.goto_parent();
-            }
-        }
+        // Top-level control flow (`if`, loops, `case`) is implementation detail
+        _ => {}
     }
 }
 
-#[cfg(not(feature = "compression-go"))]
+#[cfg(not(feature = "compression-shell"))]
 #[allow(dead_code)]
-fn extract_go_nodes(
-    _source: &str,
-    _node: tree_sitter::Node,
-    _cursor: &mut tree_sitter::TreeCursor,
-    _result: &mut String,
-) {
+fn extract_shell_nodes(_source: &str, _node: tree_sitter::Node, _result: &mut String) {}
+
+/// Parse the `[compression.levels]` table into per-language levels.
+fn language_levels(
+    levels: &HashMap<String, CompressionLevel>,
+) -> Result<HashMap<Language, CompressionLevel>, RuleyError> {
+    levels
+        .iter()
+        .map(|(name, level)| {
+            name.parse()
+                .map(|language| (language, *level))
+                .map_err(|e| RuleyError::Config(format!("[compression.levels] {}", e)))
+        })
+        .collect()
+}
+
+/// Compress one file at `level`, stepping down a level whenever the
+/// requested one fails.
+fn compress_file(
+    source: &str,
+    language: Option<Language>,
+    level: CompressionLevel,
+    tree_sitter: &TreeSitterCompressor,
+    truncating: &TruncatingCompressor,
+) -> (String, super::CompressionMethod) {
+    let lang = language.unwrap_or(Language::Unknown);
+    let mut level = level;
+    loop {
+        let (compressor, method, next): (&dyn Compressor, _, _) = match level {
+            CompressionLevel::None => return (source.to_string(), super::CompressionMethod::None),
+            CompressionLevel::Whitespace => (
+                &WhitespaceCompressor,
+                super::CompressionMethod::Whitespace,
+                CompressionLevel::None,
+            ),
+            CompressionLevel::Truncate => (
+                truncating,
+                super::CompressionMethod::Truncated,
+                CompressionLevel::Whitespace,
+            ),
+            CompressionLevel::Signatures => (
+                tree_sitter,
+                super::CompressionMethod::TreeSitter,
+                CompressionLevel::Whitespace,
+            ),
+        };
+        // Files without a detected language have no grammar to try
+        if language.is_none() && level > CompressionLevel::Whitespace {
+            level = next;
+            continue;
+        }
+        match compressor.compress(source, lang) {
+            Ok(compressed) => return (compressed, method),
+            Err(e) => {
+                tracing::warn!(
+                    "{} compression failed, falling back to {}: {}",
+                    level,
+                    next,
+                    e
+                );
+                level = next;
+            }
+        }
+    }
 }
 
 /// Compress a codebase by processing file entries and applying compression
+///
+/// Each file is compressed at its language's level from
+/// `[compression.levels]`, or `config.compress_level` otherwise. A file that
+/// cannot be compressed at its level falls back to the next less aggressive
+/// one; [`CompressedFile::compression_method`](super::CompressedFile) records
+/// what was actually applied.
 pub async fn compress_codebase(
     entries: Vec<super::walker::FileEntry>,
     config: &MergedConfig,
 ) -> Result<super::CompressedCodebase, RuleyError> {
-    // If compression is disabled, return uncompressed codebase
-    if !config.compress {
-        let mut files = Vec::new();
-        for entry in entries {
-            match tokio::fs::read_to_string(&entry.path).await {
-                Ok(original_content) => {
-                    let original_size = original_content.len();
-                    files.push(super::CompressedFile {
-                        path: entry.path,
-                        original_content: original_content.clone(),
-                        compressed_content: original_content,
-                        compression_method: super::CompressionMethod::None,
-                        original_size,
-                        compressed_size: original_size,
-                        language: entry.language,
-                    });
-                }
-                Err(e) => {
-                    return Err(RuleyError::FileSystem(std::io::Error::new(
-                        e.kind(),
-                        format!("Failed to read file {}: {}", entry.path.display(), e),
-                    )));
-                }
-            }
-        }
-        return Ok(super::CompressedCodebase::new(files));
-    }
+    compress_codebase_with_skipped(entries, config)
+        .await
+        .map(|(codebase, _)| codebase)
+}
 
-    let tree_sitter_compressor = TreeSitterCompressor;
-    let whitespace_compressor = WhitespaceCompressor;
+/// Compress a codebase like [`compress_codebase`], also returning the files
+/// left out because their contents turned out not to be valid UTF-8.
+///
+/// The scanner only samples the start of each file for binary content, so
+/// these are reported with [`SkipReason::Binary`](super::SkipReason::Binary)
+/// alongside the scanner's own skips.
+pub async fn compress_codebase_with_skipped(
+    entries: Vec<super::walker::FileEntry>,
+    config: &MergedConfig,
+) -> Result<(super::CompressedCodebase, Vec<super::SkippedFile>), RuleyError> {
+    let levels = language_levels(&config.compression.levels)?;
+    let uses_tree_sitter = config.compress_level >= CompressionLevel::Truncate
+        || levels
+            .values()
+            .any(|level| *level >= CompressionLevel::Truncate);
+    let tree_sitter_compressor = if uses_tree_sitter {
+        TreeSitterCompressor::from_config(&config.compression.queries, &config.path)?
+    } else {
+        TreeSitterCompressor::default()
+    };
+    let truncating_compressor = TruncatingCompressor::new(config.compression.truncate_lines);
     let mut compressed_files = Vec::new();
+    let mut skipped = Vec::new();
 
     for entry in entries {
         match tokio::fs::read_to_string(&entry.path).await {
             Ok(original_content) => {
                 let original_size = original_content.len();
-                let (compressed_content, compression_method) = match entry.language {
-                    Some(lang) => match tree_sitter_compressor.compress(&original_content, lang) {
-                        Ok(compressed) => (compressed, super::CompressionMethod::TreeSitter),
-                        Err(e) => {
-                            tracing::warn!(
-                                "Tree-sitter parse failed for {}, falling back to whitespace compression: {}",
-                                entry.path.display(),
-                                e
-                            );
-                            match whitespace_compressor.compress(&original_content, lang) {
-                                Ok(compressed) => {
-                                    (compressed, super::CompressionMethod::Whitespace)
-                                }
-                                Err(fallback_err) => {
-                                    tracing::warn!(
-                                        "Whitespace compression also failed for {}: {}",
-                                        entry.path.display(),
-                                        fallback_err
-                                    );
-                                    (original_content.clone(), super::CompressionMethod::None)
-                                }
-                            }
-                        }
-                    },
-                    None => {
-                        match whitespace_compressor.compress(&original_content, Language::Unknown) {
-                            Ok(compressed) => (compressed, super::CompressionMethod::Whitespace),
-                            Err(e) => {
-                                tracing::warn!(
-                                    "Whitespace compression failed for {}: {e}",
-                                    entry.path.display()
-                                );
-                                (original_content.clone(), super::CompressionMethod::None)
-                            }
-                        }
-                    }
-                };
+                let level = entry
+                    .language
+                    .and_then(|lang| levels.get(&lang).copied())
+                    .unwrap_or(config.compress_level);
+                // Name the file in warnings raised while compressing it
+                let _span = tracing::warn_span!("compress", file = %entry.path.display()).entered();
+                let (compressed_content, compression_method) = compress_file(
+                    &original_content,
+                    entry.language,
+                    level,
+                    &tree_sitter_compressor,
+                    &truncating_compressor,
+                );
 
                 let compressed_size = compressed_content.len();
 
@@ -862,6 +2305,14 @@ pub async fn compress_codebase(
                     language: entry.language,
                 });
             }
+            // The scanner only samples the start of each file for binary content
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                tracing::warn!("Skipping {}: not valid UTF-8", entry.path.display());
+                skipped.push(super::SkippedFile {
+                    path: entry.path,
+                    reason: super::SkipReason::Binary,
+                });
+            }
             Err(e) => {
                 return Err(RuleyError::FileSystem(std::io::Error::new(
                     e.kind(),
@@ -893,6 +2344,11 @@ pub async fn compress_codebase(
         .iter()
         .filter(|f| f.compression_method == super::CompressionMethod::TreeSitter)
         .count();
+    let truncated_count = codebase
+        .files
+        .iter()
+        .filter(|f| f.compression_method == super::CompressionMethod::Truncated)
+        .count();
     let whitespace_count = codebase
         .files
         .iter()
@@ -905,8 +2361,9 @@ pub async fn compress_codebase(
         .count();
 
     tracing::debug!(
-        "Compression breakdown: {} tree-sitter, {} whitespace, {} none",
+        "Compression breakdown: {} tree-sitter, {} truncated, {} whitespace, {} none",
         tree_sitter_count,
+        truncated_count,
         whitespace_count,
         none_count
     );
@@ -916,5 +2373,5 @@ pub async fn compress_codebase(
         tracing::debug!("Language distribution: {:?}", codebase.metadata.languages);
     }
 
-    Ok(codebase)
+    Ok((codebase, skipped))
 }
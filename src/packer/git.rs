@@ -2,17 +2,151 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::utils::error::RuleyError;
-use git2::Repository;
+use git2::build::CheckoutBuilder;
+use git2::{Cred, CredentialType, Direction, FetchOptions, RemoteCallbacks, Repository};
 use std::path::{Path, PathBuf};
 
+/// Options for cloning a remote repository.
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {
+    /// Branch, tag or commit SHA to check out (defaults to the remote's HEAD)
+    pub git_ref: Option<String>,
+    /// History depth to fetch; `0` fetches full history
+    pub depth: u32,
+}
+
+/// Returns true if `path` names a remote repository rather than a local path.
+///
+/// Recognizes `http(s)://`, `ssh://`, `git://` and `file://` URLs as well as
+/// scp-style `user@host:path` addresses.
+pub fn is_remote_url(path: &str) -> bool {
+    const SCHEMES: [&str; 5] = ["http://", "https://", "ssh://", "git://", "file://"];
+    if SCHEMES.iter().any(|scheme| path.starts_with(scheme)) {
+        return true;
+    }
+
+    // scp-like syntax: the user@host part comes before the first ':' and has no '/'
+    path.split_once(':')
+        .is_some_and(|(host, _)| host.contains('@') && !host.contains('/'))
+}
+
+/// Derive a directory name for a clone from its URL (`org/repo.git` -> `repo`).
+pub fn repo_name_from_url(url: &str) -> String {
+    url.trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .map(|name| name.trim_end_matches(".git"))
+        .filter(|name| !name.is_empty())
+        .unwrap_or("repository")
+        .to_string()
+}
+
+/// Clone `url` into `dest` and check out the requested ref.
+///
+/// Only the requested ref is fetched, limited to `options.depth` commits.
+/// Branches and tags are matched against the refs the remote advertises;
+/// anything else is treated as a commit SHA, which requires fetching full
+/// history so it can be resolved.
+///
+/// # Errors
+///
+/// Returns an error if the remote cannot be reached, the ref does not exist,
+/// or the checkout fails.
 pub async fn clone_repo(
     url: &str,
     dest: &Path,
-) -> Result<Repository, crate::utils::error::RuleyError> {
-    let repo = Repository::clone(url, dest)?;
+    options: &CloneOptions,
+) -> Result<Repository, RuleyError> {
+    let repo = Repository::init(dest)?;
+    let mut remote = repo.remote("origin", url)?;
+
+    remote.connect_auth(Direction::Fetch, Some(remote_callbacks()), None)?;
+    let advertised: Vec<String> = remote
+        .list()?
+        .iter()
+        .map(|head| head.name().to_string())
+        .collect();
+    let target = match options.git_ref.as_deref() {
+        None => Some(
+            remote
+                .default_branch()?
+                .as_str()
+                .unwrap_or("HEAD")
+                .to_string(),
+        ),
+        Some(git_ref) => [
+            git_ref.to_string(),
+            format!("refs/heads/{}", git_ref),
+            format!("refs/tags/{}", git_ref),
+        ]
+        .into_iter()
+        .find(|name| advertised.contains(name)),
+    };
+    remote.disconnect()?;
+
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(remote_callbacks());
+    let commit = match (&target, options.git_ref.as_deref()) {
+        (Some(refspec), _) => {
+            // Local transports cannot serve shallow fetches
+            if options.depth > 0 && !url.starts_with("file://") {
+                fetch_options.depth(options.depth as i32);
+            }
+            remote.fetch(&[refspec.as_str()], Some(&mut fetch_options), None)?;
+            repo.find_reference("FETCH_HEAD")?.peel_to_commit()?
+        }
+        (None, git_ref) => {
+            let git_ref = git_ref.unwrap_or("HEAD");
+            tracing::debug!("{} is not a branch or tag; fetching full history", git_ref);
+            remote.fetch(
+                &[
+                    "+refs/heads/*:refs/remotes/origin/*",
+                    "+refs/tags/*:refs/tags/*",
+                ],
+                Some(&mut fetch_options),
+                None,
+            )?;
+            repo.revparse_single(git_ref)
+                .and_then(|object| object.peel_to_commit())
+                .map_err(|_| {
+                    git2::Error::from_str(&format!("Ref '{}' not found in {}", git_ref, url))
+                })?
+        }
+    };
+
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().force()))?;
+    repo.set_head_detached(commit.id())?;
+    drop(commit);
+    drop(remote);
+
     Ok(repo)
 }
 
+/// Credential callbacks for private remotes.
+///
+/// SSH URLs authenticate through the running ssh-agent; HTTPS URLs use the
+/// user's configured git credential helper. Each method is tried once so a
+/// rejected credential fails instead of looping.
+fn remote_callbacks<'a>() -> RemoteCallbacks<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+    let mut tried = CredentialType::empty();
+    callbacks.credentials(move |url, username, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY) && !tried.contains(CredentialType::SSH_KEY) {
+            tried |= CredentialType::SSH_KEY;
+            return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT)
+            && !tried.contains(CredentialType::USER_PASS_PLAINTEXT)
+        {
+            tried |= CredentialType::USER_PASS_PLAINTEXT;
+            let config = git2::Config::open_default()?;
+            return Cred::credential_helper(&config, url, username);
+        }
+        Err(git2::Error::from_str("No usable credentials for remote"))
+    });
+    callbacks
+}
+
 pub fn is_git_repo<P: AsRef<Path>>(path: P) -> bool {
     Repository::open(path).is_ok()
 }
@@ -35,3 +169,64 @@ pub async fn find_git_root(path: &Path) -> Result<PathBuf, RuleyError> {
         }
     }
 }
+
+/// SHA of the commit checked out in the repository containing `path`.
+///
+/// Returns `None` if `path` is not inside a git repository or the repository
+/// has no commits yet.
+pub fn head_commit(path: &Path) -> Option<String> {
+    let repo = Repository::discover(path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_remote_url() {
+        assert!(is_remote_url("https://github.com/org/repo"));
+        assert!(is_remote_url("http://example.com/repo.git"));
+        assert!(is_remote_url("ssh://git@github.com/org/repo.git"));
+        assert!(is_remote_url("git@github.com:org/repo.git"));
+        assert!(is_remote_url("file:///srv/git/repo.git"));
+
+        assert!(!is_remote_url("."));
+        assert!(!is_remote_url("/home/user/project"));
+        assert!(!is_remote_url("./dir:with:colons"));
+        assert!(!is_remote_url("C:\\Users\\me\\project"));
+    }
+
+    #[test]
+    fn test_repo_name_from_url() {
+        assert_eq!(repo_name_from_url("https://github.com/org/repo"), "repo");
+        assert_eq!(
+            repo_name_from_url("https://github.com/org/repo.git/"),
+            "repo"
+        );
+        assert_eq!(repo_name_from_url("git@github.com:org/tool.git"), "tool");
+        assert_eq!(repo_name_from_url("git@host:tool.git"), "tool");
+        assert_eq!(repo_name_from_url("file:///srv/git/origin.git"), "origin");
+    }
+
+    #[test]
+    fn test_head_commit() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert_eq!(head_commit(temp_dir.path()), None);
+
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        assert_eq!(head_commit(temp_dir.path()), None);
+
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap();
+
+        let subdir = temp_dir.path().join("src");
+        std::fs::create_dir(&subdir).unwrap();
+        assert_eq!(head_commit(&subdir), Some(commit.to_string()));
+    }
+}
@@ -5,28 +5,91 @@
 pub mod git;
 pub mod gitignore;
 pub mod output;
+pub mod query;
 pub mod repomix;
+pub mod secrets;
 pub mod walker;
+pub mod workspace;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 use std::path::PathBuf;
 
+/// How aggressively source files are compressed (`--compress-level`).
+///
+/// Levels are ordered from least to most aggressive. When a level cannot be
+/// applied to a file (no grammar, parse failure), the next less aggressive
+/// level is used instead.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+#[value(rename_all = "lowercase")]
+pub enum CompressionLevel {
+    /// Send files as written
+    #[default]
+    None,
+    /// Trim indentation and drop blank lines
+    Whitespace,
+    /// Keep function bodies, cut to their first few lines
+    Truncate,
+    /// Keep declarations and signatures, drop bodies
+    Signatures,
+}
+
+impl fmt::Display for CompressionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Whitespace => write!(f, "whitespace"),
+            Self::Truncate => write!(f, "truncate"),
+            Self::Signatures => write!(f, "signatures"),
+        }
+    }
+}
+
 /// Enumeration of supported compression methods.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CompressionMethod {
     /// Tree-sitter based compression
     TreeSitter,
+    /// Tree-sitter body truncation
+    Truncated,
     /// Whitespace and line-break normalization
     Whitespace,
     /// No compression applied
     None,
 }
 
+impl CompressionMethod {
+    /// The compression level this method implements.
+    pub fn level(self) -> CompressionLevel {
+        match self {
+            Self::TreeSitter => CompressionLevel::Signatures,
+            Self::Truncated => CompressionLevel::Truncate,
+            Self::Whitespace => CompressionLevel::Whitespace,
+            Self::None => CompressionLevel::None,
+        }
+    }
+}
+
 impl fmt::Display for CompressionMethod {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::TreeSitter => write!(f, "tree-sitter"),
+            Self::Truncated => write!(f, "truncated"),
             Self::Whitespace => write!(f, "whitespace"),
             Self::None => write!(f, "none"),
         }
@@ -118,7 +181,10 @@ pub fn new(files: Vec<CompressedFile>) -> Self {
 }
 
 pub use compress::{
-    Compressor, Language, TreeSitterCompressor, WhitespaceCompressor, compress_codebase,
+    Compressor, Language, TreeSitterCompressor, TruncatingCompressor, WhitespaceCompressor,
+    compress_codebase, compress_codebase_with_skipped,
 };
 pub use repomix::parse_repomix;
-pub use walker::{FileEntry, scan_files};
+pub use secrets::{SecretFinding, SecretScanner, redact_codebase};
+pub use walker::{FileEntry, ScanResult, SkipReason, SkippedFile, scan_files, scan_repository};
+pub use workspace::{Workspace, WorkspaceKind, WorkspaceMember, detect_workspace};
@@ -0,0 +1,266 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! User-defined tree-sitter queries that replace a language's built-in extractor.
+//!
+//! A query file marks what survives compression with three capture names:
+//!
+//! - `@keep` - the node's source text is kept
+//! - `@elide` - inside a kept node, the node is replaced by `...` (e.g. function bodies)
+//! - `@drop` - the node is removed, even from inside a kept node
+//!
+//! Captures starting with `_` can be used in predicates and are otherwise
+//! ignored. For example, this Rust query keeps doc comments, attributes and
+//! signatures but not test modules:
+//!
+//! ```scheme
+//! (line_comment) @keep
+//! (attribute_item) @keep
+//! (function_item body: (block) @elide) @keep
+//! ((attribute_item) @_cfg @drop . (mod_item) @drop
+//!   (#eq? @_cfg "#[cfg(test)]"))
+//! ```
+
+use super::compress::Language;
+use crate::utils::error::RuleyError;
+use std::ops::Range;
+use tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+
+/// What a capture name asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptureAction {
+    Keep,
+    Elide,
+    Drop,
+    /// `_`-prefixed captures used only by predicates
+    Ignore,
+}
+
+/// A compiled compression query for one language.
+pub struct CompressionQuery {
+    language: Language,
+    grammar: tree_sitter::Language,
+    query: Query,
+    actions: Vec<CaptureAction>,
+}
+
+impl std::fmt::Debug for CompressionQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressionQuery")
+            .field("language", &self.language)
+            .field("captures", &self.query.capture_names())
+            .finish()
+    }
+}
+
+impl CompressionQuery {
+    /// Compile `source` against the grammar for `language`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuleyError::Compression`] if the language has no grammar
+    /// compiled in, the query does not parse, it uses a capture name other
+    /// than `@keep`, `@elide`, `@drop` or `@_...`, or it captures nothing
+    /// as `@keep`.
+    pub fn new(language: Language, source: &str) -> Result<Self, RuleyError> {
+        let error = |message: String| RuleyError::Compression {
+            language: language.to_string(),
+            message,
+        };
+
+        let grammar = super::compress::grammar(language).ok_or_else(|| {
+            error(format!(
+                "no tree-sitter grammar for {} is compiled in; enable its compression feature",
+                language
+            ))
+        })?;
+        let query = Query::new(&grammar, source).map_err(|e| error(e.to_string()))?;
+
+        let actions = query
+            .capture_names()
+            .iter()
+            .map(|name| match *name {
+                "keep" => Ok(CaptureAction::Keep),
+                "elide" => Ok(CaptureAction::Elide),
+                "drop" => Ok(CaptureAction::Drop),
+                name if name.starts_with('_') => Ok(CaptureAction::Ignore),
+                name => Err(error(format!(
+                    "unknown capture @{}; use @keep, @elide, @drop or an @_-prefixed name",
+                    name
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !actions.contains(&CaptureAction::Keep) {
+            return Err(error("query has no @keep capture".to_string()));
+        }
+
+        Ok(Self {
+            language,
+            grammar,
+            query,
+            actions,
+        })
+    }
+
+    /// Returns the language this query was compiled for.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Compress `source` by keeping only the `@keep` captures, with `@elide`
+    /// captures replaced by `...` and `@drop` captures removed.
+    pub fn apply(&self, source: &str) -> Result<String, RuleyError> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&self.grammar)
+            .map_err(|_| RuleyError::Compression {
+                language: self.language.to_string(),
+                message: "Failed to set tree-sitter language".to_string(),
+            })?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| RuleyError::Compression {
+                language: self.language.to_string(),
+                message: format!("Failed to parse {} source code", self.language),
+            })?;
+
+        let mut keep = Vec::new();
+        let mut elide = Vec::new();
+        let mut drop = Vec::new();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&self.query, tree.root_node(), source.as_bytes());
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let range = capture.node.byte_range();
+                match self.actions[capture.index as usize] {
+                    CaptureAction::Keep => keep.push(range),
+                    CaptureAction::Elide => elide.push(range),
+                    CaptureAction::Drop => drop.push(range),
+                    CaptureAction::Ignore => {}
+                }
+            }
+        }
+
+        let drop = outermost(drop);
+        let keep: Vec<_> = outermost(keep)
+            .into_iter()
+            .filter(|range| !drop.iter().any(|d| contains(d, range)))
+            .collect();
+        // Dropping wins over eliding when both apply
+        let mut removed: Vec<(Range<usize>, &str)> = drop.into_iter().map(|r| (r, "")).collect();
+        removed.extend(outermost(elide).into_iter().map(|r| (r, "...")));
+        removed.sort_by_key(|(range, _)| (range.start, std::cmp::Reverse(range.end)));
+
+        let mut result = String::new();
+        for range in keep {
+            let mut pos = range.start;
+            let mut text = String::new();
+            for (cut, replacement) in &removed {
+                if cut.start < pos || cut.end > range.end {
+                    continue;
+                }
+                text.push_str(&source[pos..cut.start]);
+                text.push_str(replacement);
+                pos = cut.end;
+            }
+            text.push_str(&source[pos..range.end]);
+
+            let text = text.trim();
+            if !text.is_empty() {
+                result.push_str(text);
+                result.push('\n');
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Sort ranges by position and remove those nested inside another range.
+fn outermost(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| (range.start, std::cmp::Reverse(range.end)));
+    let mut result: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if result.last().is_none_or(|last| !contains(last, &range)) {
+            result.push(range);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outermost_removes_nested_and_duplicate_ranges() {
+        let ranges = vec![10..20, 0..5, 12..15, 10..20, 3..4, 25..30];
+        assert_eq!(outermost(ranges), vec![0..5, 10..20, 25..30]);
+    }
+
+    #[cfg(feature = "compression-rust")]
+    #[test]
+    fn test_keep_elide_and_drop() {
+        let query = CompressionQuery::new(
+            Language::Rust,
+            r##"
+            (line_comment) @keep
+            (attribute_item) @keep
+            (function_item body: (block) @elide) @keep
+            ((attribute_item) @_cfg @drop . (mod_item) @drop
+              (#eq? @_cfg "#[cfg(test)]"))
+            "##,
+        )
+        .unwrap();
+
+        let source = r#"
+/// Adds two numbers.
+#[inline]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+struct Hidden;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_add() {
+        assert_eq!(super::add(1, 2), 3);
+    }
+}
+"#;
+
+        let result = query.apply(source).unwrap();
+        assert_eq!(
+            result,
+            "/// Adds two numbers.\n#[inline]\npub fn add(a: i32, b: i32) -> i32 ...\n"
+        );
+    }
+
+    #[cfg(feature = "compression-rust")]
+    #[test]
+    fn test_invalid_queries_are_rejected() {
+        let syntax = CompressionQuery::new(Language::Rust, "(function_item").unwrap_err();
+        assert!(syntax.to_string().contains("rust"), "{syntax}");
+
+        let node = CompressionQuery::new(Language::Rust, "(not_a_node) @keep").unwrap_err();
+        assert!(node.to_string().contains("not_a_node"), "{node}");
+
+        let capture = CompressionQuery::new(Language::Rust, "(function_item) @fn").unwrap_err();
+        assert!(capture.to_string().contains("@fn"), "{capture}");
+
+        let no_keep = CompressionQuery::new(Language::Rust, "(function_item) @drop").unwrap_err();
+        assert!(no_keep.to_string().contains("@keep"), "{no_keep}");
+    }
+
+    #[test]
+    fn test_language_without_grammar_is_rejected() {
+        let err = CompressionQuery::new(Language::Unknown, "(x) @keep").unwrap_err();
+        assert!(err.to_string().contains("no tree-sitter grammar"), "{err}");
+    }
+}
@@ -30,7 +30,7 @@ pub fn new(path: PathBuf) -> Result<Self, RuleyError> {
             ))
         })?;
 
-        let language = detect_language(&path);
+        let language = detect_language(&path).or_else(|| detect_shebang_language(&path));
 
         Ok(Self {
             path,
@@ -53,14 +53,188 @@ pub(crate) fn detect_language(path: &Path) -> Option<Language> {
             "rs" => Some(Language::Rust),
             "go" => Some(Language::Go),
             "java" => Some(Language::Java),
+            "cs" => Some(Language::CSharp),
+            "kt" | "kts" => Some(Language::Kotlin),
             "c" | "h" => Some(Language::C),
             "cpp" | "hpp" | "cc" | "cxx" => Some(Language::Cpp),
             "rb" => Some(Language::Ruby),
             "php" => Some(Language::Php),
+            "sh" | "bash" => Some(Language::Shell),
             _ => None,
         })
 }
 
+/// Detect a shell script without an extension from its `#!` line.
+///
+/// Accepts both direct interpreters (`#!/bin/bash`) and `env` indirection
+/// (`#!/usr/bin/env bash`).
+pub(crate) fn detect_shebang_language(path: &Path) -> Option<Language> {
+    use std::io::Read;
+
+    if path.extension().is_some() {
+        return None;
+    }
+
+    let mut head = [0u8; 128];
+    let len = std::fs::File::open(path)
+        .and_then(|mut f| f.read(&mut head))
+        .ok()?;
+    let first_line = head[..len].split(|&b| b == b'\n').next()?;
+    let interpreter_line = std::str::from_utf8(first_line).ok()?.strip_prefix("#!")?;
+
+    let mut words = interpreter_line.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        interpreter = words.find(|word| !word.starts_with('-'))?;
+    }
+
+    match interpreter {
+        "sh" | "bash" | "dash" | "ksh" => Some(Language::Shell),
+        _ => None,
+    }
+}
+
+/// Bytes read from the start of each file for binary and generated-file checks.
+const SAMPLE_SIZE: usize = 8192;
+
+/// Lines at the top of a file searched for generated-code markers.
+const HEADER_LINES: usize = 10;
+
+/// Header comments that mark a file as generated.
+const GENERATED_MARKERS: [&str; 3] = ["@generated", "DO NOT EDIT", "<auto-generated"];
+
+/// File name patterns for generated files: lockfiles, minified bundles and
+/// protobuf output.
+const GENERATED_PATTERNS: [&str; 18] = [
+    "**/*.min.js",
+    "**/*.min.css",
+    "**/*.js.map",
+    "**/*.css.map",
+    "**/*.pb.go",
+    "**/*_pb2.py",
+    "**/*.pb.h",
+    "**/*.pb.cc",
+    "**/Cargo.lock",
+    "**/package-lock.json",
+    "**/npm-shrinkwrap.json",
+    "**/yarn.lock",
+    "**/pnpm-lock.yaml",
+    "**/poetry.lock",
+    "**/Pipfile.lock",
+    "**/Gemfile.lock",
+    "**/composer.lock",
+    "**/go.sum",
+];
+
+/// Why the scanner left a file out of the codebase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Contains NUL bytes or is not valid UTF-8
+    Binary,
+    /// Larger than `[scan] max_file_size`
+    TooLarge { size: u64, limit: u64 },
+    /// Matches a generated-file name pattern or header marker
+    Generated(String),
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Binary => write!(f, "binary"),
+            Self::TooLarge { size, limit } => {
+                write!(f, "too large: {} bytes > {} bytes", size, limit)
+            }
+            Self::Generated(why) => write!(f, "generated: {}", why),
+        }
+    }
+}
+
+/// A file the scanner skipped, and why.
+#[derive(Debug, Clone)]
+pub struct SkippedFile {
+    /// Path to the file
+    pub path: PathBuf,
+    /// Why the file was skipped
+    pub reason: SkipReason,
+}
+
+/// Result of scanning a repository.
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    /// Files to analyze
+    pub files: Vec<FileEntry>,
+    /// Files left out by the binary, size and generated-file filters
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Decide whether a file should be skipped from its size, name and first bytes.
+///
+/// `generated` is `None` when generated files are included.
+fn skip_reason(
+    path: &Path,
+    normalized_path: &str,
+    size: u64,
+    max_file_size: u64,
+    generated: Option<&GlobSet>,
+) -> Option<SkipReason> {
+    use std::io::Read;
+
+    if size > max_file_size {
+        return Some(SkipReason::TooLarge {
+            size,
+            limit: max_file_size,
+        });
+    }
+
+    if let Some(generated) = generated
+        && let Some(index) = generated.matches(normalized_path).first()
+    {
+        return Some(SkipReason::Generated(
+            GENERATED_PATTERNS[*index]
+                .trim_start_matches("**/")
+                .to_string(),
+        ));
+    }
+
+    let mut sample = Vec::with_capacity(SAMPLE_SIZE);
+    // Unreadable files are reported when compression reads them
+    let file = std::fs::File::open(path).ok()?;
+    file.take(SAMPLE_SIZE as u64)
+        .read_to_end(&mut sample)
+        .ok()?;
+
+    if is_binary(&sample) {
+        return Some(SkipReason::Binary);
+    }
+
+    if generated.is_some() {
+        let header = String::from_utf8_lossy(&sample);
+        for line in header.lines().take(HEADER_LINES) {
+            if let Some(marker) = GENERATED_MARKERS
+                .iter()
+                .find(|marker| line.contains(**marker))
+            {
+                return Some(SkipReason::Generated(format!("\"{}\" header", marker)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns true if `sample` (the start of a file) contains a NUL byte or
+/// invalid UTF-8. A multi-byte character cut off at the end of the sample is
+/// not an error.
+fn is_binary(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
 /// Build a GlobSet from a list of patterns.
 fn build_globset(patterns: &[String]) -> Result<GlobSet, RuleyError> {
     let mut builder = GlobSetBuilder::new();
@@ -81,19 +255,39 @@ fn normalize_path(path: &Path) -> String {
 }
 
 /// Scan files in a directory with pattern matching and language detection.
+///
+/// Binary, oversized and generated files are left out; use [`scan_repository`]
+/// to also get the list of skipped files.
 pub async fn scan_files(root: &Path, config: &MergedConfig) -> Result<Vec<FileEntry>, RuleyError> {
+    scan_repository(root, config).await.map(|scan| scan.files)
+}
+
+/// Scan files in a directory, recording the files skipped by the binary,
+/// size and generated-file filters.
+pub async fn scan_repository(root: &Path, config: &MergedConfig) -> Result<ScanResult, RuleyError> {
     // Build glob sets for include and exclude patterns
     let include_set = build_globset(&config.include)?;
     let exclude_set = build_globset(&config.exclude)?;
+    let generated_set = if config.scan.include_generated {
+        None
+    } else {
+        Some(build_globset(
+            &GENERATED_PATTERNS.map(|pattern| pattern.to_string()),
+        )?)
+    };
 
     let walker = WalkBuilder::new(root)
         .hidden(false)
         .git_ignore(true)
         .follow_links(false)
         .max_depth(None)
+        // Hidden files are scanned, but never git's object store or ruley's
+        // own cache, whose state changes on every run
+        .filter_entry(|entry| entry.file_name() != ".git" && entry.file_name() != ".ruley")
         .build();
 
     let mut entries = Vec::new();
+    let mut skipped = Vec::new();
 
     for result in walker {
         match result {
@@ -126,7 +320,24 @@ pub async fn scan_files(root: &Path, config: &MergedConfig) -> Result<Vec<FileEn
 
                 // Create FileEntry
                 match FileEntry::new(path.to_path_buf()) {
-                    Ok(file_entry) => entries.push(file_entry),
+                    Ok(file_entry) => {
+                        match skip_reason(
+                            path,
+                            &normalized_path,
+                            file_entry.size,
+                            config.scan.max_file_size,
+                            generated_set.as_ref(),
+                        ) {
+                            Some(reason) => {
+                                tracing::debug!("Skipping {} ({})", path.display(), reason);
+                                skipped.push(SkippedFile {
+                                    path: file_entry.path,
+                                    reason,
+                                });
+                            }
+                            None => entries.push(file_entry),
+                        }
+                    }
                     Err(e) => {
                         // Log warnings for specific errors but continue scanning
                         if matches!(e, RuleyError::FileSystem(ref io_err) if io_err.kind() == std::io::ErrorKind::PermissionDenied)
@@ -145,8 +356,15 @@ pub async fn scan_files(root: &Path, config: &MergedConfig) -> Result<Vec<FileEn
         }
     }
 
-    tracing::info!("Scanned {} files", entries.len());
-    Ok(entries)
+    tracing::info!(
+        "Scanned {} files ({} skipped)",
+        entries.len(),
+        skipped.len()
+    );
+    Ok(ScanResult {
+        files: entries,
+        skipped,
+    })
 }
 
 pub struct FileWalker {
@@ -166,6 +384,7 @@ pub fn walk(&self) -> Result<Vec<std::path::PathBuf>, RuleyError> {
         let walker = WalkBuilder::new(&self.root)
             .hidden(false)
             .git_ignore(true)
+            .filter_entry(|entry| entry.file_name() != ".git")
             .build();
 
         for result in walker {
@@ -218,9 +437,132 @@ fn test_detect_language_extension_mapping() {
         );
         assert_eq!(detect_language(Path::new("lib.rs")), Some(Language::Rust));
         assert_eq!(detect_language(Path::new("main.go")), Some(Language::Go));
+        assert_eq!(
+            detect_language(Path::new("Service.java")),
+            Some(Language::Java)
+        );
+        assert_eq!(
+            detect_language(Path::new("Program.cs")),
+            Some(Language::CSharp)
+        );
+        assert_eq!(
+            detect_language(Path::new("Service.kt")),
+            Some(Language::Kotlin)
+        );
+        assert_eq!(
+            detect_language(Path::new("build.gradle.kts")),
+            Some(Language::Kotlin)
+        );
+
+        // C and C++, including headers
+        assert_eq!(detect_language(Path::new("main.c")), Some(Language::C));
+        assert_eq!(detect_language(Path::new("device.h")), Some(Language::C));
+        assert_eq!(
+            detect_language(Path::new("buffer.cpp")),
+            Some(Language::Cpp)
+        );
+        assert_eq!(
+            detect_language(Path::new("buffer.hpp")),
+            Some(Language::Cpp)
+        );
+
+        // Shell scripts
+        assert_eq!(
+            detect_language(Path::new("build.sh")),
+            Some(Language::Shell)
+        );
+        assert_eq!(
+            detect_language(Path::new("setup.bash")),
+            Some(Language::Shell)
+        );
 
         // Unknown extension returns None
         assert_eq!(detect_language(Path::new("readme.md")), None);
         assert_eq!(detect_language(Path::new("config.toml")), None);
     }
+
+    /// Test that extension-less scripts are detected from their shebang.
+    #[test]
+    fn test_detect_shebang_language() {
+        let temp = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+
+        let bash = write("deploy", "#!/bin/bash\nset -e\n");
+        let env_sh = write("configure", "#!/usr/bin/env -S sh -e\necho hi\n");
+        let python = write("manage", "#!/usr/bin/env python3\nprint('hi')\n");
+        let plain = write("LICENSE", "Apache License\n");
+        let with_ext = write("notes.txt", "#!/bin/sh\n");
+
+        assert_eq!(detect_shebang_language(&bash), Some(Language::Shell));
+        assert_eq!(detect_shebang_language(&env_sh), Some(Language::Shell));
+        assert_eq!(detect_shebang_language(&python), None);
+        assert_eq!(detect_shebang_language(&plain), None);
+        assert_eq!(detect_shebang_language(&with_ext), None);
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b"plain text\n"));
+        assert!(!is_binary("caf\u{e9}".as_bytes()));
+        assert!(is_binary(b"\x7fELF\x02\x01\x00\x00"));
+        assert!(is_binary(b"latin-1 caf\xe9 text"));
+        // A character split by the sample boundary is still text
+        assert!(!is_binary(&"caf\u{e9}".as_bytes()[..4]));
+    }
+
+    /// Test the size, binary and generated-file checks and their reasons.
+    #[test]
+    fn test_skip_reason() {
+        let temp = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &[u8]| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+        let generated = build_globset(&GENERATED_PATTERNS.map(|p| p.to_string())).unwrap();
+        let check = |path: &Path, limit: u64, generated: Option<&GlobSet>| {
+            let size = std::fs::metadata(path).unwrap().len();
+            skip_reason(path, &normalize_path(path), size, limit, generated)
+        };
+
+        let source = write("main.rs", b"fn main() {}\n");
+        assert_eq!(check(&source, 1024, Some(&generated)), None);
+        assert_eq!(
+            check(&source, 4, Some(&generated)),
+            Some(SkipReason::TooLarge { size: 13, limit: 4 })
+        );
+
+        let image = write("logo.png", b"\x89PNG\r\n\x1a\n\x00\x00");
+        assert_eq!(check(&image, 1024, None), Some(SkipReason::Binary));
+
+        let lockfile = write(
+            "Cargo.lock",
+            b"# This file is automatically @generated by Cargo.\n",
+        );
+        assert_eq!(
+            check(&lockfile, 1024, Some(&generated)),
+            Some(SkipReason::Generated("Cargo.lock".to_string()))
+        );
+        let bundle = write("app.min.js", b"var a=1;\n");
+        assert_eq!(
+            check(&bundle, 1024, Some(&generated)),
+            Some(SkipReason::Generated("*.min.js".to_string()))
+        );
+        let protobuf = write(
+            "api.go",
+            b"// Code generated by protoc-gen-go. DO NOT EDIT.\n\npackage api\n",
+        );
+        assert_eq!(
+            check(&protobuf, 1024, Some(&generated)),
+            Some(SkipReason::Generated("\"DO NOT EDIT\" header".to_string()))
+        );
+
+        // With generated files included, only binary and size checks apply
+        assert_eq!(check(&lockfile, 1024, None), None);
+        assert_eq!(check(&protobuf, 1024, None), None);
+    }
 }
@@ -0,0 +1,571 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Monorepo workspace detection.
+//!
+//! Finds the member packages of Cargo, Go, Nx, pnpm, npm/Yarn and Turborepo
+//! workspaces so rules can be generated for each package separately
+//! (`--workspaces`). Member globs are expanded against the directories that
+//! contain the workspace's manifest file (`Cargo.toml`, `go.mod`,
+//! `package.json`, `project.json`).
+
+use crate::utils::error::RuleyError;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Directories never searched for members.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build"];
+
+/// The tool whose configuration defined the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    /// `[workspace] members` in `Cargo.toml`
+    Cargo,
+    /// `use` directives in `go.work`
+    Go,
+    /// `project.json` files next to `nx.json`
+    Nx,
+    /// `packages` in `pnpm-workspace.yaml`
+    Pnpm,
+    /// `workspaces` in `package.json` (npm, Yarn, Bun)
+    Npm,
+    /// pnpm or npm workspaces with a `turbo.json`
+    Turbo,
+}
+
+impl fmt::Display for WorkspaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Cargo => "Cargo",
+            Self::Go => "Go",
+            Self::Nx => "Nx",
+            Self::Pnpm => "pnpm",
+            Self::Npm => "npm",
+            Self::Turbo => "Turborepo",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A package in a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// Package name from its manifest, or its directory name
+    pub name: String,
+    /// Directory of the package, relative to the workspace root
+    pub path: PathBuf,
+}
+
+impl WorkspaceMember {
+    /// File-name-safe form of the package name (`@acme/web` -> `acme-web`).
+    pub fn slug(&self) -> String {
+        let slug: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let slug = slug.trim_matches(['-', '.']).to_string();
+        if slug.is_empty() {
+            "package".to_string()
+        } else {
+            slug
+        }
+    }
+}
+
+/// A detected workspace and its members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub kind: WorkspaceKind,
+    /// Members sorted by path; never includes the root itself
+    pub members: Vec<WorkspaceMember>,
+}
+
+/// Finds the member directories of one workspace kind, relative to the root.
+type MemberDetector = fn(&Path) -> Result<Vec<PathBuf>, RuleyError>;
+
+/// Detect the workspace rooted at `root`.
+///
+/// Checks, in order: a Cargo `[workspace]`, `go.work`, Nx (`nx.json` with
+/// `project.json` files), `pnpm-workspace.yaml` and `package.json`
+/// `workspaces`. pnpm and npm workspaces are reported as Turborepo when a
+/// `turbo.json` is present. Returns `None` if no workspace with members is
+/// found.
+///
+/// # Errors
+///
+/// Returns an error if a workspace manifest exists but cannot be read or
+/// parsed.
+pub fn detect_workspace(root: &Path) -> Result<Option<Workspace>, RuleyError> {
+    let detectors: [(WorkspaceKind, MemberDetector); 3] = [
+        (WorkspaceKind::Cargo, cargo_members),
+        (WorkspaceKind::Go, go_members),
+        (WorkspaceKind::Nx, nx_members),
+    ];
+    for (kind, detect) in detectors {
+        let paths = detect(root)?;
+        if !paths.is_empty() {
+            return Ok(Some(workspace(root, kind, paths)));
+        }
+    }
+
+    let paths = js_members(root)?;
+    if paths.is_empty() {
+        return Ok(None);
+    }
+    let kind = if root.join("turbo.json").is_file() {
+        WorkspaceKind::Turbo
+    } else if root.join("pnpm-workspace.yaml").is_file() {
+        WorkspaceKind::Pnpm
+    } else {
+        WorkspaceKind::Npm
+    };
+    Ok(Some(workspace(root, kind, paths)))
+}
+
+fn workspace(root: &Path, kind: WorkspaceKind, mut paths: Vec<PathBuf>) -> Workspace {
+    paths.sort();
+    paths.dedup();
+    let members = paths
+        .into_iter()
+        .map(|path| WorkspaceMember {
+            name: member_name(kind, &root.join(&path)).unwrap_or_else(|| dir_name(&path)),
+            path,
+        })
+        .collect();
+    Workspace { kind, members }
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Package name from the member's manifest.
+fn member_name(kind: WorkspaceKind, dir: &Path) -> Option<String> {
+    match kind {
+        WorkspaceKind::Cargo => {
+            let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+            let table: toml::Table = manifest.parse().ok()?;
+            table
+                .get("package")?
+                .get("name")?
+                .as_str()
+                .map(str::to_string)
+        }
+        WorkspaceKind::Go => {
+            let go_mod = std::fs::read_to_string(dir.join("go.mod")).ok()?;
+            go_mod.lines().find_map(|line| {
+                line.trim()
+                    .strip_prefix("module ")
+                    .map(|module| module.trim().trim_matches('"').to_string())
+            })
+        }
+        WorkspaceKind::Nx => {
+            json_name(&dir.join("project.json")).or_else(|| json_name(&dir.join("package.json")))
+        }
+        WorkspaceKind::Pnpm | WorkspaceKind::Npm | WorkspaceKind::Turbo => {
+            json_name(&dir.join("package.json"))
+        }
+    }
+}
+
+fn json_name(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json.get("name")?.as_str().map(str::to_string)
+}
+
+fn read_manifest(path: &Path) -> Result<Option<String>, RuleyError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(path)
+        .map(Some)
+        .map_err(|e| RuleyError::Config(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+fn parse_error(path: &Path, e: impl fmt::Display) -> RuleyError {
+    RuleyError::Config(format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Members of a Cargo `[workspace]`, minus its `exclude` list.
+fn cargo_members(root: &Path) -> Result<Vec<PathBuf>, RuleyError> {
+    let path = root.join("Cargo.toml");
+    let Some(manifest) = read_manifest(&path)? else {
+        return Ok(Vec::new());
+    };
+    let table: toml::Table = manifest.parse().map_err(|e| parse_error(&path, e))?;
+    let Some(workspace) = table.get("workspace") else {
+        return Ok(Vec::new());
+    };
+
+    let strings = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    expand_members(root, &strings("members"), &strings("exclude"), "Cargo.toml")
+}
+
+/// Directories named by `use` directives in `go.work`.
+fn go_members(root: &Path) -> Result<Vec<PathBuf>, RuleyError> {
+    let Some(go_work) = read_manifest(&root.join("go.work"))? else {
+        return Ok(Vec::new());
+    };
+
+    let mut paths = Vec::new();
+    let mut in_block = false;
+    for line in go_work.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        let entry = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+
+        let relative = normalize(entry.trim_matches('"'));
+        if !relative.is_empty() && root.join(&relative).join("go.mod").is_file() {
+            paths.push(PathBuf::from(relative));
+        }
+    }
+    Ok(paths)
+}
+
+/// Directories with a `project.json` in an Nx workspace.
+fn nx_members(root: &Path) -> Result<Vec<PathBuf>, RuleyError> {
+    if !root.join("nx.json").is_file() {
+        return Ok(Vec::new());
+    }
+    let members = expand_members(root, &["**".to_string()], &[], "project.json")?;
+    if members.is_empty() {
+        // Nx on top of package manager workspaces
+        return js_members(root);
+    }
+    Ok(members)
+}
+
+/// Members from `pnpm-workspace.yaml`, else from `package.json` `workspaces`.
+fn js_members(root: &Path) -> Result<Vec<PathBuf>, RuleyError> {
+    let patterns = if let Some(yaml) = read_manifest(&root.join("pnpm-workspace.yaml"))? {
+        pnpm_packages(&yaml)
+    } else if let Some(json) = read_manifest(&root.join("package.json"))? {
+        let path = root.join("package.json");
+        let json: serde_json::Value =
+            serde_json::from_str(&json).map_err(|e| parse_error(&path, e))?;
+        // Either an array or `{ "packages": [...] }` (Yarn classic)
+        let workspaces = json.get("workspaces");
+        let list = workspaces
+            .and_then(|w| w.get("packages"))
+            .or(workspaces)
+            .and_then(|w| w.as_array());
+        list.map(|values| {
+            values
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let (excludes, includes): (Vec<String>, Vec<String>) =
+        patterns.into_iter().partition(|p| p.starts_with('!'));
+    let excludes: Vec<String> = excludes
+        .iter()
+        .map(|p| p.trim_start_matches('!').to_string())
+        .collect();
+    expand_members(root, &includes, &excludes, "package.json")
+}
+
+/// The `packages` list of a `pnpm-workspace.yaml`.
+///
+/// Only the two forms pnpm documents are understood: a block sequence
+/// (`- 'packages/*'`) and a flow sequence (`['packages/*']`).
+fn pnpm_packages(yaml: &str) -> Vec<String> {
+    let unquote = |s: &str| s.trim().trim_matches(['\'', '"']).to_string();
+
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let content = line.split(" #").next().unwrap_or("").trim_end();
+        if content.trim().is_empty() || content.trim_start().starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = content.strip_prefix("packages:") {
+            let rest = rest.trim();
+            if let Some(flow) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+                packages.extend(flow.split(',').map(unquote).filter(|p| !p.is_empty()));
+                return packages;
+            }
+            in_packages = true;
+        } else if in_packages {
+            match content.trim_start().strip_prefix('-') {
+                Some(item) if content.starts_with([' ', '\t', '-']) => packages.push(unquote(item)),
+                _ => break,
+            }
+        }
+    }
+    packages
+}
+
+/// Strip `./` prefixes and trailing slashes from a member path or glob.
+fn normalize(pattern: &str) -> String {
+    let mut pattern = pattern.trim();
+    while let Some(rest) = pattern.strip_prefix("./") {
+        pattern = rest;
+    }
+    pattern.trim_end_matches('/').to_string()
+}
+
+fn glob_set(patterns: &[String]) -> Result<GlobSet, RuleyError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = normalize(pattern);
+        if pattern.is_empty() || pattern == "." {
+            continue;
+        }
+        let glob = GlobBuilder::new(&pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| {
+                RuleyError::Config(format!("Invalid workspace member glob '{pattern}': {e}"))
+            })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| RuleyError::Config(format!("Invalid workspace member globs: {e}")))
+}
+
+/// Directories below `root` that contain `manifest`, match one of `includes`
+/// and none of `excludes`.
+fn expand_members(
+    root: &Path,
+    includes: &[String],
+    excludes: &[String],
+    manifest: &str,
+) -> Result<Vec<PathBuf>, RuleyError> {
+    let includes = glob_set(includes)?;
+    if includes.is_empty() {
+        return Ok(Vec::new());
+    }
+    let excludes = glob_set(excludes)?;
+
+    let walker = WalkBuilder::new(root)
+        .hidden(true)
+        .git_ignore(true)
+        .follow_links(false)
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !SKIPPED_DIRS.contains(&name.as_ref())
+        })
+        .build();
+
+    let mut members = Vec::new();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_dir()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() || !entry.path().join(manifest).is_file() {
+            continue;
+        }
+        if includes.is_match(relative) && !excludes.is_match(relative) {
+            members.push(relative.to_path_buf());
+        }
+    }
+    Ok(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_files(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn members(workspace: &Workspace) -> Vec<(&str, &str)> {
+        workspace
+            .members
+            .iter()
+            .map(|m| (m.name.as_str(), m.path.to_str().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_cargo_workspace() {
+        let dir = create_files(&[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/old\"]\n",
+            ),
+            (
+                "crates/core/Cargo.toml",
+                "[package]\nname = \"acme-core\"\n",
+            ),
+            ("crates/old/Cargo.toml", "[package]\nname = \"old\"\n"),
+            ("crates/notes/README.md", "no manifest"),
+            (
+                "crates/core/nested/Cargo.toml",
+                "[package]\nname = \"nested\"\n",
+            ),
+            ("tools/cli/Cargo.toml", "[package]\nname = \"acme\"\n"),
+        ]);
+
+        let workspace = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Cargo);
+        assert_eq!(
+            members(&workspace),
+            vec![("acme-core", "crates/core"), ("acme", "tools/cli")]
+        );
+    }
+
+    #[test]
+    fn test_cargo_package_without_workspace() {
+        let dir = create_files(&[("Cargo.toml", "[package]\nname = \"single\"\n")]);
+        assert_eq!(detect_workspace(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_go_work() {
+        let dir = create_files(&[
+            (
+                "go.work",
+                "go 1.22\n\nuse (\n\t./api // service\n\t./worker\n)\nuse ./tools\n",
+            ),
+            ("api/go.mod", "module github.com/acme/api\n"),
+            ("worker/go.mod", "module github.com/acme/worker\n"),
+            ("tools/go.mod", "module github.com/acme/tools\n"),
+        ]);
+
+        let workspace = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Go);
+        assert_eq!(
+            members(&workspace),
+            vec![
+                ("github.com/acme/api", "api"),
+                ("github.com/acme/tools", "tools"),
+                ("github.com/acme/worker", "worker"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pnpm_workspace() {
+        let dir = create_files(&[
+            (
+                "pnpm-workspace.yaml",
+                "# workspace\npackages:\n  - 'apps/*'\n  - \"packages/**\"\n  - '!packages/legacy'\ncatalog:\n  react: ^18\n",
+            ),
+            ("apps/web/package.json", "{\"name\": \"@acme/web\"}"),
+            ("packages/ui/package.json", "{\"name\": \"@acme/ui\"}"),
+            ("packages/legacy/package.json", "{\"name\": \"legacy\"}"),
+            ("packages/ui/node_modules/dep/package.json", "{}"),
+        ]);
+
+        let workspace = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Pnpm);
+        assert_eq!(
+            members(&workspace),
+            vec![("@acme/web", "apps/web"), ("@acme/ui", "packages/ui")]
+        );
+        assert_eq!(workspace.members[0].slug(), "acme-web");
+    }
+
+    #[test]
+    fn test_npm_and_turbo_workspaces() {
+        let files = [
+            (
+                "package.json",
+                "{\"workspaces\": {\"packages\": [\"packages/*\"]}}",
+            ),
+            ("packages/api/package.json", "{}"),
+        ];
+        let dir = create_files(&files);
+        let workspace = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Npm);
+        assert_eq!(members(&workspace), vec![("api", "packages/api")]);
+
+        std::fs::write(dir.path().join("turbo.json"), "{}").unwrap();
+        let workspace = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Turbo);
+    }
+
+    #[test]
+    fn test_nx_workspace() {
+        let dir = create_files(&[
+            ("nx.json", "{}"),
+            ("package.json", "{\"name\": \"root\"}"),
+            ("apps/shop/project.json", "{\"name\": \"shop\"}"),
+            ("libs/data/project.json", "{}"),
+        ]);
+
+        let workspace = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Nx);
+        assert_eq!(
+            members(&workspace),
+            vec![("shop", "apps/shop"), ("data", "libs/data")]
+        );
+    }
+
+    #[test]
+    fn test_pnpm_packages_forms() {
+        assert_eq!(
+            pnpm_packages("packages: ['a/*', \"b\"]\n"),
+            vec!["a/*".to_string(), "b".to_string()]
+        );
+        assert_eq!(
+            pnpm_packages("packages:\n- a # apps\n- ./b/\nonlyBuiltDependencies:\n- esbuild\n"),
+            vec!["a".to_string(), "./b/".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        let dir = create_files(&[("Cargo.toml", "[workspace\n")]);
+        assert!(matches!(
+            detect_workspace(dir.path()),
+            Err(RuleyError::Config(_))
+        ));
+    }
+}
@@ -6,11 +6,15 @@
 //! This module manages temporary files created during the rule generation pipeline:
 //! - `files.json` - Scanned file list
 //! - `compressed.txt` - Compressed codebase content
-//! - `chunk-{id}.json` - Individual chunk analysis results
+//! - `chunk-{key}.json` - Individual chunk analysis results, keyed by
+//!   [`chunk_cache_key`] so interrupted runs can be resumed
 //! - `state.json` - Persistent state (preserved across cleanups)
 
+use crate::llm::analysis::{ChunkResult, ChunkResultStore};
+use crate::llm::chunker::Chunk;
 use crate::utils::error::RuleyError;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
@@ -205,16 +209,16 @@ pub fn read_compressed_codebase(&self) -> Result<String, RuleyError> {
             .map_err(|e| RuleyError::Cache(format!("Failed to read {}: {}", path.display(), e)))
     }
 
-    /// Write a chunk analysis result to `chunk-{id}.json`.
+    /// Write a chunk analysis result to `chunk-{key}.json`.
     ///
     /// # Arguments
-    /// * `chunk_id` - Numeric identifier for the chunk
+    /// * `key` - Identifier for the chunk (see [`chunk_cache_key`])
     /// * `result` - Analysis result content
     ///
     /// # Returns
     /// Path to the written file on success.
-    pub fn write_chunk_result(&self, chunk_id: usize, result: &str) -> Result<PathBuf, RuleyError> {
-        let filename = format!("{}{}.json", Self::CHUNK_PREFIX, chunk_id);
+    pub fn write_chunk_result(&self, key: &str, result: &str) -> Result<PathBuf, RuleyError> {
+        let filename = format!("{}{}.json", Self::CHUNK_PREFIX, key);
         let path = self.ruley_dir.join(&filename);
 
         std::fs::write(&path, result)
@@ -223,15 +227,15 @@ pub fn write_chunk_result(&self, chunk_id: usize, result: &str) -> Result<PathBu
         Ok(path)
     }
 
-    /// Read a chunk analysis result from `chunk-{id}.json`.
+    /// Read a chunk analysis result from `chunk-{key}.json`.
     ///
     /// # Arguments
-    /// * `chunk_id` - Numeric identifier for the chunk
+    /// * `key` - Identifier for the chunk (see [`chunk_cache_key`])
     ///
     /// # Returns
     /// Analysis result content as string on success.
-    pub fn read_chunk_result(&self, chunk_id: usize) -> Result<String, RuleyError> {
-        let filename = format!("{}{}.json", Self::CHUNK_PREFIX, chunk_id);
+    pub fn read_chunk_result(&self, key: &str) -> Result<String, RuleyError> {
+        let filename = format!("{}{}.json", Self::CHUNK_PREFIX, key);
         let path = self.ruley_dir.join(&filename);
 
         std::fs::read_to_string(&path)
@@ -380,6 +384,103 @@ pub fn cleanup_old_temp_files(
     }
 }
 
+/// Compute the cache key for a chunk analysis.
+///
+/// The key is a SHA-256 hex digest of the model, the analysis prompt and the
+/// chunk content, so a cached result is only reused for an identical request.
+pub fn chunk_cache_key(content: &str, prompt: &str, model: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [model, prompt, content] {
+        // Length-prefix each part so boundaries can't shift between them
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// A chunk analysis as stored in `chunk-{key}.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedChunkResult {
+    analysis: String,
+    prompt_tokens: usize,
+    completion_tokens: usize,
+}
+
+/// [`ChunkResultStore`] backed by `chunk-{key}.json` files in `.ruley/`.
+///
+/// Every analysis is written as it completes. Stored results are only read
+/// back when `reuse` is set (the `--resume` flag).
+#[derive(Debug)]
+pub struct ChunkResultCache<'a> {
+    manager: &'a TempFileManager,
+    prompt: &'a str,
+    model: &'a str,
+    reuse: bool,
+}
+
+impl<'a> ChunkResultCache<'a> {
+    /// Create a chunk result cache for one analysis prompt and model.
+    ///
+    /// # Arguments
+    /// * `manager` - The `.ruley/` directory manager
+    /// * `prompt` - The analysis prompt template sent with every chunk
+    /// * `model` - The model the chunks are analyzed with
+    /// * `reuse` - Whether stored results may be loaded
+    pub fn new(manager: &'a TempFileManager, prompt: &'a str, model: &'a str, reuse: bool) -> Self {
+        Self {
+            manager,
+            prompt,
+            model,
+            reuse,
+        }
+    }
+
+    fn key(&self, chunk: &Chunk) -> String {
+        chunk_cache_key(&chunk.content, self.prompt, self.model)
+    }
+}
+
+impl ChunkResultStore for ChunkResultCache<'_> {
+    fn load(&self, chunk: &Chunk) -> Option<ChunkResult> {
+        if !self.reuse {
+            return None;
+        }
+
+        // A missing file is the normal case for chunks that never completed
+        let json = self.manager.read_chunk_result(&self.key(chunk)).ok()?;
+        match serde_json::from_str::<CachedChunkResult>(&json) {
+            Ok(cached) => Some(ChunkResult::new(
+                chunk.id,
+                cached.analysis,
+                cached.prompt_tokens,
+                cached.completion_tokens,
+            )),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring corrupt cached result for chunk {}: {}",
+                    chunk.id + 1,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    fn store(&self, chunk: &Chunk, result: &ChunkResult) {
+        let cached = CachedChunkResult {
+            analysis: result.analysis.clone(),
+            prompt_tokens: result.prompt_tokens,
+            completion_tokens: result.completion_tokens,
+        };
+        let written = serde_json::to_string(&cached)
+            .map_err(|e| RuleyError::Cache(format!("Failed to serialize chunk result: {}", e)))
+            .and_then(|json| self.manager.write_chunk_result(&self.key(chunk), &json));
+        if let Err(e) = written {
+            tracing::warn!("Failed to cache result for chunk {}: {}", chunk.id + 1, e);
+        }
+    }
+}
+
 /// Ensure `.ruley/` is listed in the project's `.gitignore` file.
 ///
 /// This is a standalone function that can be called independently of `TempFileManager`.
@@ -547,10 +648,10 @@ fn test_write_read_chunk_result() {
 
         // Write chunks
         let path_0 = manager
-            .write_chunk_result(0, chunk_0_result)
+            .write_chunk_result("0", chunk_0_result)
             .expect("Failed to write chunk 0");
         let path_1 = manager
-            .write_chunk_result(1, chunk_1_result)
+            .write_chunk_result("1", chunk_1_result)
             .expect("Failed to write chunk 1");
 
         assert!(path_0.exists());
@@ -560,10 +661,10 @@ fn test_write_read_chunk_result() {
 
         // Read back and verify
         let read_0 = manager
-            .read_chunk_result(0)
+            .read_chunk_result("0")
             .expect("Failed to read chunk 0");
         let read_1 = manager
-            .read_chunk_result(1)
+            .read_chunk_result("1")
             .expect("Failed to read chunk 1");
 
         assert_eq!(read_0, chunk_0_result);
@@ -575,7 +676,7 @@ fn test_read_nonexistent_chunk_returns_error() {
         let temp_dir = create_test_dir();
         let manager = TempFileManager::new(temp_dir.path()).expect("Failed to create manager");
 
-        let result = manager.read_chunk_result(999);
+        let result = manager.read_chunk_result("999");
         assert!(result.is_err(), "Reading non-existent chunk should fail");
 
         let err = result.unwrap_err();
@@ -585,6 +686,83 @@ fn test_read_nonexistent_chunk_returns_error() {
         );
     }
 
+    #[test]
+    fn test_chunk_cache_key_covers_content_prompt_and_model() {
+        let key = chunk_cache_key("fn main() {}", "Analyze", "model-a");
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, chunk_cache_key("fn main() {}", "Analyze", "model-a"));
+        assert_ne!(key, chunk_cache_key("fn main() {} ", "Analyze", "model-a"));
+        assert_ne!(key, chunk_cache_key("fn main() {}", "Analyze!", "model-a"));
+        assert_ne!(key, chunk_cache_key("fn main() {}", "Analyze", "model-b"));
+        // Moving text between parts changes the key
+        assert_ne!(
+            chunk_cache_key("ab", "c", "m"),
+            chunk_cache_key("b", "ac", "m")
+        );
+    }
+
+    #[test]
+    fn test_chunk_result_cache_stores_and_resumes() {
+        let temp_dir = create_test_dir();
+        let manager = TempFileManager::new(temp_dir.path()).expect("Failed to create manager");
+        let chunk = Chunk {
+            id: 3,
+            content: "fn main() {}".to_string(),
+            token_count: 3,
+            overlap_token_count: 0,
+            files: Vec::new(),
+        };
+
+        let writer = ChunkResultCache::new(&manager, "Analyze", "model-a", false);
+        writer.store(
+            &chunk,
+            &ChunkResult::new(3, "Uses Rust".to_string(), 100, 20),
+        );
+        // Without --resume, stored results are never read back
+        assert!(writer.load(&chunk).is_none());
+
+        let resumed = ChunkResultCache::new(&manager, "Analyze", "model-a", true)
+            .load(&chunk)
+            .expect("stored result should be reused");
+        assert_eq!(resumed.chunk_id, 3);
+        assert_eq!(resumed.analysis, "Uses Rust");
+        assert_eq!(resumed.prompt_tokens, 100);
+        assert_eq!(resumed.completion_tokens, 20);
+
+        // A different model or prompt never matches
+        assert!(
+            ChunkResultCache::new(&manager, "Analyze", "model-b", true)
+                .load(&chunk)
+                .is_none()
+        );
+        assert!(
+            ChunkResultCache::new(&manager, "Other", "model-a", true)
+                .load(&chunk)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_chunk_result_cache_ignores_corrupt_entry() {
+        let temp_dir = create_test_dir();
+        let manager = TempFileManager::new(temp_dir.path()).expect("Failed to create manager");
+        let chunk = Chunk {
+            id: 0,
+            content: "fn main() {}".to_string(),
+            token_count: 3,
+            overlap_token_count: 0,
+            files: Vec::new(),
+        };
+
+        let key = chunk_cache_key(&chunk.content, "Analyze", "model-a");
+        manager
+            .write_chunk_result(&key, "not json")
+            .expect("Failed to write chunk");
+
+        let cache = ChunkResultCache::new(&manager, "Analyze", "model-a", true);
+        assert!(cache.load(&chunk).is_none());
+    }
+
     #[test]
     fn test_cleanup_removes_temp_preserves_state() {
         let temp_dir = create_test_dir();
@@ -598,7 +776,7 @@ fn test_cleanup_removes_temp_preserves_state() {
             .write_compressed_codebase("test")
             .expect("Failed to write compressed");
         manager
-            .write_chunk_result(0, "chunk0")
+            .write_chunk_result("0", "chunk0")
             .expect("Failed to write chunk");
 
         // Create state.json manually
@@ -11,19 +11,16 @@
 //!
 //! ```ignore
 //! use ruley::utils::cost_display::{display_cost_estimate, prompt_confirmation};
-//! use ruley::llm::provider::Pricing;
+//! use ruley::llm::models::ModelRegistry;
 //!
-//! let pricing = Pricing {
-//!     input_per_1k: 0.003,
-//!     output_per_1k: 0.015,
-//! };
+//! let model = ModelRegistry::new().resolve("anthropic", Some("claude-sonnet-4-5"));
 //!
 //! display_cost_estimate(
 //!     &codebase,
 //!     &chunks,
 //!     &["cursor".to_string(), "claude".to_string()],
 //!     "anthropic",
-//!     &pricing,
+//!     &model,
 //!     false, // quiet
 //! )?;
 //!
@@ -32,7 +29,7 @@
 
 use crate::llm::chunker::Chunk;
 use crate::llm::cost::CostCalculator;
-use crate::llm::provider::Pricing;
+use crate::llm::models::ModelInfo;
 use crate::packer::{CompressedCodebase, Language};
 use crate::utils::formatting::format_number;
 use anyhow::Result;
@@ -40,7 +37,7 @@
 use std::collections::HashMap;
 use std::io::Write;
 
-/// Estimated output tokens per chunk analysis.
+/// Estimated output tokens per chunk analysis, capped at the model's output limit.
 const ESTIMATED_OUTPUT_TOKENS_PER_CHUNK: usize = 4096;
 
 /// Estimated tokens per format refinement call.
@@ -60,7 +57,7 @@
 /// * `chunks` - The chunks prepared for analysis
 /// * `formats` - The output formats to generate
 /// * `provider` - The LLM provider name (for display)
-/// * `pricing` - The pricing information for cost calculation
+/// * `model` - The resolved model info (pricing, context window, output limit)
 /// * `quiet` - If true, suppresses output entirely
 ///
 /// # Errors
@@ -71,7 +68,7 @@ pub fn display_cost_estimate(
     chunks: &[Chunk],
     formats: &[String],
     provider: &str,
-    pricing: &Pricing,
+    model: &ModelInfo,
     quiet: bool,
 ) -> Result<()> {
     if quiet {
@@ -79,14 +76,15 @@ pub fn display_cost_estimate(
     }
 
     let mut term = Term::stdout();
-    let calculator = CostCalculator::new(pricing.clone());
+    let calculator = CostCalculator::new(model.pricing.clone());
+    let output_tokens = ESTIMATED_OUTPUT_TOKENS_PER_CHUNK.min(model.max_output_tokens);
 
     // Calculate token counts
     let total_compressed_tokens: usize = chunks.iter().map(|c| c.token_count).sum();
     let is_multi_chunk = chunks.len() > 1;
 
     // Calculate costs
-    let breakdown = calculate_cost_breakdown(chunks, formats, &calculator);
+    let breakdown = calculate_cost_breakdown(chunks, formats, &calculator, output_tokens);
 
     // Format language breakdown
     let language_breakdown = format_language_breakdown(&codebase.metadata.languages);
@@ -153,9 +151,10 @@ pub fn display_cost_estimate(
     if is_multi_chunk {
         writeln!(
             term,
-            "{} Chunks: {} (exceeds context limit)",
+            "{} Chunks: {} (exceeds {} token context window)",
             style("\u{251c}\u{2500}").dim(),
-            chunks.len()
+            chunks.len(),
+            format_number(model.context_window)
         )?;
     } else {
         writeln!(
@@ -191,8 +190,7 @@ pub fn display_cost_estimate(
     if is_multi_chunk {
         // Multi-chunk breakdown
         for (i, chunk) in chunks.iter().enumerate() {
-            let chunk_cost =
-                calculator.calculate_cost(chunk.token_count, ESTIMATED_OUTPUT_TOKENS_PER_CHUNK);
+            let chunk_cost = calculator.calculate_cost(chunk.token_count, output_tokens);
             writeln!(
                 term,
                 "{} Chunk {} analysis: ${:.2} ({} tokens)",
@@ -204,8 +202,7 @@ pub fn display_cost_estimate(
         }
 
         // Merge call
-        let merge_cost =
-            calculator.calculate_cost(ESTIMATED_MERGE_TOKENS, ESTIMATED_OUTPUT_TOKENS_PER_CHUNK);
+        let merge_cost = calculator.calculate_cost(ESTIMATED_MERGE_TOKENS, output_tokens);
         writeln!(
             term,
             "{} Merge call: ${:.2} (~{} tokens)",
@@ -337,18 +334,19 @@ fn calculate_cost_breakdown(
     chunks: &[Chunk],
     formats: &[String],
     calculator: &CostCalculator,
+    output_tokens: usize,
 ) -> CostBreakdownDisplay {
     let is_multi_chunk = chunks.len() > 1;
 
     // Calculate analysis cost
     let analysis_cost: f64 = chunks
         .iter()
-        .map(|c| calculator.calculate_cost(c.token_count, ESTIMATED_OUTPUT_TOKENS_PER_CHUNK))
+        .map(|c| calculator.calculate_cost(c.token_count, output_tokens))
         .sum();
 
     // Calculate merge cost (only for multi-chunk)
     let merge_cost = if is_multi_chunk {
-        calculator.calculate_cost(ESTIMATED_MERGE_TOKENS, ESTIMATED_OUTPUT_TOKENS_PER_CHUNK)
+        calculator.calculate_cost(ESTIMATED_MERGE_TOKENS, output_tokens)
     } else {
         0.0
     };
@@ -399,8 +397,12 @@ fn format_provider_display(provider: &str) -> String {
     match provider.to_lowercase().as_str() {
         "anthropic" => "Anthropic Claude Sonnet".to_string(),
         "openai" => "OpenAI GPT-4o".to_string(),
+        "openai-compatible" => "OpenAI-compatible".to_string(),
         "ollama" => "Ollama (local)".to_string(),
         "openrouter" => "OpenRouter".to_string(),
+        "xai" => "xAI Grok".to_string(),
+        "groq" => "Groq".to_string(),
+        "gemini" => "Google Gemini".to_string(),
         other => other.to_string(),
     }
 }
@@ -408,13 +410,20 @@ fn format_provider_display(provider: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::llm::provider::Pricing;
+    use crate::llm::tokenizer::TokenizerModel;
     use crate::packer::{CodebaseMetadata, CompressedFile, CompressionMethod};
     use std::path::PathBuf;
 
-    fn create_test_pricing() -> Pricing {
-        Pricing {
-            input_per_1k: 0.003,
-            output_per_1k: 0.015,
+    fn create_test_model() -> ModelInfo {
+        ModelInfo {
+            context_window: 200_000,
+            max_output_tokens: 64_000,
+            pricing: Pricing {
+                input_per_1k: 0.003,
+                output_per_1k: 0.015,
+            },
+            tokenizer: TokenizerModel::Claude,
         }
     }
 
@@ -453,6 +462,7 @@ fn create_test_chunks(count: usize, tokens_per_chunk: usize) -> Vec<Chunk> {
                 content: "test content".to_string(),
                 token_count: tokens_per_chunk,
                 overlap_token_count: if i > 0 { 1000 } else { 0 },
+                files: Vec::new(),
             })
             .collect()
     }
@@ -490,17 +500,22 @@ fn test_format_provider_display() {
         );
         assert_eq!(format_provider_display("openai"), "OpenAI GPT-4o");
         assert_eq!(format_provider_display("ollama"), "Ollama (local)");
+        assert_eq!(format_provider_display("gemini"), "Google Gemini");
         assert_eq!(format_provider_display("unknown"), "unknown");
     }
 
     #[test]
     fn test_calculate_cost_breakdown_single_chunk() {
-        let pricing = create_test_pricing();
-        let calculator = CostCalculator::new(pricing);
+        let calculator = CostCalculator::new(create_test_model().pricing);
         let chunks = create_test_chunks(1, 50000);
         let formats = vec!["cursor".to_string(), "claude".to_string()];
 
-        let breakdown = calculate_cost_breakdown(&chunks, &formats, &calculator);
+        let breakdown = calculate_cost_breakdown(
+            &chunks,
+            &formats,
+            &calculator,
+            ESTIMATED_OUTPUT_TOKENS_PER_CHUNK,
+        );
 
         assert!(breakdown.total_cost > 0.0);
         assert!(breakdown.analysis_cost > 0.0);
@@ -510,8 +525,7 @@ fn test_calculate_cost_breakdown_single_chunk() {
 
     #[test]
     fn test_calculate_cost_breakdown_multi_chunk() {
-        let pricing = create_test_pricing();
-        let calculator = CostCalculator::new(pricing);
+        let calculator = CostCalculator::new(create_test_model().pricing);
         let chunks = create_test_chunks(3, 78189);
         let formats = vec![
             "cursor".to_string(),
@@ -519,7 +533,12 @@ fn test_calculate_cost_breakdown_multi_chunk() {
             "copilot".to_string(),
         ];
 
-        let breakdown = calculate_cost_breakdown(&chunks, &formats, &calculator);
+        let breakdown = calculate_cost_breakdown(
+            &chunks,
+            &formats,
+            &calculator,
+            ESTIMATED_OUTPUT_TOKENS_PER_CHUNK,
+        );
 
         assert!(breakdown.total_cost > 0.0);
         assert!(breakdown.analysis_cost > 0.0);
@@ -532,12 +551,29 @@ fn test_calculate_cost_breakdown_multi_chunk() {
         assert!((breakdown.total_cost - expected_total).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_calculate_cost_breakdown_respects_output_limit() {
+        let calculator = CostCalculator::new(create_test_model().pricing);
+        let chunks = create_test_chunks(1, 50000);
+        let formats = vec!["cursor".to_string()];
+
+        let full = calculate_cost_breakdown(
+            &chunks,
+            &formats,
+            &calculator,
+            ESTIMATED_OUTPUT_TOKENS_PER_CHUNK,
+        );
+        let capped = calculate_cost_breakdown(&chunks, &formats, &calculator, 1024);
+
+        assert!(capped.analysis_cost < full.analysis_cost);
+    }
+
     #[test]
     fn test_display_cost_estimate_quiet_mode() {
         let codebase = create_test_codebase();
         let chunks = create_test_chunks(1, 48234);
         let formats = vec!["cursor".to_string()];
-        let pricing = create_test_pricing();
+        let model = create_test_model();
 
         // Should not error in quiet mode
         let result = display_cost_estimate(
@@ -545,7 +581,7 @@ fn test_display_cost_estimate_quiet_mode() {
             &chunks,
             &formats,
             "anthropic",
-            &pricing,
+            &model,
             true, // quiet
         );
 
@@ -561,11 +597,11 @@ fn test_display_cost_estimate_single_chunk() {
             "claude".to_string(),
             "copilot".to_string(),
         ];
-        let pricing = create_test_pricing();
+        let model = create_test_model();
 
         // Should not error
         let result =
-            display_cost_estimate(&codebase, &chunks, &formats, "anthropic", &pricing, false);
+            display_cost_estimate(&codebase, &chunks, &formats, "anthropic", &model, false);
 
         assert!(result.is_ok());
     }
@@ -579,11 +615,11 @@ fn test_display_cost_estimate_multi_chunk() {
             "claude".to_string(),
             "copilot".to_string(),
         ];
-        let pricing = create_test_pricing();
+        let model = create_test_model();
 
         // Should not error
         let result =
-            display_cost_estimate(&codebase, &chunks, &formats, "anthropic", &pricing, false);
+            display_cost_estimate(&codebase, &chunks, &formats, "anthropic", &model, false);
 
         assert!(result.is_ok());
     }
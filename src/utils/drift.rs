@@ -0,0 +1,468 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drift report for `ruley drift`.
+//!
+//! Sends the existing rule files, the list of current files and a sample of
+//! the code to the LLM, which judges every rule against the code, lists
+//! conventions no rule covers and references to files that no longer exist.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ruley::utils::drift::{parse_drift_response, render_drift_markdown, sample_codebase};
+//!
+//! let sample = sample_codebase(codebase, 50_000, tokenizer.as_ref(), &repo_path);
+//! let findings = parse_drift_response(&response.content)?;
+//! ```
+
+use crate::llm::cost::CostSummary;
+use crate::llm::selection::select_files;
+use crate::llm::tokenizer::Tokenizer;
+use crate::packer::{CompressedCodebase, CompressedFile};
+use crate::utils::error::RuleyError;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use console::{Term, style};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+/// Tokens of code sampled when no `--token-budget` is given.
+pub const DEFAULT_SAMPLE_TOKENS: usize = 50_000;
+
+/// Output tokens expected for a drift report, used for the cost estimate.
+pub const ESTIMATED_REPORT_TOKENS: usize = 4_000;
+
+/// Whether the code still follows a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// The sampled code follows the rule
+    Followed,
+    /// The sampled code contradicts the rule
+    Violated,
+    /// The sample gives no evidence either way
+    Unverifiable,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Followed => "followed",
+            Self::Violated => "violated",
+            Self::Unverifiable => "unverifiable",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// The verdict on one rule of a rule file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleVerdict {
+    /// Rule file the rule comes from
+    pub file: String,
+    /// The rule, as stated or summarized
+    pub rule: String,
+    pub verdict: Verdict,
+    /// Files and patterns supporting the verdict
+    #[serde(default)]
+    pub evidence: String,
+}
+
+/// A convention the code follows that no rule describes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UncoveredPattern {
+    pub pattern: String,
+    /// Files showing the pattern
+    #[serde(default)]
+    pub evidence: String,
+}
+
+/// A file or directory a rule refers to that no longer exists.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissingReference {
+    /// Rule file containing the reference
+    pub file: String,
+    /// The referenced path
+    pub reference: String,
+}
+
+/// Findings parsed from the LLM response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct DriftFindings {
+    #[serde(default)]
+    pub rules: Vec<RuleVerdict>,
+    #[serde(default)]
+    pub uncovered_patterns: Vec<UncoveredPattern>,
+    #[serde(default)]
+    pub missing_references: Vec<MissingReference>,
+}
+
+impl DriftFindings {
+    /// Drop reported references that exist below `root` after all.
+    pub fn retain_missing_references(&mut self, root: &Path) {
+        self.missing_references
+            .retain(|r| !root.join(r.reference.trim_start_matches("./")).exists());
+    }
+}
+
+/// Complete drift report, as displayed and written to Markdown or JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftReport {
+    pub generated_at: DateTime<Utc>,
+    /// Rule files that were audited
+    pub rule_files: Vec<String>,
+    /// Number of files in the code sample
+    pub files_sampled: usize,
+    /// Number of files in the codebase
+    pub files_total: usize,
+    pub rules: Vec<RuleVerdict>,
+    pub uncovered_patterns: Vec<UncoveredPattern>,
+    pub missing_references: Vec<MissingReference>,
+    /// Cost of the LLM calls that produced the report
+    pub cost: CostSummary,
+}
+
+impl DriftReport {
+    /// Number of rules with the given verdict.
+    pub fn count(&self, verdict: Verdict) -> usize {
+        self.rules.iter().filter(|r| r.verdict == verdict).count()
+    }
+}
+
+/// Sample the files of `codebase` that fit in `budget` tokens, with paths
+/// relative to `root` so the LLM can match them against rule references.
+pub fn sample_codebase(
+    codebase: &CompressedCodebase,
+    budget: usize,
+    tokenizer: &dyn Tokenizer,
+    root: &Path,
+) -> CompressedCodebase {
+    let selection = select_files(codebase, budget, tokenizer);
+    let files = selection
+        .apply(codebase.clone())
+        .files
+        .into_iter()
+        .map(|file| CompressedFile {
+            path: file
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&file.path)
+                .to_path_buf(),
+            ..file
+        })
+        .collect();
+    CompressedCodebase::new(files)
+}
+
+/// Parse the JSON findings from the LLM response.
+///
+/// Text around the JSON object, such as code fences, is ignored.
+///
+/// # Errors
+///
+/// Returns [`RuleyError::ParseError`] if the response holds no valid findings.
+pub fn parse_drift_response(response: &str) -> Result<DriftFindings, RuleyError> {
+    let json = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => {
+            return Err(RuleyError::ParseError {
+                message: "Drift report response contains no JSON object".to_string(),
+                source: None,
+            });
+        }
+    };
+
+    serde_json::from_str(json).map_err(|e| RuleyError::ParseError {
+        message: format!("Invalid drift report JSON: {e}"),
+        source: Some(Box::new(e)),
+    })
+}
+
+/// Render the report as Markdown.
+pub fn render_drift_markdown(report: &DriftReport) -> String {
+    let mut out = String::from("# Rule Drift Report\n\n");
+    out.push_str(&format!(
+        "Generated {} from {} rule file(s) and a sample of {} of {} files.\n\n",
+        report.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        report.rule_files.len(),
+        report.files_sampled,
+        report.files_total
+    ));
+    out.push_str(&format!(
+        "{} followed, {} violated, {} unverifiable, {} uncovered pattern(s), {} missing reference(s). Cost: ${:.4}\n",
+        report.count(Verdict::Followed),
+        report.count(Verdict::Violated),
+        report.count(Verdict::Unverifiable),
+        report.uncovered_patterns.len(),
+        report.missing_references.len(),
+        report.cost.total_cost
+    ));
+
+    // Rule files in audit order, then any other file the LLM attributed rules to
+    let mut files: Vec<&str> = report.rule_files.iter().map(String::as_str).collect();
+    for rule in &report.rules {
+        if !files.contains(&rule.file.as_str()) {
+            files.push(&rule.file);
+        }
+    }
+
+    for file in files {
+        out.push_str(&format!("\n## `{}`\n\n", file));
+        if !report.rules.iter().any(|r| r.file == file) {
+            out.push_str("No rules were judged.\n");
+            continue;
+        }
+        out.push_str("| Rule | Verdict | Evidence |\n");
+        out.push_str("| --- | --- | --- |\n");
+        for rule in report.rules.iter().filter(|r| r.file == file) {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                escape_cell(&rule.rule),
+                rule.verdict,
+                escape_cell(&rule.evidence)
+            ));
+        }
+    }
+
+    if !report.uncovered_patterns.is_empty() {
+        out.push_str("\n## Uncovered Patterns\n\n");
+        for pattern in &report.uncovered_patterns {
+            out.push_str(&format!("- {}", pattern.pattern));
+            if !pattern.evidence.is_empty() {
+                out.push_str(&format!(" ({})", pattern.evidence));
+            }
+            out.push('\n');
+        }
+    }
+
+    if !report.missing_references.is_empty() {
+        out.push_str("\n## Missing References\n\n");
+        for reference in &report.missing_references {
+            out.push_str(&format!(
+                "- `{}` in `{}`\n",
+                reference.reference, reference.file
+            ));
+        }
+    }
+
+    out
+}
+
+/// Write the report to `path` as JSON.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_drift_json(report: &DriftReport, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(report).context("Failed to serialize drift report")?;
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write drift report to {}", path.display()))
+}
+
+/// Print the report, listing violated rules, uncovered patterns and missing references.
+pub fn display_drift_report(report: &DriftReport) -> Result<()> {
+    let mut term = Term::stdout();
+
+    writeln!(term)?;
+    writeln!(term, "{}", style("Rule Drift Report").bold())?;
+    writeln!(
+        term,
+        "{} rule file(s), {} of {} files sampled",
+        report.rule_files.len(),
+        report.files_sampled,
+        report.files_total
+    )?;
+    writeln!(
+        term,
+        "{} followed, {} violated, {} unverifiable",
+        style(report.count(Verdict::Followed)).green(),
+        style(report.count(Verdict::Violated)).yellow(),
+        report.count(Verdict::Unverifiable)
+    )?;
+
+    let violated: Vec<_> = report
+        .rules
+        .iter()
+        .filter(|r| r.verdict == Verdict::Violated)
+        .collect();
+    if !violated.is_empty() {
+        writeln!(term)?;
+        writeln!(term, "{}:", style("Violated rules").bold())?;
+        for (i, rule) in violated.iter().enumerate() {
+            let prefix = tree_prefix(i, violated.len());
+            writeln!(term, "{} {}: {}", style(prefix).dim(), rule.file, rule.rule)?;
+            if !rule.evidence.is_empty() {
+                let indent = if i == violated.len() - 1 {
+                    "  "
+                } else {
+                    "\u{2502} "
+                };
+                writeln!(
+                    term,
+                    "{}   {}",
+                    style(indent).dim(),
+                    style(&rule.evidence).dim()
+                )?;
+            }
+        }
+    }
+
+    if !report.uncovered_patterns.is_empty() {
+        writeln!(term)?;
+        writeln!(term, "{}:", style("Uncovered patterns").bold())?;
+        for (i, pattern) in report.uncovered_patterns.iter().enumerate() {
+            let prefix = tree_prefix(i, report.uncovered_patterns.len());
+            writeln!(term, "{} {}", style(prefix).dim(), pattern.pattern)?;
+        }
+    }
+
+    if !report.missing_references.is_empty() {
+        writeln!(term)?;
+        writeln!(term, "{}:", style("Missing references").bold())?;
+        for (i, reference) in report.missing_references.iter().enumerate() {
+            let prefix = tree_prefix(i, report.missing_references.len());
+            writeln!(
+                term,
+                "{} {} (in {})",
+                style(prefix).dim(),
+                reference.reference,
+                reference.file
+            )?;
+        }
+    }
+
+    writeln!(term)?;
+    writeln!(
+        term,
+        "Cost: ${:.4} ({} input, {} output tokens)",
+        report.cost.total_cost, report.cost.total_input_tokens, report.cost.total_output_tokens
+    )?;
+
+    Ok(())
+}
+
+fn tree_prefix(index: usize, len: usize) -> &'static str {
+    if index == len - 1 {
+        "\u{2514}\u{2500}"
+    } else {
+        "\u{251c}\u{2500}"
+    }
+}
+
+/// Keep a value on one line and from splitting the table row.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::test_support::{WordTokenizer, create_test_codebase};
+    use std::path::PathBuf;
+
+    const RESPONSE: &str = r#"```json
+{"rules": [
+  {"file": "CLAUDE.md", "rule": "Use thiserror | anyhow", "verdict": "violated", "evidence": "src/api.rs"},
+  {"file": "CLAUDE.md", "rule": "Tests live in tests/", "verdict": "followed"},
+  {"file": ".cursor/rules/style.mdc", "rule": "Prefer iterators", "verdict": "unverifiable", "evidence": ""}
+],
+"uncovered_patterns": [{"pattern": "Builders take self by value", "evidence": "src/client.rs"}],
+"missing_references": [{"file": "CLAUDE.md", "reference": "src/legacy/mod.rs"}, {"file": "CLAUDE.md", "reference": "src/main.rs"}]}
+```"#;
+
+    fn report(findings: DriftFindings) -> DriftReport {
+        DriftReport {
+            generated_at: "2026-01-29T12:00:00Z".parse().unwrap(),
+            rule_files: vec![
+                ".cursor/rules/style.mdc".to_string(),
+                "CLAUDE.md".to_string(),
+            ],
+            files_sampled: 2,
+            files_total: 5,
+            rules: findings.rules,
+            uncovered_patterns: findings.uncovered_patterns,
+            missing_references: findings.missing_references,
+            cost: CostSummary {
+                total_cost: 0.0123,
+                total_input_tokens: 1000,
+                total_output_tokens: 200,
+                operation_count: 1,
+                cache_hits: 0,
+                operations: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_drift_response() {
+        let findings = parse_drift_response(RESPONSE).unwrap();
+        assert_eq!(findings.rules.len(), 3);
+        assert_eq!(findings.rules[0].verdict, Verdict::Violated);
+        assert_eq!(findings.rules[1].evidence, "");
+        assert_eq!(findings.uncovered_patterns[0].evidence, "src/client.rs");
+        assert_eq!(findings.missing_references.len(), 2);
+
+        let empty = parse_drift_response(r#"{"rules": []}"#).unwrap();
+        assert_eq!(empty, DriftFindings::default());
+
+        assert!(matches!(
+            parse_drift_response("No drift found."),
+            Err(RuleyError::ParseError { .. })
+        ));
+        assert!(parse_drift_response(r#"{"rules": [{"verdict": "maybe"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_retain_missing_references() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        let mut findings = parse_drift_response(RESPONSE).unwrap();
+        findings.retain_missing_references(temp_dir.path());
+        assert_eq!(
+            findings.missing_references,
+            vec![MissingReference {
+                file: "CLAUDE.md".to_string(),
+                reference: "src/legacy/mod.rs".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render_drift_markdown() {
+        let report = report(parse_drift_response(RESPONSE).unwrap());
+        assert_eq!(report.count(Verdict::Violated), 1);
+
+        let markdown = render_drift_markdown(&report);
+        assert!(markdown.contains("a sample of 2 of 5 files"));
+        assert!(markdown.contains(
+            "1 followed, 1 violated, 1 unverifiable, 1 uncovered pattern(s), 2 missing reference(s). Cost: $0.0123"
+        ));
+        assert!(markdown.contains("## `CLAUDE.md`"));
+        assert!(markdown.contains("| Use thiserror \\| anyhow | violated | src/api.rs |"));
+        assert!(markdown.contains("| Prefer iterators | unverifiable |  |"));
+        assert!(markdown.contains("- Builders take self by value (src/client.rs)"));
+        assert!(markdown.contains("- `src/legacy/mod.rs` in `CLAUDE.md`"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["rules"][0]["verdict"], "violated");
+        assert_eq!(json["cost"]["total_input_tokens"], 1000);
+    }
+
+    #[test]
+    fn test_sample_codebase() {
+        let big = "word ".repeat(500);
+        let codebase = create_test_codebase(vec![
+            ("/repo/src/main.rs", "fn main() {}"),
+            ("/repo/src/big.rs", &big),
+        ]);
+
+        let sample = sample_codebase(&codebase, 100, &WordTokenizer, Path::new("/repo"));
+        assert_eq!(sample.metadata.total_files, 1);
+        assert_eq!(sample.files[0].path, PathBuf::from("src/main.rs"));
+    }
+}
@@ -13,16 +13,20 @@
 //!
 //! display_dry_run_summary(
 //!     &compressed_codebase,
+//!     &skipped_files,
+//!     &secret_findings,
+//!     file_selection.as_ref(),
 //!     &["cursor", "claude", "copilot"],
 //!     &merged_config,
-//!     &pricing,
+//!     &model_info,
 //! )?;
 //! ```
 
 use crate::MergedConfig;
-use crate::llm::provider::Pricing;
+use crate::llm::models::ModelInfo;
+use crate::llm::selection::{FileSelection, RankedFile};
 use crate::output::get_formatter;
-use crate::packer::{CompressedCodebase, Language};
+use crate::packer::{CompressedCodebase, CompressionLevel, Language, SecretFinding, SkippedFile};
 use crate::utils::formatting::format_number;
 use anyhow::Result;
 use console::{Term, style};
@@ -34,24 +38,30 @@
 
 /// Display a comprehensive dry-run summary.
 ///
-/// Shows file breakdown by language with token counts, compression statistics,
-/// estimated cost, and output locations.
+/// Shows file breakdown by language with token counts, skipped files,
+/// redacted secrets, token-budget selection, compression statistics, estimated cost, and output locations.
 ///
 /// # Arguments
 ///
 /// * `codebase` - The compressed codebase with file metadata
+/// * `skipped` - Files the scanner left out, each with its reason
+/// * `secrets` - Secrets that were redacted, with their locations
+/// * `selection` - Files included and dropped by `--token-budget`, if set
 /// * `formats` - Output formats that would be generated
 /// * `config` - The merged configuration
-/// * `pricing` - Pricing information for cost estimation
+/// * `model` - Resolved model info for cost estimation and limits
 ///
 /// # Errors
 ///
 /// Returns an error if writing to the terminal fails.
 pub fn display_dry_run_summary(
     codebase: &CompressedCodebase,
+    skipped: &[SkippedFile],
+    secrets: &[SecretFinding],
+    selection: Option<&FileSelection>,
     formats: &[String],
     config: &MergedConfig,
-    pricing: &Pricing,
+    model: &ModelInfo,
 ) -> Result<()> {
     let mut term = Term::stdout();
 
@@ -78,14 +88,9 @@ pub fn display_dry_run_summary(
     languages.sort_by(|a, b| {
         files_by_language
             .get(*b)
-            .map(|v: &Vec<(String, usize)>| v.len())
+            .map(Vec::len)
             .unwrap_or(0)
-            .cmp(
-                &files_by_language
-                    .get(*a)
-                    .map(|v: &Vec<(String, usize)>| v.len())
-                    .unwrap_or(0),
-            )
+            .cmp(&files_by_language.get(*a).map(Vec::len).unwrap_or(0))
     });
 
     for (lang_idx, language) in languages.iter().enumerate() {
@@ -118,7 +123,7 @@ pub fn display_dry_run_summary(
 
         // Show first few files
         let files_to_show = files.len().min(MAX_FILES_PER_LANGUAGE);
-        for (file_idx, (path, tokens)) in files.iter().take(files_to_show).enumerate() {
+        for (file_idx, file) in files.iter().take(files_to_show).enumerate() {
             let is_last_file =
                 file_idx == files_to_show - 1 && files.len() <= MAX_FILES_PER_LANGUAGE;
             let file_prefix = if is_last_file {
@@ -129,11 +134,12 @@ pub fn display_dry_run_summary(
 
             writeln!(
                 term,
-                "{}{} {} ({} tokens)",
+                "{}{} {} ({} tokens, {})",
                 child_prefix,
                 style(file_prefix).dim(),
-                path,
-                format_number(*tokens)
+                file.path,
+                format_number(file.tokens),
+                file.level
             )?;
         }
 
@@ -148,6 +154,71 @@ pub fn display_dry_run_summary(
         }
     }
 
+    // Every skipped file, so filters that are too broad are easy to spot
+    if !skipped.is_empty() {
+        writeln!(term)?;
+        writeln!(
+            term,
+            "{} ({}):",
+            style("Skipped files").bold(),
+            format_number(skipped.len())
+        )?;
+        for (i, file) in skipped.iter().enumerate() {
+            let prefix = if i == skipped.len() - 1 {
+                "\u{2514}\u{2500}"
+            } else {
+                "\u{251c}\u{2500}"
+            };
+            writeln!(
+                term,
+                "{} {} ({})",
+                style(prefix).dim(),
+                file.path.display(),
+                file.reason
+            )?;
+        }
+    }
+
+    // Every redacted secret, so they can be removed or excluded before a real run
+    if !secrets.is_empty() {
+        writeln!(term)?;
+        writeln!(
+            term,
+            "{} ({}):",
+            style("Secrets to redact").bold(),
+            format_number(secrets.len())
+        )?;
+        for (i, finding) in secrets.iter().enumerate() {
+            let prefix = if i == secrets.len() - 1 {
+                "\u{2514}\u{2500}"
+            } else {
+                "\u{251c}\u{2500}"
+            };
+            writeln!(
+                term,
+                "{} {}:{} ({})",
+                style(prefix).dim(),
+                finding.path.display(),
+                finding.line,
+                finding.pattern
+            )?;
+        }
+    }
+
+    // Every file included or dropped by the token budget
+    if let Some(selection) = selection {
+        writeln!(term)?;
+        writeln!(
+            term,
+            "{}: {} of {} tokens used",
+            style("Token budget").bold(),
+            format_number(selection.included_tokens()),
+            format_number(selection.budget)
+        )?;
+        write_ranked_files(&mut term, "Included files", &selection.included)?;
+        write_ranked_files(&mut term, "Dropped files", &selection.dropped)?;
+    }
+
     // Total line
     let total_compressed_tokens: usize = codebase
         .files
@@ -185,7 +256,7 @@ pub fn display_dry_run_summary(
     }
 
     // Estimated cost
-    let estimated_cost = estimate_cost(total_compressed_tokens, formats.len(), pricing);
+    let estimated_cost = estimate_cost(total_compressed_tokens, formats.len(), model);
     writeln!(
         term,
         "{}: {}",
@@ -198,9 +269,9 @@ pub fn display_dry_run_summary(
     writeln!(term, "{}:", style("Configuration").bold())?;
     writeln!(
         term,
-        "{} Compress: {}",
+        "{} Compression Level: {}",
         style("\u{251c}\u{2500}").dim(),
-        config.compress
+        config.compress_level
     )?;
     writeln!(
         term,
@@ -208,6 +279,12 @@ pub fn display_dry_run_summary(
         style("\u{251c}\u{2500}").dim(),
         config.chunk_size
     )?;
+    writeln!(
+        term,
+        "{} Context Window: {}",
+        style("\u{251c}\u{2500}").dim(),
+        format_number(model.context_window)
+    )?;
     writeln!(
         term,
         "{} On Conflict: {}",
@@ -242,26 +319,67 @@ pub fn display_dry_run_summary(
     Ok(())
 }
 
+/// Write a titled list of token-budget files with their tokens and ranking reasons.
+fn write_ranked_files(term: &mut Term, title: &str, files: &[RankedFile]) -> Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let tokens: usize = files.iter().map(|f| f.tokens).sum();
+    writeln!(
+        term,
+        "{} ({}, {} tokens):",
+        style(title).bold(),
+        format_number(files.len()),
+        format_number(tokens)
+    )?;
+    for (i, file) in files.iter().enumerate() {
+        let prefix = if i == files.len() - 1 {
+            "\u{2514}\u{2500}"
+        } else {
+            "\u{251c}\u{2500}"
+        };
+        let mut details = format!("{} tokens", format_number(file.tokens));
+        for reason in &file.reasons {
+            details.push_str(&format!(", {reason}"));
+        }
+        writeln!(
+            term,
+            "{} {} ({})",
+            style(prefix).dim(),
+            file.path.display(),
+            details
+        )?;
+    }
+    Ok(())
+}
+
+/// A file line in the dry-run breakdown.
+#[derive(Debug)]
+struct FileSummary {
+    path: String,
+    tokens: usize,
+    /// Compression level actually applied (after any fallback)
+    level: CompressionLevel,
+}
+
 /// Group files by their detected language.
-fn group_files_by_language(
-    codebase: &CompressedCodebase,
-) -> HashMap<Language, Vec<(String, usize)>> {
-    let mut by_language: HashMap<Language, Vec<(String, usize)>> = HashMap::new();
+fn group_files_by_language(codebase: &CompressedCodebase) -> HashMap<Language, Vec<FileSummary>> {
+    let mut by_language: HashMap<Language, Vec<FileSummary>> = HashMap::new();
 
     for file in &codebase.files {
         let language = file.language.unwrap_or(Language::Unknown);
-        let tokens = estimate_tokens(&file.compressed_content);
-        let path = file.path.display().to_string();
 
-        by_language
-            .entry(language)
-            .or_default()
-            .push((path, tokens));
+        by_language.entry(language).or_default().push(FileSummary {
+            path: file.path.display().to_string(),
+            tokens: estimate_tokens(&file.compressed_content),
+            level: file.compression_method.level(),
+        });
     }
 
     // Sort files within each language by token count (descending)
     for files in by_language.values_mut() {
-        files.sort_by(|a: &(String, usize), b: &(String, usize)| b.1.cmp(&a.1));
+        files.sort_by(|a, b| b.tokens.cmp(&a.tokens));
     }
 
     by_language
@@ -287,19 +405,20 @@ fn estimate_tokens(content: &str) -> usize {
     content.len().div_ceil(4)
 }
 
-/// Estimated output tokens per analysis call.
+/// Estimated output tokens per analysis call, capped at the model's output limit.
 const ESTIMATED_OUTPUT_TOKENS: usize = 4096;
 
 /// Estimated tokens per format refinement call.
 const TOKENS_PER_FORMAT: usize = 500;
 
 /// Estimate the cost for the dry run.
-fn estimate_cost(input_tokens: usize, format_count: usize, pricing: &Pricing) -> f64 {
+fn estimate_cost(input_tokens: usize, format_count: usize, model: &ModelInfo) -> f64 {
     let format_tokens = format_count * TOKENS_PER_FORMAT;
+    let output_tokens = ESTIMATED_OUTPUT_TOKENS.min(model.max_output_tokens);
 
-    let input_cost = (input_tokens as f64 / 1000.0) * pricing.input_per_1k;
+    let input_cost = (input_tokens as f64 / 1000.0) * model.pricing.input_per_1k;
     let output_cost =
-        ((ESTIMATED_OUTPUT_TOKENS + format_tokens) as f64 / 1000.0) * pricing.output_per_1k;
+        ((output_tokens + format_tokens) as f64 / 1000.0) * model.pricing.output_per_1k;
 
     input_cost + output_cost
 }
@@ -310,6 +429,9 @@ fn get_output_path(format: &str, config: &MergedConfig) -> String {
     if let Some(ref output) = config.output {
         return output.display().to_string();
     }
+    if let Some(path) = config.output_paths.get(format) {
+        return path.clone();
+    }
 
     // Get default path from formatter
     if let Ok(formatter) = get_formatter(format) {
@@ -331,13 +453,20 @@ fn get_output_path(format: &str, config: &MergedConfig) -> String {
 mod tests {
     use super::*;
     use crate::cli::config::ProvidersConfig;
+    use crate::llm::provider::Pricing;
+    use crate::llm::tokenizer::TokenizerModel;
     use crate::packer::{CodebaseMetadata, CompressedFile, CompressionMethod};
     use std::path::PathBuf;
 
-    fn create_test_pricing() -> Pricing {
-        Pricing {
-            input_per_1k: 0.003,
-            output_per_1k: 0.015,
+    fn create_test_model() -> ModelInfo {
+        ModelInfo {
+            context_window: 200_000,
+            max_output_tokens: 64_000,
+            pricing: Pricing {
+                input_per_1k: 0.003,
+                output_per_1k: 0.015,
+            },
+            tokenizer: TokenizerModel::Claude,
         }
     }
 
@@ -347,21 +476,39 @@ fn create_test_config() -> MergedConfig {
             model: None,
             format: vec!["cursor".to_string(), "claude".to_string()],
             output: None,
+            output_dir: None,
+            git_ref: None,
+            depth: 1,
             repomix_file: None,
             path: PathBuf::from("."),
             description: None,
             rule_type: crate::generator::rules::RuleType::Auto,
             include: vec![],
             exclude: vec![],
-            compress: true,
+            compress_level: crate::packer::CompressionLevel::Signatures,
             chunk_size: 100_000,
+            token_budget: None,
+            full_coverage: false,
+            workspaces: false,
             no_confirm: false,
             dry_run: true,
+            token_report: false,
+            token_report_json: None,
+            resume: false,
+            incremental: false,
+            check: false,
+            command: None,
             verbose: 0,
             quiet: false,
             output_paths: std::collections::HashMap::new(),
             chunking: None,
             providers: ProvidersConfig::default(),
+            models: std::collections::HashMap::new(),
+            llm: crate::cli::config::LlmConfig::default(),
+            cache: crate::cli::config::CacheConfig::default(),
+            compression: crate::cli::config::CompressionConfig::default(),
+            scan: crate::cli::config::ScanConfig::default(),
+            secrets: crate::cli::config::SecretsConfig::default(),
             validation: crate::cli::config::ValidationConfig::default(),
             finalization: crate::cli::config::FinalizationConfig::default(),
             on_conflict: "prompt".to_string(),
@@ -392,7 +539,7 @@ fn create_test_codebase() -> CompressedCodebase {
                 path: PathBuf::from("src/utils.py"),
                 original_content: "c".repeat(600),
                 compressed_content: "c".repeat(400),
-                compression_method: CompressionMethod::TreeSitter,
+                compression_method: CompressionMethod::Truncated,
                 original_size: 600,
                 compressed_size: 400,
                 language: Some(Language::Python),
@@ -426,14 +573,24 @@ fn test_estimate_tokens() {
 
     #[test]
     fn test_estimate_cost() {
-        let pricing = create_test_pricing();
-        let cost = estimate_cost(50000, 3, &pricing);
+        let model = create_test_model();
+        let cost = estimate_cost(50000, 3, &model);
 
         // Should be input + output cost
         assert!(cost > 0.0);
         assert!(cost < 1.0); // Reasonable for 50k tokens
     }
 
+    #[test]
+    fn test_estimate_cost_capped_by_output_limit() {
+        let mut model = create_test_model();
+        let uncapped = estimate_cost(50000, 0, &model);
+        model.max_output_tokens = 1024;
+        let capped = estimate_cost(50000, 0, &model);
+
+        assert!(capped < uncapped);
+    }
+
     #[test]
     fn test_get_output_path_default() {
         let config = create_test_config();
@@ -467,6 +624,14 @@ fn test_group_files_by_language() {
         assert!(grouped.contains_key(&Language::Python));
         assert_eq!(grouped.get(&Language::TypeScript).unwrap().len(), 2);
         assert_eq!(grouped.get(&Language::Python).unwrap().len(), 1);
+        assert_eq!(
+            grouped.get(&Language::Python).unwrap()[0].level,
+            CompressionLevel::Truncate
+        );
+        assert_eq!(
+            grouped.get(&Language::TypeScript).unwrap()[0].level,
+            CompressionLevel::Signatures
+        );
     }
 
     #[test]
@@ -485,10 +650,43 @@ fn test_display_dry_run_summary() {
         let codebase = create_test_codebase();
         let formats = vec!["cursor".to_string(), "claude".to_string()];
         let config = create_test_config();
-        let pricing = create_test_pricing();
+        let model = create_test_model();
 
         // Should not error
-        let result = display_dry_run_summary(&codebase, &formats, &config, &pricing);
+        let skipped = vec![SkippedFile {
+            path: PathBuf::from("./yarn.lock"),
+            reason: crate::packer::SkipReason::Generated("yarn.lock".to_string()),
+        }];
+        let secrets = vec![SecretFinding {
+            path: PathBuf::from("./.env"),
+            line: 3,
+            pattern: "aws-access-key-id".to_string(),
+        }];
+        let selection = crate::llm::selection::FileSelection {
+            budget: 1_000,
+            included: vec![RankedFile {
+                path: PathBuf::from("src/index.ts"),
+                tokens: 120,
+                score: 120,
+                reasons: vec![crate::llm::selection::SelectionReason::EntryPoint],
+            }],
+            dropped: vec![RankedFile {
+                path: PathBuf::from("src/big.ts"),
+                tokens: 2_000,
+                score: 0,
+                reasons: Vec::new(),
+            }],
+        };
+
+        let result = display_dry_run_summary(
+            &codebase,
+            &skipped,
+            &secrets,
+            Some(&selection),
+            &formats,
+            &config,
+            &model,
+        );
         assert!(result.is_ok());
     }
 }
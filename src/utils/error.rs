@@ -89,8 +89,24 @@ pub enum RuleyError {
 
     #[error("State error: {0}")]
     State(String),
+
+    #[error("Secrets detected in {} location(s)", locations.len())]
+    SecretsDetected {
+        /// `path:line (pattern)` for each finding
+        locations: Vec<String>,
+    },
+
+    #[error("Rules are out of date: {}", reasons.join("; "))]
+    StaleRules {
+        /// Why the rules no longer match the repository
+        reasons: Vec<String>,
+    },
 }
 
+/// Process exit code for [`RuleyError::StaleRules`], distinct from the
+/// generic failure code 1 and clap's usage error code 2.
+pub const EXIT_STALE: i32 = 3;
+
 /// Redact sensitive information from error messages.
 fn redact_sensitive_data(message: &str) -> String {
     let mut result = message.to_string();
@@ -101,6 +117,14 @@ fn redact_sensitive_data(message: &str) -> String {
 }
 
 impl RuleyError {
+    /// Process exit code for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RuleyError::StaleRules { .. } => EXIT_STALE,
+            _ => 1,
+        }
+    }
+
     pub fn invalid_format(format: &str) -> Self {
         RuleyError::ValidationError {
             message: format!("Invalid output format: '{}'", format),
@@ -241,6 +265,9 @@ pub fn format_error(error: &RuleyError, verbose: bool) -> String {
     output
 }
 
+/// Maximum secret locations listed in a [`RuleyError::SecretsDetected`] report.
+const MAX_SECRET_LOCATIONS: usize = 10;
+
 /// Get a short title for the error type.
 fn error_title(error: &RuleyError) -> &'static str {
     match error {
@@ -257,6 +284,8 @@ fn error_title(error: &RuleyError) -> &'static str {
         RuleyError::NetworkError { .. } => "Network error",
         RuleyError::Cache(_) => "Cache error",
         RuleyError::State(_) => "State error",
+        RuleyError::SecretsDetected { .. } => "Secrets detected",
+        RuleyError::StaleRules { .. } => "Rules out of date",
     }
 }
 
@@ -349,6 +378,22 @@ fn error_context_lines(error: &RuleyError) -> Vec<String> {
                 format!("Error: {}", msg),
             ]
         }
+        RuleyError::SecretsDetected { locations } => {
+            let mut lines = vec!["Stage: Scanning file contents for secrets".to_string()];
+            lines.extend(locations.iter().take(MAX_SECRET_LOCATIONS).cloned());
+            if locations.len() > MAX_SECRET_LOCATIONS {
+                lines.push(format!(
+                    "... and {} more",
+                    locations.len() - MAX_SECRET_LOCATIONS
+                ));
+            }
+            lines
+        }
+        RuleyError::StaleRules { reasons } => {
+            let mut lines = vec!["Stage: Checking rules against .ruley/state.json".to_string()];
+            lines.extend(reasons.iter().cloned());
+            lines
+        }
     }
 }
 
@@ -502,6 +547,19 @@ fn error_suggestions(error: &RuleyError) -> Vec<String> {
                 vec!["Delete .ruley/state.json and try again".to_string()]
             }
         }
+        RuleyError::SecretsDetected { .. } => {
+            vec![
+                "Remove the secrets, or leave the files out with --exclude".to_string(),
+                "Or run without --fail-on-secrets to send them redacted".to_string(),
+                "Use --dry-run to list every finding".to_string(),
+            ]
+        }
+        RuleyError::StaleRules { .. } => {
+            vec![
+                "Regenerate the rules with ruley, or ruley --incremental".to_string(),
+                "Commit the updated rule files together with .ruley/state.json".to_string(),
+            ]
+        }
     }
 }
 
@@ -749,6 +807,35 @@ fn test_format_error_cache_corrupt() {
         assert!(formatted.contains("rm -rf .ruley"));
     }
 
+    #[test]
+    fn test_format_error_secrets_detected() {
+        let locations: Vec<String> = (1..=12)
+            .map(|line| format!(".env:{} (generic-secret)", line))
+            .collect();
+        let err = RuleyError::SecretsDetected { locations };
+        let formatted = format_error(&err, false);
+
+        assert!(formatted.contains("Secrets detected"));
+        assert!(formatted.contains(".env:10 (generic-secret)"));
+        assert!(!formatted.contains(".env:11 (generic-secret)"));
+        assert!(formatted.contains("... and 2 more"));
+        assert!(formatted.contains("--fail-on-secrets"));
+    }
+
+    #[test]
+    fn test_format_error_stale_rules() {
+        let err = RuleyError::StaleRules {
+            reasons: vec!["CLAUDE.md was edited by hand".to_string()],
+        };
+        let formatted = format_error(&err, false);
+
+        assert!(formatted.contains("Rules out of date"));
+        assert!(formatted.contains("CLAUDE.md was edited by hand"));
+        assert!(formatted.contains("--incremental"));
+        assert_eq!(err.exit_code(), EXIT_STALE);
+        assert_eq!(RuleyError::Cache("x".to_string()).exit_code(), 1);
+    }
+
     #[test]
     fn test_format_error_state_version() {
         let err = RuleyError::State("version mismatch during migration".to_string());
@@ -5,7 +5,7 @@
 //!
 //! Handles:
 //! - Post-processing (normalize line endings, trim whitespace, ensure trailing newline)
-//! - Metadata injection (timestamp, version, provider, cost, token counts)
+//! - Metadata injection (timestamp, version, provider, cost, token counts, content hash)
 //! - LLM-based deconfliction with existing rule files in the project
 
 use crate::cli::config::FinalizationConfig;
@@ -14,6 +14,7 @@
 use crate::llm::cost::{CostCalculator, CostTracker};
 use crate::llm::provider::{CompletionOptions, Message};
 use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -52,6 +53,20 @@ pub struct FinalizationResult {
     pub deconflicted: bool,
 }
 
+/// Start of the metadata header comment written by [`inject_metadata`].
+pub const METADATA_HEADER_PREFIX: &str = "<!-- Generated by ruley";
+
+/// Whether a rule file still matches the content hash in its metadata header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderStatus {
+    /// The content below the header is unchanged since generation
+    Intact,
+    /// The content below the header was edited after generation
+    Edited,
+    /// The file has no metadata header with a content hash
+    Missing,
+}
+
 /// Known rule file names for simple file-exists checks.
 const KNOWN_RULE_FILE_NAMES: &[&str] = &[
     "CLAUDE.md",
@@ -199,8 +214,16 @@ fn inject_metadata(rules: &mut GeneratedRules) {
             }
 
             let metadata_comment = format!(
-                "<!-- Generated by ruley v{} | {} | {}/{} | tokens: {}/{} | cost: ${:.4} -->",
-                version, timestamp, provider, model, input_tokens, output_tokens, cost
+                "{} v{} | {} | {}/{} | tokens: {}/{} | cost: ${:.4} | sha256: {} -->",
+                METADATA_HEADER_PREFIX,
+                version,
+                timestamp,
+                provider,
+                model,
+                input_tokens,
+                output_tokens,
+                cost,
+                content_hash(&formatted.content)
             );
 
             let content_with_metadata = format!("{}\n{}", metadata_comment, formatted.content);
@@ -215,8 +238,57 @@ fn inject_metadata(rules: &mut GeneratedRules) {
     }
 }
 
+/// Short SHA-256 of rule content, as recorded in the metadata header.
+///
+/// Line endings are normalized first, so a checkout that converts them does
+/// not count as an edit.
+pub fn content_hash(content: &str) -> String {
+    let digest = Sha256::digest(content.replace("\r\n", "\n").as_bytes());
+    format!("{digest:x}")[..16].to_string()
+}
+
+/// Check a written rule file against the content hash in its metadata header.
+pub fn verify_metadata_header(content: &str) -> HeaderStatus {
+    let (header, body) = content.split_once('\n').unwrap_or((content, ""));
+    let recorded = header
+        .trim_end()
+        .strip_prefix(METADATA_HEADER_PREFIX)
+        .and_then(|rest| rest.strip_suffix("-->"))
+        .and_then(|rest| {
+            rest.split(" | ")
+                .find_map(|field| field.strip_prefix("sha256: "))
+        })
+        .map(str::trim);
+
+    match recorded {
+        None => HeaderStatus::Missing,
+        Some(hash) if hash == content_hash(body) => HeaderStatus::Intact,
+        Some(_) => HeaderStatus::Edited,
+    }
+}
+
+/// Format a rule file belongs to, from its path relative to the project.
+///
+/// Returns `None` for paths that are not a known rule file.
+pub fn rule_file_format(path: &str) -> Option<&'static str> {
+    let path = path.replace('\\', "/");
+    match path.as_str() {
+        "CLAUDE.md" => Some("claude"),
+        ".windsurfrules" => Some("windsurf"),
+        "CONVENTIONS.md" | ".aider.conf.yml" => Some("aider"),
+        "AI_RULES.md" => Some("generic"),
+        ".cursorrules" => Some("cursor"),
+        ".github/copilot-instructions.md" => Some("copilot"),
+        p if p.starts_with(".cursor/rules/") && p.ends_with(".mdc") => Some("cursor"),
+        _ => None,
+    }
+}
+
 /// Detect existing rule files in the project that won't be overwritten.
-fn detect_existing_rules(
+///
+/// Returns the content of each rule file keyed by its path relative to
+/// `project_path`. With no formats being generated, every rule file is returned.
+pub fn detect_existing_rules(
     project_path: &Path,
     formats_being_generated: &[String],
 ) -> HashMap<String, String> {
@@ -226,13 +298,8 @@ fn detect_existing_rules(
         let path = project_path.join(filename);
         if path.exists() {
             // Determine what format this file belongs to
-            let format = match *filename {
-                "CLAUDE.md" => "claude",
-                ".windsurfrules" => "windsurf",
-                "CONVENTIONS.md" | ".aider.conf.yml" => "aider",
-                "AI_RULES.md" => "generic",
-                ".cursorrules" => "cursor",
-                _ => continue,
+            let Some(format) = rule_file_format(filename) else {
+                continue;
             };
 
             // Only include if we're NOT generating this format (existing files we keep)
@@ -409,11 +476,7 @@ async fn deconflict_rules(
 
             // Track cost
             if let Some(tracker) = cost_tracker {
-                tracker.add_operation(
-                    format!("deconfliction_{}", format),
-                    response.prompt_tokens,
-                    response.completion_tokens,
-                );
+                tracker.add_completion(format!("deconfliction_{}", format), &response);
             }
 
             // Update rules with deconflicted content
@@ -514,6 +577,51 @@ fn test_inject_metadata_markdown() {
         assert!(cursor.content.contains("<!-- Generated by ruley"));
         assert!(cursor.content.contains("anthropic/claude-3-opus"));
         assert!(cursor.content.contains("1000/500"));
+        assert!(
+            cursor
+                .content
+                .contains(&format!("sha256: {}", content_hash("# Cursor Rules")))
+        );
+    }
+
+    #[test]
+    fn test_rule_file_format() {
+        assert_eq!(rule_file_format("CLAUDE.md"), Some("claude"));
+        assert_eq!(rule_file_format(".aider.conf.yml"), Some("aider"));
+        assert_eq!(
+            rule_file_format(".github/copilot-instructions.md"),
+            Some("copilot")
+        );
+        assert_eq!(rule_file_format(".cursor/rules/core.mdc"), Some("cursor"));
+        assert_eq!(rule_file_format(".cursor\\rules\\core.mdc"), Some("cursor"));
+        assert_eq!(rule_file_format(".cursor/rules/notes.md"), None);
+        assert_eq!(rule_file_format("README.md"), None);
+    }
+
+    #[test]
+    fn test_verify_metadata_header() {
+        let mut rules = GeneratedRules::new("analysis");
+        rules.add_format(FormattedRules::new("claude", "# Rules\n\nUse spaces.\n"));
+        inject_metadata(&mut rules);
+        let written = rules.get_format("claude").unwrap().content.clone();
+
+        assert_eq!(verify_metadata_header(&written), HeaderStatus::Intact);
+        assert_eq!(
+            verify_metadata_header(&written.replace("\n", "\r\n")),
+            HeaderStatus::Intact
+        );
+        assert_eq!(
+            verify_metadata_header(&written.replace("spaces", "tabs")),
+            HeaderStatus::Edited
+        );
+        assert_eq!(
+            verify_metadata_header("# Rules\n\nUse spaces.\n"),
+            HeaderStatus::Missing
+        );
+
+        // Headers written before content hashes were recorded
+        let old = "<!-- Generated by ruley v0.1.0 | 2024-01-01T00:00:00Z | anthropic/claude-3-opus | tokens: 1000/500 | cost: $0.0500 -->\n# Rules\n";
+        assert_eq!(verify_metadata_header(old), HeaderStatus::Missing);
     }
 
     #[test]
@@ -0,0 +1,252 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Change detection for `--incremental` runs.
+//!
+//! Every run records a SHA-256 of each analyzed file in `.ruley/state.json`.
+//! An incremental run hashes the current files, compares them with the
+//! recorded hashes and analyzes only the files that were added or modified,
+//! so uncommitted edits are picked up as well as new commits.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ruley::utils::incremental::{diff_hashes, hash_files};
+//!
+//! let hashes = hash_files(&compressed_codebase, &repo_path);
+//! let changes = diff_hashes(&state.file_hashes, &hashes);
+//! let changed = changes.apply(compressed_codebase, &repo_path);
+//! ```
+
+use crate::packer::CompressedCodebase;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Files that differ between two runs, as paths relative to the repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileChanges {
+    /// Files that did not exist in the previous run
+    pub added: Vec<PathBuf>,
+    /// Files whose content changed
+    pub modified: Vec<PathBuf>,
+    /// Files that no longer exist
+    pub removed: Vec<PathBuf>,
+}
+
+impl FileChanges {
+    /// Whether no file was added, modified or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+
+    /// Keep only the added and modified files of `codebase`.
+    pub fn apply(&self, codebase: CompressedCodebase, root: &Path) -> CompressedCodebase {
+        let changed: HashSet<&Path> = self
+            .added
+            .iter()
+            .chain(&self.modified)
+            .map(PathBuf::as_path)
+            .collect();
+        let files = codebase
+            .files
+            .into_iter()
+            .filter(|f| changed.contains(relative_path(&f.path, root)))
+            .collect();
+        CompressedCodebase::new(files)
+    }
+}
+
+impl fmt::Display for FileChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} modified, {} added, {} removed",
+            self.modified.len(),
+            self.added.len(),
+            self.removed.len()
+        )
+    }
+}
+
+/// SHA-256 of each file's original content, keyed by path relative to `root`.
+pub fn hash_files(codebase: &CompressedCodebase, root: &Path) -> BTreeMap<PathBuf, String> {
+    codebase
+        .files
+        .iter()
+        .map(|file| {
+            let hash = Sha256::digest(file.original_content.as_bytes());
+            (
+                relative_path(&file.path, root).to_path_buf(),
+                format!("{hash:x}"),
+            )
+        })
+        .collect()
+}
+
+/// Compare the hashes recorded by the previous run with the current ones.
+pub fn diff_hashes(
+    previous: &BTreeMap<PathBuf, String>,
+    current: &BTreeMap<PathBuf, String>,
+) -> FileChanges {
+    let mut changes = FileChanges::default();
+    for (path, hash) in current {
+        match previous.get(path) {
+            None => changes.added.push(path.clone()),
+            Some(old) if old != hash => changes.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    changes.removed = previous
+        .keys()
+        .filter(|path| !current.contains_key(*path))
+        .cloned()
+        .collect();
+    changes
+}
+
+/// Drop the rule files written by a run, and their `.bak` backups, from
+/// `hashes`.
+///
+/// Rule files are rewritten by every run, so comparing them as sources would
+/// report changes right after each regeneration.
+pub fn source_hashes(
+    hashes: &BTreeMap<PathBuf, String>,
+    outputs: &[PathBuf],
+) -> BTreeMap<PathBuf, String> {
+    hashes
+        .iter()
+        .filter(|(path, _)| !is_output_or_backup(path, outputs))
+        .map(|(path, hash)| (path.clone(), hash.clone()))
+        .collect()
+}
+
+/// Whether `path` is one of `outputs`, or a backup of one written next to it
+/// as `<name>.bak` or `<name>.<timestamp>.bak`.
+fn is_output_or_backup(path: &Path, outputs: &[PathBuf]) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    outputs.iter().any(|output| {
+        path == output
+            || (name.ends_with(".bak")
+                && path.parent() == output.parent()
+                && output
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|output_name| name.strip_prefix(output_name))
+                    .is_some_and(|rest| rest.starts_with('.')))
+    })
+}
+
+fn relative_path<'a>(path: &'a Path, root: &Path) -> &'a Path {
+    path.strip_prefix(root).unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packer::{CompressedFile, CompressionMethod};
+
+    fn file(path: &str, content: &str) -> CompressedFile {
+        CompressedFile {
+            path: PathBuf::from(path),
+            original_content: content.to_string(),
+            compressed_content: content.to_string(),
+            compression_method: CompressionMethod::None,
+            original_size: content.len(),
+            compressed_size: content.len(),
+            language: None,
+        }
+    }
+
+    fn codebase() -> CompressedCodebase {
+        CompressedCodebase::new(vec![
+            file("/repo/src/main.rs", "fn main() {}"),
+            file("/repo/src/lib.rs", "pub mod api;"),
+            file("/repo/README.md", "# Demo"),
+        ])
+    }
+
+    #[test]
+    fn test_hash_files() {
+        let hashes = hash_files(&codebase(), Path::new("/repo"));
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(
+            hashes[Path::new("README.md")],
+            format!("{:x}", Sha256::digest(b"# Demo"))
+        );
+        assert_ne!(
+            hashes[Path::new("src/main.rs")],
+            hashes[Path::new("src/lib.rs")]
+        );
+    }
+
+    #[test]
+    fn test_diff_hashes() {
+        let current = hash_files(&codebase(), Path::new("/repo"));
+        assert!(diff_hashes(&current, &current).is_empty());
+
+        let mut previous = current.clone();
+        previous.remove(Path::new("README.md"));
+        previous.insert(PathBuf::from("src/lib.rs"), "stale".to_string());
+        previous.insert(PathBuf::from("src/old.rs"), "gone".to_string());
+
+        let changes = diff_hashes(&previous, &current);
+        assert_eq!(changes.added, vec![PathBuf::from("README.md")]);
+        assert_eq!(changes.modified, vec![PathBuf::from("src/lib.rs")]);
+        assert_eq!(changes.removed, vec![PathBuf::from("src/old.rs")]);
+        assert_eq!(changes.to_string(), "1 modified, 1 added, 1 removed");
+    }
+
+    #[test]
+    fn test_apply_keeps_changed_files() {
+        let changes = FileChanges {
+            added: vec![PathBuf::from("README.md")],
+            modified: vec![PathBuf::from("src/lib.rs")],
+            removed: vec![PathBuf::from("src/old.rs")],
+        };
+
+        let changed = changes.apply(codebase(), Path::new("/repo"));
+        let paths: Vec<_> = changed.files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/repo/src/lib.rs"),
+                PathBuf::from("/repo/README.md")
+            ]
+        );
+        assert_eq!(changed.metadata.total_files, 2);
+    }
+
+    #[test]
+    fn test_source_hashes_drops_outputs_and_backups() {
+        let hashes: BTreeMap<PathBuf, String> = [
+            "CLAUDE.md",
+            "CLAUDE.md.bak",
+            "CLAUDE.md.20260129_120000.bak",
+            ".cursor/rules/style.mdc.bak",
+            "docs/CLAUDE.md.bak",
+            "CLAUDE.mdx.bak",
+            "src/main.rs",
+        ]
+        .into_iter()
+        .map(|path| (PathBuf::from(path), "hash".to_string()))
+        .collect();
+        let outputs = [
+            PathBuf::from("CLAUDE.md"),
+            PathBuf::from(".cursor/rules/style.mdc"),
+        ];
+
+        let sources: Vec<_> = source_hashes(&hashes, &outputs).into_keys().collect();
+        assert_eq!(
+            sources,
+            vec![
+                PathBuf::from("CLAUDE.mdx.bak"),
+                PathBuf::from("docs/CLAUDE.md.bak"),
+                PathBuf::from("src/main.rs"),
+            ]
+        );
+    }
+}
@@ -3,11 +3,16 @@
 
 pub mod cache;
 pub mod cost_display;
+pub mod drift;
 pub mod dry_run;
 pub mod error;
 pub mod finalization;
 pub mod formatting;
+pub mod incremental;
 pub mod progress;
+pub mod sarif;
+pub mod staleness;
 pub mod state;
 pub mod summary;
+pub mod token_report;
 pub mod validation;
@@ -5,6 +5,12 @@
 
 use console::Term;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::sync::Arc;
+
+use crate::llm::client::TokenProgress;
+
+/// Callback receiving `(completed, total)` task counts for a stage.
+pub type TaskProgress = Arc<dyn Fn(usize, usize) + Send + Sync>;
 
 /// Stage name constants for consistent progress tracking.
 pub mod stages {
@@ -105,7 +111,7 @@ pub fn new() -> Self {
     /// # Arguments
     ///
     /// * `name` - The stage name (use constants from [`stages`] module).
-    /// * `total` - The total number of items to process (ignored for spinner stages).
+    /// * `total` - The total number of items to process (chunks, for the analyzing spinner).
     ///
     /// # Returns
     ///
@@ -115,8 +121,11 @@ pub fn new() -> Self {
     ///
     /// - **scanning**: `"[{bar:40.cyan/blue}] {pos}/{len} Scanning files... {msg}"`
     /// - **compressing**: `"[{bar:40.cyan/blue}] {pos}/{len} Compressing... ({msg})"`
-    /// - **analyzing**: `"{spinner:.green} Analyzing... {msg}"` (spinner, no progress)
-    /// - **formatting**: `"[{bar:40.cyan/blue}] {pos}/{len} Generating {msg} format"`
+    /// - **analyzing**: `"{spinner:.green} Analyzing... {pos}/{len} chunks {msg} {prefix}"` (spinner)
+    /// - **formatting**: `"[{bar:40.cyan/blue}] {pos}/{len} Generating formats... {msg} {prefix}"`
+    ///
+    /// The `{prefix}` slot of the analyzing and formatting stages shows live token
+    /// counts reported through [`token_progress`](Self::token_progress).
     /// - **validating**: `"[{bar:40.cyan/blue}] {pos}/{len} Validating... {msg}"`
     /// - **finalizing**: `"[{bar:40.cyan/blue}] {pos}/{len} Finalizing... {msg}"`
     /// - **writing**: `"[{bar:40.cyan/blue}] {pos}/{len} Writing files... {msg}"`
@@ -128,6 +137,7 @@ pub fn add_stage(&mut self, name: &str, total: u64) -> ProgressBar {
             if name == stages::ANALYZING {
                 // Use a spinner for the analyzing stage (indeterminate progress)
                 let spinner = ProgressBar::new_spinner();
+                spinner.set_length(total);
                 spinner.enable_steady_tick(std::time::Duration::from_millis(100));
                 self.multi.add(spinner)
             } else {
@@ -210,13 +220,52 @@ pub fn abandon(&self, stage: &str) {
         }
     }
 
+    /// Returns a callback that shows received-token counts on a stage's bar.
+    ///
+    /// Pass the result to [`LLMClient::set_token_progress`](crate::llm::client::LLMClient::set_token_progress)
+    /// to display streamed tokens as they arrive.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if the stage doesn't exist.
+    #[must_use]
+    pub fn token_progress(&self, stage: &str) -> Option<TokenProgress> {
+        let pb = self.bars.get(stage)?.clone();
+        Some(Arc::new(move |tokens: usize| {
+            if tokens == 0 {
+                pb.set_prefix("");
+            } else {
+                pb.set_prefix(format!("(~{} tokens received)", tokens));
+            }
+        }))
+    }
+
+    /// Returns a callback that moves a stage's bar as concurrent tasks complete.
+    ///
+    /// Tasks may finish in any order, so the callback takes the completed count
+    /// rather than a task index.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if the stage doesn't exist.
+    #[must_use]
+    pub fn task_progress(&self, stage: &str) -> Option<TaskProgress> {
+        let pb = self.bars.get(stage)?.clone();
+        Some(Arc::new(move |completed: usize, total: usize| {
+            pb.set_length(total as u64);
+            pb.set_position(completed as u64);
+        }))
+    }
+
     /// Returns the progress style for a given stage name.
     fn style_for_stage(name: &str) -> ProgressStyle {
         let template = match name {
             stages::SCANNING => "[{bar:40.cyan/blue}] {pos}/{len} Scanning files... {msg}",
             stages::COMPRESSING => "[{bar:40.cyan/blue}] {pos}/{len} Compressing... ({msg})",
-            stages::ANALYZING => "{spinner:.green} Analyzing... {msg}",
-            stages::FORMATTING => "[{bar:40.cyan/blue}] {pos}/{len} Generating {msg} format",
+            stages::ANALYZING => "{spinner:.green} Analyzing... {pos}/{len} chunks {msg} {prefix}",
+            stages::FORMATTING => {
+                "[{bar:40.cyan/blue}] {pos}/{len} Generating formats... {msg} {prefix}"
+            }
             stages::VALIDATING => "[{bar:40.cyan/blue}] {pos}/{len} Validating... {msg}",
             stages::FINALIZING => "[{bar:40.cyan/blue}] {pos}/{len} Finalizing... {msg}",
             stages::WRITING => "[{bar:40.cyan/blue}] {pos}/{len} Writing files... {msg}",
@@ -329,4 +378,36 @@ fn test_progress_manager_default() {
         let manager = ProgressManager::default();
         assert!(manager.bars.is_empty());
     }
+
+    #[test]
+    fn test_token_progress_updates_prefix() {
+        let mut manager = ProgressManager::new();
+        let pb = manager.add_stage(stages::ANALYZING, 0);
+
+        let progress = manager
+            .token_progress(stages::ANALYZING)
+            .expect("stage should exist");
+        progress(42);
+        assert_eq!(pb.prefix(), "(~42 tokens received)");
+
+        progress(0);
+        assert_eq!(pb.prefix(), "");
+
+        assert!(manager.token_progress("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_task_progress_sets_position_and_length() {
+        let mut manager = ProgressManager::new();
+        let pb = manager.add_stage(stages::FORMATTING, 0);
+
+        let progress = manager
+            .task_progress(stages::FORMATTING)
+            .expect("stage should exist");
+        progress(2, 5);
+        assert_eq!(pb.position(), 2);
+        assert_eq!(pb.length(), Some(5));
+
+        assert!(manager.task_progress("nonexistent").is_none());
+    }
 }
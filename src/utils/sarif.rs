@@ -0,0 +1,344 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! SARIF 2.1.0 export of validation results.
+//!
+//! Each validation layer is a SARIF rule (`syntax`, `schema`, `semantic`).
+//! Errors become `error` results and warnings `warning` results, located by
+//! rule file and line where the validator determined them, so GitHub code
+//! scanning and IDEs can show rule-file problems inline. Code scanning drops
+//! results without a location, so issues spanning several rule files point at
+//! the first file involved.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ruley::utils::sarif::write_validation_sarif;
+//!
+//! write_validation_sarif(&results, Path::new("ruley.sarif"))?;
+//! ```
+
+use crate::utils::validation::{
+    ValidationIssue, ValidationLayer, ValidationReport, ValidationResult,
+};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// SARIF version written by [`build_sarif_log`].
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// JSON schema of SARIF 2.1.0.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Validation layers in rule index order.
+const LAYERS: [ValidationLayer; 3] = [
+    ValidationLayer::Syntax,
+    ValidationLayer::Schema,
+    ValidationLayer::Semantic,
+];
+
+/// Top-level SARIF log.
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub name: String,
+    pub short_description: SarifMessage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    /// `error` or `warning`
+    pub level: &'static str,
+    pub message: SarifMessage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    /// Path relative to the repository root
+    pub uri: String,
+    pub uri_base_id: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+}
+
+/// Build a SARIF log with one run holding every error and warning.
+///
+/// Issues without a rule file of their own, such as cross-format conflicts,
+/// are located at the first rule file their message names, or else the first
+/// rule file validated, without a line. They have no location only when no
+/// result came from a rule file.
+pub fn build_sarif_log(results: &[ValidationResult]) -> SarifLog {
+    let report = ValidationReport::from_results(results);
+    let files: Vec<&str> = report
+        .results
+        .iter()
+        .filter_map(|r| r.file.as_deref())
+        .collect();
+    let mut sarif_results = Vec::new();
+    for result in &report.results {
+        for (issues, level) in [(&result.errors, "error"), (&result.warnings, "warning")] {
+            sarif_results.extend(
+                issues
+                    .iter()
+                    .map(|issue| sarif_result(issue, level, &files)),
+            );
+        }
+    }
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "ruley",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: env!("CARGO_PKG_REPOSITORY"),
+                    rules: LAYERS
+                        .iter()
+                        .map(|layer| SarifRule {
+                            id: rule_id(*layer),
+                            name: format!("{}Validation", layer),
+                            short_description: SarifMessage {
+                                text: format!("{} validation of AI IDE rule files", layer),
+                            },
+                        })
+                        .collect(),
+                },
+            },
+            results: sarif_results,
+        }],
+    }
+}
+
+/// Write the validation results to `path` as a SARIF 2.1.0 log.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_validation_sarif(results: &[ValidationResult], path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(&build_sarif_log(results))
+        .context("Failed to serialize SARIF log")?;
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write SARIF log to {}", path.display()))
+}
+
+/// Convert one issue, falling back to a file-level location in one of
+/// `files` when the issue has no rule file.
+fn sarif_result(issue: &ValidationIssue, level: &'static str, files: &[&str]) -> SarifResult {
+    let text = match issue.suggestion {
+        Some(ref suggestion) => format!("{}\nSuggestion: {}", issue.message, suggestion),
+        None => issue.message.clone(),
+    };
+    let located = issue
+        .location
+        .as_ref()
+        .and_then(|location| Some((location.file.as_deref()?, location.line)));
+    let involved = || {
+        files
+            .iter()
+            .find(|file| issue.message.contains(**file))
+            .or(files.first())
+            .map(|file| (*file, None))
+    };
+    let locations = located
+        .or_else(involved)
+        .map(|(file, line)| SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: file.replace('\\', "/"),
+                    uri_base_id: "%SRCROOT%",
+                },
+                region: line.map(|start_line| SarifRegion { start_line }),
+            },
+        })
+        .into_iter()
+        .collect();
+
+    SarifResult {
+        rule_id: rule_id(issue.layer),
+        rule_index: LAYERS
+            .iter()
+            .position(|l| *l == issue.layer)
+            .unwrap_or_default(),
+        level,
+        message: SarifMessage { text },
+        locations,
+    }
+}
+
+fn rule_id(layer: ValidationLayer) -> String {
+    layer.to_string().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::validation::{ValidationError, ValidationWarning};
+
+    fn results() -> Vec<ValidationResult> {
+        vec![
+            ValidationResult {
+                format: "cursor".to_string(),
+                passed: false,
+                errors: vec![ValidationError {
+                    layer: ValidationLayer::Syntax,
+                    message: "Unclosed YAML frontmatter (missing closing ---)".to_string(),
+                    location: Some("line 1".to_string()),
+                    suggestion: Some("Add closing --- after frontmatter".to_string()),
+                }],
+                warnings: vec![ValidationWarning {
+                    layer: ValidationLayer::Semantic,
+                    message: "File path \"src/old.rs\" not found in codebase".to_string(),
+                    location: None,
+                    suggestion: None,
+                }],
+                file: Some(".cursor\\rules\\style.mdc".to_string()),
+            },
+            ValidationResult {
+                format: "cross-format".to_string(),
+                passed: false,
+                errors: vec![ValidationError {
+                    layer: ValidationLayer::Semantic,
+                    message: "Cross-format conflict for indentation style".to_string(),
+                    location: None,
+                    suggestion: None,
+                }],
+                warnings: Vec::new(),
+                file: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_build_sarif_log() {
+        let log = serde_json::to_value(build_sarif_log(&results())).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["$schema"], SARIF_SCHEMA);
+
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "ruley");
+        assert_eq!(run["tool"]["driver"]["rules"][2]["id"], "semantic");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "syntax");
+        assert_eq!(results[0]["ruleIndex"], 0);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["message"]["text"],
+            "Unclosed YAML frontmatter (missing closing ---)\nSuggestion: Add closing --- after frontmatter"
+        );
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(
+            location["artifactLocation"]["uri"],
+            ".cursor/rules/style.mdc"
+        );
+        assert_eq!(location["region"]["startLine"], 1);
+
+        // Known file, unknown line
+        assert_eq!(results[1]["level"], "warning");
+        assert!(results[1]["locations"][0]["physicalLocation"]["region"].is_null());
+
+        // No rule file of its own: located at the first rule file, without a line
+        assert_eq!(results[2]["ruleIndex"], 2);
+        let location = &results[2]["locations"][0]["physicalLocation"];
+        assert_eq!(
+            location["artifactLocation"]["uri"],
+            ".cursor/rules/style.mdc"
+        );
+        assert!(location["region"].is_null());
+    }
+
+    #[test]
+    fn test_cross_file_issue_located_at_named_file() {
+        let mut results = results();
+        results.insert(
+            1,
+            ValidationResult {
+                format: "claude".to_string(),
+                passed: true,
+                errors: Vec::new(),
+                warnings: Vec::new(),
+                file: Some("CLAUDE.md".to_string()),
+            },
+        );
+        results[2].errors[0].message =
+            "Cross-format conflict for indentation style: tabs in [CLAUDE.md] vs spaces in [.cursor/rules/style.mdc]"
+                .to_string();
+
+        let log = serde_json::to_value(build_sarif_log(&results)).unwrap();
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "CLAUDE.md"
+        );
+    }
+
+    #[test]
+    fn test_no_rule_files_no_location() {
+        let results: Vec<ValidationResult> = results().split_off(1);
+        let log = serde_json::to_value(build_sarif_log(&results)).unwrap();
+        assert!(log["runs"][0]["results"][0].get("locations").is_none());
+    }
+}
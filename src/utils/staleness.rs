@@ -0,0 +1,474 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Staleness check for `--check`.
+//!
+//! Compares the repository with what `.ruley/state.json` recorded when the
+//! rules were generated: the content hash of every source file, a fingerprint
+//! of the generation settings, and the written rule files, which must still
+//! exist and match the content hash in their metadata header. Needs no LLM
+//! and no API key, so it can gate CI.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ruley::utils::staleness::{check_staleness, config_fingerprint, display_staleness_report};
+//!
+//! let report = check_staleness(&state, &hashes, &config_fingerprint(&config), &repo_path);
+//! display_staleness_report(&report)?;
+//! ```
+
+use crate::MergedConfig;
+use crate::utils::finalization::{HeaderStatus, verify_metadata_header};
+use crate::utils::incremental::{FileChanges, diff_hashes, source_hashes};
+use crate::utils::state::State;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use console::{Term, style};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+/// State of a rule file written by the last generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputState {
+    /// Sources and settings are unchanged and the file is as generated
+    UpToDate,
+    /// Sources or settings changed since the file was generated
+    Stale,
+    /// The file was edited after generation
+    HandEdited,
+    /// The file no longer exists
+    Missing,
+}
+
+impl std::fmt::Display for OutputState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Self::UpToDate => "up to date",
+            Self::Stale => "stale",
+            Self::HandEdited => "edited by hand",
+            Self::Missing => "missing",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// A rule file and its state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputStatus {
+    /// Path as recorded in the state
+    pub path: PathBuf,
+    pub state: OutputState,
+}
+
+/// A top-level directory whose files changed since generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriftedArea {
+    /// Top-level directory, or `.` for files in the repository root
+    pub path: PathBuf,
+    /// Number of added, modified and removed files below it
+    pub files: usize,
+}
+
+/// Result of comparing the repository with the recorded state.
+#[derive(Debug, Clone)]
+pub struct StalenessReport {
+    /// When the rules were generated
+    pub generated_at: DateTime<Utc>,
+    /// Commit the rules were generated from
+    pub commit: Option<String>,
+    /// Whether the generation settings changed
+    pub config_changed: bool,
+    /// Source files changed since generation
+    pub changes: FileChanges,
+    /// Changed files grouped by top-level directory, most changes first
+    pub areas: Vec<DriftedArea>,
+    /// Every rule file the last generation wrote
+    pub outputs: Vec<OutputStatus>,
+}
+
+impl StalenessReport {
+    /// Whether the rules need regenerating or were edited by hand.
+    pub fn is_stale(&self) -> bool {
+        self.config_changed
+            || !self.changes.is_empty()
+            || self
+                .outputs
+                .iter()
+                .any(|o| o.state != OutputState::UpToDate)
+    }
+
+    /// One line per reason the rules are out of date.
+    pub fn reasons(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.config_changed {
+            reasons.push("Generation settings changed".to_string());
+        }
+        if !self.changes.is_empty() {
+            reasons.push(format!("Source files changed: {}", self.changes));
+        }
+        for output in &self.outputs {
+            if matches!(output.state, OutputState::HandEdited | OutputState::Missing) {
+                reasons.push(format!("{} is {}", output.path.display(), output.state));
+            }
+        }
+        reasons
+    }
+}
+
+/// Fingerprint of the settings that shape the generated rules.
+///
+/// Covers the provider, model, formats, rule type, focus description,
+/// file filters, compression level and token budget.
+pub fn config_fingerprint(config: &MergedConfig) -> String {
+    let mut formats = config.format.clone();
+    formats.sort();
+    let fields = [
+        config.provider.clone(),
+        config.model.clone().unwrap_or_default(),
+        formats.join(","),
+        config.rule_type.slug().to_string(),
+        config.description.clone().unwrap_or_default(),
+        config.include.join(","),
+        config.exclude.join(","),
+        config.compress_level.to_string(),
+        config
+            .token_budget
+            .filter(|_| !config.full_coverage)
+            .map(|b| b.to_string())
+            .unwrap_or_default(),
+    ];
+
+    let mut hasher = Sha256::new();
+    for field in fields {
+        // Length-prefix each field so boundaries can't shift between them
+        hasher.update((field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Compare the repository with the state recorded at generation time.
+///
+/// `hashes` are the current source file hashes and `root` resolves the
+/// relative output paths in the state.
+pub fn check_staleness(
+    state: &State,
+    hashes: &BTreeMap<PathBuf, String>,
+    config_hash: &str,
+    root: &Path,
+) -> StalenessReport {
+    let config_changed = state.config_hash.as_deref() != Some(config_hash);
+    // Rule files written into the repository, and the backups of the files
+    // they replaced, are scanned like any other file; rule files are checked
+    // through their metadata header instead
+    let changes = diff_hashes(
+        &source_hashes(&state.file_hashes, &state.output_files),
+        &source_hashes(hashes, &state.output_files),
+    );
+    let drifted = config_changed || !changes.is_empty();
+
+    let outputs = state
+        .output_files
+        .iter()
+        .map(|path| {
+            let state = match std::fs::read_to_string(root.join(path)) {
+                Err(_) => OutputState::Missing,
+                Ok(content) if verify_metadata_header(&content) == HeaderStatus::Edited => {
+                    OutputState::HandEdited
+                }
+                Ok(_) if drifted => OutputState::Stale,
+                Ok(_) => OutputState::UpToDate,
+            };
+            OutputStatus {
+                path: path.clone(),
+                state,
+            }
+        })
+        .collect();
+
+    StalenessReport {
+        generated_at: state.last_run,
+        commit: state.commit.clone(),
+        config_changed,
+        areas: drifted_areas(&changes),
+        changes,
+        outputs,
+    }
+}
+
+/// Group changed files by their top-level directory.
+fn drifted_areas(changes: &FileChanges) -> Vec<DriftedArea> {
+    let mut counts: HashMap<PathBuf, usize> = HashMap::new();
+    for path in changes
+        .added
+        .iter()
+        .chain(&changes.modified)
+        .chain(&changes.removed)
+    {
+        let mut components = path.components();
+        let area = match (components.next(), components.next()) {
+            (Some(Component::Normal(dir)), Some(_)) => PathBuf::from(dir),
+            _ => PathBuf::from("."),
+        };
+        *counts.entry(area).or_insert(0) += 1;
+    }
+
+    let mut areas: Vec<DriftedArea> = counts
+        .into_iter()
+        .map(|(path, files)| DriftedArea { path, files })
+        .collect();
+    areas.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.path.cmp(&b.path)));
+    areas
+}
+
+/// Print the staleness report.
+pub fn display_staleness_report(report: &StalenessReport) -> Result<()> {
+    let mut term = Term::stdout();
+
+    writeln!(term)?;
+    writeln!(term, "{}", style("Rules Check").bold())?;
+    let commit = report
+        .commit
+        .as_deref()
+        .map(|c| format!(" from commit {}", &c[..c.len().min(7)]))
+        .unwrap_or_default();
+    writeln!(
+        term,
+        "Generated {}{}",
+        report.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        commit
+    )?;
+
+    writeln!(term)?;
+    let settings = if report.config_changed {
+        style("changed").yellow().to_string()
+    } else {
+        "unchanged".to_string()
+    };
+    writeln!(term, "{}: {}", style("Settings").bold(), settings)?;
+    writeln!(
+        term,
+        "{}: {}",
+        style("Source changes").bold(),
+        report.changes
+    )?;
+    for (i, area) in report.areas.iter().enumerate() {
+        let prefix = if i == report.areas.len() - 1 {
+            "\u{2514}\u{2500}"
+        } else {
+            "\u{251c}\u{2500}"
+        };
+        let label = if area.path == Path::new(".") {
+            "(root)".to_string()
+        } else {
+            format!("{}/", area.path.display())
+        };
+        let noun = if area.files == 1 { "file" } else { "files" };
+        writeln!(
+            term,
+            "{} {} ({} {})",
+            style(prefix).dim(),
+            label,
+            area.files,
+            noun
+        )?;
+    }
+
+    writeln!(term)?;
+    writeln!(term, "{}:", style("Rule files").bold())?;
+    for (i, output) in report.outputs.iter().enumerate() {
+        let prefix = if i == report.outputs.len() - 1 {
+            "\u{2514}\u{2500}"
+        } else {
+            "\u{251c}\u{2500}"
+        };
+        let state = match output.state {
+            OutputState::UpToDate => style(output.state.to_string()).green(),
+            _ => style(output.state.to_string()).yellow(),
+        };
+        writeln!(
+            term,
+            "{} {}: {}",
+            style(prefix).dim(),
+            output.path.display(),
+            state
+        )?;
+    }
+
+    writeln!(term)?;
+    if report.is_stale() {
+        writeln!(term, "{}", style("Rules are out of date").yellow().bold())?;
+    } else {
+        writeln!(term, "{}", style("Rules are up to date").green().bold())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::finalization::content_hash;
+
+    fn hashes(entries: &[(&str, &str)]) -> BTreeMap<PathBuf, String> {
+        entries
+            .iter()
+            .map(|(path, hash)| (PathBuf::from(path), hash.to_string()))
+            .collect()
+    }
+
+    fn generated(body: &str) -> String {
+        format!(
+            "<!-- Generated by ruley v1.0.0 | 2026-01-29T12:00:00Z | anthropic/m | tokens: 1/1 | cost: $0.0000 | sha256: {} -->\n{}",
+            content_hash(body),
+            body
+        )
+    }
+
+    #[test]
+    fn test_check_staleness() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("CLAUDE.md"), generated("# Rules\n")).unwrap();
+        std::fs::write(
+            root.join("AI_RULES.md"),
+            generated("# Rules\n").replace("Rules", "Edited"),
+        )
+        .unwrap();
+
+        let state = State {
+            output_files: vec![
+                PathBuf::from("CLAUDE.md"),
+                PathBuf::from("AI_RULES.md"),
+                PathBuf::from("CONVENTIONS.md"),
+            ],
+            file_hashes: hashes(&[("src/main.rs", "a"), ("src/lib.rs", "b"), ("build.rs", "c")]),
+            config_hash: Some("cfg".to_string()),
+            ..Default::default()
+        };
+
+        let current = hashes(&[
+            ("src/main.rs", "a"),
+            ("src/lib.rs", "b2"),
+            ("src/api.rs", "d"),
+            ("CLAUDE.md", "e"),
+        ]);
+        let report = check_staleness(&state, &current, "cfg", root);
+        assert!(report.is_stale());
+        assert!(!report.config_changed);
+        assert_eq!(report.changes.to_string(), "1 modified, 1 added, 1 removed");
+        assert_eq!(
+            report.areas,
+            vec![
+                DriftedArea {
+                    path: PathBuf::from("src"),
+                    files: 2
+                },
+                DriftedArea {
+                    path: PathBuf::from("."),
+                    files: 1
+                },
+            ]
+        );
+        let states: Vec<_> = report.outputs.iter().map(|o| o.state).collect();
+        assert_eq!(
+            states,
+            vec![
+                OutputState::Stale,
+                OutputState::HandEdited,
+                OutputState::Missing
+            ]
+        );
+        assert!(
+            report
+                .reasons()
+                .contains(&"AI_RULES.md is edited by hand".to_string())
+        );
+
+        // Matching sources but different settings
+        let report = check_staleness(&state, &state.file_hashes, "other", root);
+        assert!(report.config_changed);
+        assert!(report.changes.is_empty());
+        assert_eq!(report.reasons()[0], "Generation settings changed");
+    }
+
+    #[test]
+    fn test_check_up_to_date() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("CLAUDE.md"), generated("# Rules\n")).unwrap();
+        let state = State {
+            output_files: vec![PathBuf::from("CLAUDE.md")],
+            file_hashes: hashes(&[("src/main.rs", "a")]),
+            config_hash: Some("cfg".to_string()),
+            ..Default::default()
+        };
+
+        let report = check_staleness(&state, &state.file_hashes, "cfg", temp_dir.path());
+        assert!(!report.is_stale());
+        assert!(report.reasons().is_empty());
+        assert_eq!(report.outputs[0].state, OutputState::UpToDate);
+    }
+
+    /// Hash every file below `root`, keyed by relative path, like a rescan.
+    fn scan(root: &Path) -> BTreeMap<PathBuf, String> {
+        fn walk(dir: &Path, root: &Path, hashes: &mut BTreeMap<PathBuf, String>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    walk(&path, root, hashes);
+                } else {
+                    let hash = Sha256::digest(std::fs::read(&path).unwrap());
+                    let relative = path.strip_prefix(root).unwrap().to_path_buf();
+                    hashes.insert(relative, format!("{hash:x}"));
+                }
+            }
+        }
+        let mut hashes = BTreeMap::new();
+        walk(root, root, &mut hashes);
+        hashes
+    }
+
+    #[tokio::test]
+    async fn test_check_up_to_date_after_regenerating_over_existing_file() {
+        use crate::generator::rules::{FormattedRules, GeneratedRules};
+        use crate::output::{ConflictStrategy, WriteOptions, write_output};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("CLAUDE.md"), "# Hand-written rules\n").unwrap();
+
+        let options = WriteOptions::new(root)
+            .with_backups(true)
+            .with_conflict_strategy(ConflictStrategy::Overwrite);
+        let formats = vec!["claude".to_string()];
+        // Regenerate twice so both backup names (`.bak`, `.<timestamp>.bak`) exist
+        for body in ["# Rules\n", "# Rules v2\n"] {
+            // Hashes are recorded before the rule files are written
+            let file_hashes = scan(root);
+            let mut rules = GeneratedRules::new("analysis");
+            rules.add_format(FormattedRules::new("claude", generated(body)));
+            let results = write_output(
+                &rules, &formats, "demo", &options, None, &mut None, None, true,
+            )
+            .await
+            .unwrap();
+            assert!(results[0].backup_created);
+
+            let state = State {
+                output_files: vec![PathBuf::from("CLAUDE.md")],
+                file_hashes,
+                config_hash: Some("cfg".to_string()),
+                ..Default::default()
+            };
+            let report = check_staleness(&state, &scan(root), "cfg", root);
+            assert!(!report.is_stale(), "{:?}", report.reasons());
+            assert_eq!(report.outputs[0].state, OutputState::UpToDate);
+        }
+        assert!(root.join("CLAUDE.md.bak").exists());
+    }
+}
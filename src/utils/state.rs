@@ -8,10 +8,12 @@
 //! - Last run timestamp
 //! - Cost/token/compression metrics from the last run
 //! - Output file paths from the last run
+//! - Commit, file hashes and analysis the last rules were generated from
 
 use crate::utils::error::RuleyError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// Current state file version for migration support.
@@ -47,6 +49,8 @@ pub struct UserSelections {
 /// - User preferences for file conflict resolution
 /// - Metrics from the last run (cost, tokens, compression ratio)
 /// - Output file paths from the last run
+/// - The commit, file hashes and analysis behind the last rules, for
+///   `--incremental` runs
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct State {
     /// Version of the state file format for migrations.
@@ -55,7 +59,8 @@ pub struct State {
     pub last_run: DateTime<Utc>,
     /// User selections and preferences.
     pub user_selections: UserSelections,
-    /// Output files generated in the last run.
+    /// Output files generated in the last run, relative to the repository
+    /// when they are inside it.
     pub output_files: Vec<PathBuf>,
     /// Total cost spent in the last run (USD). Must be >= 0.0.
     pub cost_spent: f32,
@@ -69,6 +74,18 @@ pub struct State {
     ///
     /// Must be in range 0.0..=1.0.
     pub compression_ratio: f32,
+    /// HEAD commit the rules were generated from (`None` outside git repositories).
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// SHA-256 of each analyzed file, keyed by path relative to the repository.
+    #[serde(default)]
+    pub file_hashes: BTreeMap<PathBuf, String>,
+    /// Analysis the rules were generated from.
+    #[serde(default)]
+    pub analysis: Option<String>,
+    /// Fingerprint of the generation settings, compared by `--check`.
+    #[serde(default)]
+    pub config_hash: Option<String>,
 }
 
 impl Default for State {
@@ -81,6 +98,10 @@ fn default() -> Self {
             cost_spent: 0.0,
             token_count: 0,
             compression_ratio: 1.0,
+            commit: None,
+            file_hashes: BTreeMap::new(),
+            analysis: None,
+            config_hash: None,
         }
     }
 }
@@ -260,6 +281,10 @@ fn test_state_serialization() {
             cost_spent: 0.0234,
             token_count: 45678,
             compression_ratio: 0.68,
+            commit: Some("4f2a9c1e".to_string()),
+            file_hashes: BTreeMap::from([(PathBuf::from("src/main.rs"), "ab12".to_string())]),
+            analysis: Some("Uses anyhow for errors".to_string()),
+            config_hash: Some("9d1e".to_string()),
         };
 
         // Serialize to JSON
@@ -272,6 +297,8 @@ fn test_state_serialization() {
         assert!(json.contains("\"cost_spent\": 0.0234"));
         assert!(json.contains("\"token_count\": 45678"));
         assert!(json.contains("\"compression_ratio\": 0.68"));
+        assert!(json.contains("\"commit\": \"4f2a9c1e\""));
+        assert!(json.contains("\"src/main.rs\": \"ab12\""));
 
         // Deserialize back
         let deserialized: State = serde_json::from_str(&json).expect("Failed to deserialize");
@@ -298,6 +325,7 @@ fn test_save_load_state() {
             cost_spent: 1.5,
             token_count: 100000,
             compression_ratio: 0.7,
+            ..Default::default()
         };
 
         // Save state
@@ -316,6 +344,27 @@ fn test_save_load_state() {
         assert_eq!(state, loaded);
     }
 
+    #[test]
+    fn test_load_state_without_incremental_fields() {
+        let temp_dir = create_test_dir();
+        let ruley_dir = temp_dir.path().join(".ruley");
+        std::fs::create_dir_all(&ruley_dir).expect("Failed to create .ruley dir");
+
+        // State written before commits, hashes and analyses were recorded
+        std::fs::write(
+            ruley_dir.join("state.json"),
+            r#"{"version": "1.0.0", "last_run": "2026-01-29T12:00:00Z", "user_selections": {"file_conflict_action": null, "apply_to_all": false}, "output_files": [], "cost_spent": 0.0, "token_count": 0, "compression_ratio": 1.0}"#,
+        )
+        .expect("Failed to write file");
+
+        let state = load_state(&ruley_dir)
+            .expect("Should not error")
+            .expect("State should load");
+        assert!(state.commit.is_none());
+        assert!(state.file_hashes.is_empty());
+        assert!(state.analysis.is_none());
+    }
+
     #[test]
     fn test_load_missing_state() {
         let temp_dir = create_test_dir();
@@ -363,6 +412,7 @@ fn test_migrate_v1_to_v1() {
             cost_spent: 0.5,
             token_count: 5000,
             compression_ratio: 0.8,
+            ..Default::default()
         };
 
         // Convert to JSON Value
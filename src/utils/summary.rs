@@ -19,6 +19,8 @@
 //!     tokens_processed,
 //!     compression_ratio,
 //!     actual_cost,
+//!     0,    // no cached responses
+//!     None, // no chunks reused
 //!     Duration::from_secs_f32(12.3),
 //!     false, // quiet
 //! )?;
@@ -31,6 +33,15 @@
 use std::io::Write;
 use std::time::Duration;
 
+/// Chunk analyses reused from an interrupted run via `--resume`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResumeSummary {
+    /// Number of chunk analyses loaded from the cache instead of requested
+    pub chunks_reused: usize,
+    /// What the reused analyses cost when they were first requested (in dollars)
+    pub cost_saved: f64,
+}
+
 /// Display a success summary after rule generation completes.
 ///
 /// Shows output files with sizes, statistics, and next steps.
@@ -42,6 +53,8 @@
 /// * `tokens_processed` - Total tokens sent to LLM
 /// * `compression_ratio` - Compression ratio if compression was used (0.0-1.0)
 /// * `actual_cost` - Actual cost incurred (in dollars)
+/// * `cache_hits` - LLM requests answered from the response cache
+/// * `resume` - Chunk analyses reused via `--resume`, if any
 /// * `elapsed` - Time elapsed for the entire operation
 /// * `quiet` - If true, suppresses output entirely
 ///
@@ -55,6 +68,8 @@ pub fn display_success_summary(
     tokens_processed: usize,
     compression_ratio: Option<f32>,
     actual_cost: f64,
+    cache_hits: usize,
+    resume: Option<&ResumeSummary>,
     elapsed: Duration,
     quiet: bool,
 ) -> Result<()> {
@@ -139,6 +154,30 @@ pub fn display_success_summary(
         style(format!("${:.2}", actual_cost)).green()
     )?;
 
+    // Requests served from the response cache at no cost
+    if cache_hits > 0 {
+        writeln!(
+            term,
+            "{} Cache hits: {} ({})",
+            style("\u{251c}\u{2500}").dim(),
+            format_number(cache_hits),
+            style("$0.00").green()
+        )?;
+    }
+
+    // Chunks reused from an interrupted run
+    if let Some(resume) = resume
+        && resume.chunks_reused > 0
+    {
+        writeln!(
+            term,
+            "{} Chunks reused: {} (saved {})",
+            style("\u{251c}\u{2500}").dim(),
+            format_number(resume.chunks_reused),
+            style(format!("${:.2}", resume.cost_saved)).green()
+        )?;
+    }
+
     // Time elapsed
     writeln!(
         term,
@@ -259,6 +298,8 @@ fn test_display_success_summary_quiet_mode() {
             50000,
             Some(0.3),
             0.14,
+            0,
+            None,
             Duration::from_secs(12),
             true,
         );
@@ -288,6 +329,8 @@ fn test_display_success_summary_with_files() {
             48234,
             Some(0.31),
             0.14,
+            0,
+            None,
             Duration::from_secs_f64(12.3),
             false,
         );
@@ -304,6 +347,8 @@ fn test_display_success_summary_no_compression() {
             10000,
             None, // No compression
             0.05,
+            0,
+            None,
             Duration::from_secs(5),
             false,
         );
@@ -319,12 +364,35 @@ fn test_display_success_summary_long_duration() {
             200000,
             Some(0.25),
             1.50,
+            0,
+            None,
             Duration::from_secs_f64(150.5), // 2m 30.5s
             false,
         );
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_display_success_summary_with_resumed_chunks() {
+        let results = vec![];
+        let resume = ResumeSummary {
+            chunks_reused: 8,
+            cost_saved: 0.42,
+        };
+        let result = display_success_summary(
+            &results,
+            500,
+            200000,
+            None,
+            0.06,
+            0,
+            Some(&resume),
+            Duration::from_secs(20),
+            false,
+        );
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_get_file_size_nonexistent() {
         let path = PathBuf::from("/nonexistent/path/file.txt");
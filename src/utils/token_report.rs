@@ -0,0 +1,512 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-directory token attribution for `--token-report`.
+//!
+//! Builds a tree of directories and files ranked by tokens, with original
+//! and compressed sizes and each node's share of the total. Directories of
+//! fixtures, vendored code and snapshots that take up a large share are
+//! turned into `--exclude` suggestions. The report prints as a tree and can
+//! be exported as JSON.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ruley::utils::token_report::{build_token_report, display_token_report};
+//!
+//! let report = build_token_report(&compressed_codebase, &repo_path, tokenizer.as_ref());
+//! display_token_report(&report)?;
+//! ```
+
+use crate::llm::tokenizer::Tokenizer;
+use crate::packer::CompressedCodebase;
+use crate::utils::formatting::format_number;
+use anyhow::{Context, Result};
+use console::{Term, style};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Component, Path};
+
+/// Deepest directory level printed in the tree (the JSON export is complete).
+const MAX_DISPLAY_DEPTH: usize = 4;
+
+/// Children printed per directory before the rest are summarized.
+const MAX_DISPLAY_CHILDREN: usize = 8;
+
+/// Minimum share of the total tokens, in percent, for an exclude suggestion.
+const SUGGESTION_MIN_PERCENT: f64 = 5.0;
+
+/// Directory names worth excluding, with the reason shown in suggestions.
+const EXCLUDABLE_DIRS: &[(&str, &str)] = &[
+    ("fixtures", "fixtures"),
+    ("fixture", "fixtures"),
+    ("__fixtures__", "fixtures"),
+    ("testdata", "fixtures"),
+    ("test-data", "fixtures"),
+    ("test_data", "fixtures"),
+    ("vendor", "vendored code"),
+    ("vendored", "vendored code"),
+    ("third_party", "vendored code"),
+    ("third-party", "vendored code"),
+    ("thirdparty", "vendored code"),
+    ("external", "vendored code"),
+    ("__snapshots__", "snapshots"),
+    ("snapshots", "snapshots"),
+];
+
+/// Extension of snapshot files (insta, Jest) that live next to their tests.
+const SNAPSHOT_EXTENSION: &str = "snap";
+
+/// Whether a report node is a directory or a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    Directory,
+    File,
+}
+
+/// A directory or file with the tokens and bytes attributed to it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportNode {
+    /// Last path component (`"."` for the repository root)
+    pub name: String,
+    /// Path relative to the repository root, `/`-separated
+    pub path: String,
+    pub kind: NodeKind,
+    /// Number of files at or below this node
+    pub files: usize,
+    /// Tokens of the compressed content, including file paths
+    pub tokens: usize,
+    /// Bytes before compression
+    pub original_size: usize,
+    /// Bytes after compression
+    pub compressed_size: usize,
+    /// Share of the total tokens, in percent
+    pub percent: f64,
+    /// Children ranked by tokens, largest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ReportNode>,
+}
+
+/// A glob that would remove a large share of tokens.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExcludeSuggestion {
+    /// Glob to pass to `--exclude`
+    pub pattern: String,
+    /// What the matched files are (`fixtures`, `vendored code`, `snapshots`)
+    pub reason: String,
+    pub tokens: usize,
+    pub percent: f64,
+}
+
+/// Token attribution for a whole codebase.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TokenReport {
+    pub total_tokens: usize,
+    pub root: ReportNode,
+    /// Suggestions ranked by tokens, largest first
+    pub suggestions: Vec<ExcludeSuggestion>,
+}
+
+/// Accumulates totals while the tree is built.
+#[derive(Default)]
+struct DirBuilder {
+    files: usize,
+    tokens: usize,
+    original_size: usize,
+    compressed_size: usize,
+    dirs: BTreeMap<String, DirBuilder>,
+    leaves: Vec<ReportNode>,
+}
+
+impl DirBuilder {
+    fn add(&mut self, tokens: usize, original_size: usize, compressed_size: usize) {
+        self.files += 1;
+        self.tokens += tokens;
+        self.original_size += original_size;
+        self.compressed_size += compressed_size;
+    }
+
+    fn build(self, name: String, path: String, total: usize) -> ReportNode {
+        let mut children: Vec<ReportNode> = self
+            .dirs
+            .into_iter()
+            .map(|(name, dir)| {
+                let child_path = join(&path, &name);
+                dir.build(name, child_path, total)
+            })
+            .chain(self.leaves.into_iter().map(|mut leaf| {
+                leaf.percent = percent(leaf.tokens, total);
+                leaf
+            }))
+            .collect();
+        children.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.name.cmp(&b.name)));
+
+        ReportNode {
+            name,
+            path,
+            kind: NodeKind::Directory,
+            files: self.files,
+            tokens: self.tokens,
+            original_size: self.original_size,
+            compressed_size: self.compressed_size,
+            percent: percent(self.tokens, total),
+            children,
+        }
+    }
+}
+
+fn join(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{parent}/{name}")
+    }
+}
+
+fn percent(tokens: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        tokens as f64 * 100.0 / total as f64
+    }
+}
+
+/// Attribute the tokens of `codebase` to its directories and files.
+///
+/// Paths are shown relative to `root`. Token counts cover the path and
+/// compressed content of each file, matching
+/// [`calculate_tokens`](crate::llm::tokenizer::calculate_tokens).
+pub fn build_token_report(
+    codebase: &CompressedCodebase,
+    root: &Path,
+    tokenizer: &dyn Tokenizer,
+) -> TokenReport {
+    let mut tree = DirBuilder::default();
+    // Snapshot files as (path, tokens)
+    let mut snapshots = Vec::new();
+
+    for file in &codebase.files {
+        let relative = file.path.strip_prefix(root).unwrap_or(&file.path);
+        let components: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        let Some((file_name, dirs)) = components.split_last() else {
+            continue;
+        };
+
+        let tokens = tokenizer.count_tokens(&file.path.to_string_lossy())
+            + tokenizer.count_tokens(&file.compressed_content);
+        let (original_size, compressed_size) = (file.original_size, file.compressed_size);
+
+        let mut node = &mut tree;
+        node.add(tokens, original_size, compressed_size);
+        for dir in dirs {
+            node = node.dirs.entry(dir.clone()).or_default();
+            node.add(tokens, original_size, compressed_size);
+        }
+
+        let path = components.join("/");
+        if relative.extension().and_then(|e| e.to_str()) == Some(SNAPSHOT_EXTENSION) {
+            snapshots.push((path.clone(), tokens));
+        }
+        node.leaves.push(ReportNode {
+            name: file_name.clone(),
+            path,
+            kind: NodeKind::File,
+            files: 1,
+            tokens,
+            original_size,
+            compressed_size,
+            percent: 0.0,
+            children: Vec::new(),
+        });
+    }
+
+    let total_tokens = tree.tokens;
+    let root = tree.build(".".to_string(), String::new(), total_tokens);
+
+    let mut suggestions = Vec::new();
+    collect_suggestions(&root, &mut suggestions);
+    // Snapshot files outside an already-suggested directory
+    let snapshot_tokens: usize = snapshots
+        .iter()
+        .filter(|(path, _)| !covered(path, &suggestions))
+        .map(|(_, tokens)| tokens)
+        .sum();
+    if percent(snapshot_tokens, total_tokens) >= SUGGESTION_MIN_PERCENT {
+        suggestions.push(ExcludeSuggestion {
+            pattern: format!("**/*.{SNAPSHOT_EXTENSION}"),
+            reason: "snapshots".to_string(),
+            tokens: snapshot_tokens,
+            percent: percent(snapshot_tokens, total_tokens),
+        });
+    }
+    suggestions.sort_by(|a, b| {
+        b.tokens
+            .cmp(&a.tokens)
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+
+    TokenReport {
+        total_tokens,
+        root,
+        suggestions,
+    }
+}
+
+/// Suggest excluding large excludable directories, without descending into them.
+fn collect_suggestions(node: &ReportNode, suggestions: &mut Vec<ExcludeSuggestion>) {
+    for child in &node.children {
+        if child.kind != NodeKind::Directory {
+            continue;
+        }
+        let lower = child.name.to_lowercase();
+        let reason = EXCLUDABLE_DIRS
+            .iter()
+            .find(|(name, _)| *name == lower)
+            .map(|(_, reason)| reason);
+        match reason {
+            Some(reason) if child.percent >= SUGGESTION_MIN_PERCENT => {
+                suggestions.push(ExcludeSuggestion {
+                    pattern: format!("{}/**", child.path),
+                    reason: reason.to_string(),
+                    tokens: child.tokens,
+                    percent: child.percent,
+                });
+            }
+            _ => collect_suggestions(child, suggestions),
+        }
+    }
+}
+
+/// Whether `path` is inside a directory that is already suggested.
+fn covered(path: &str, suggestions: &[ExcludeSuggestion]) -> bool {
+    suggestions.iter().any(|s| {
+        s.pattern
+            .strip_suffix("/**")
+            .is_some_and(|dir| path.starts_with(&format!("{dir}/")))
+    })
+}
+
+/// Print the report as a tree followed by exclude suggestions.
+///
+/// # Errors
+///
+/// Returns an error if writing to the terminal fails.
+pub fn display_token_report(report: &TokenReport) -> Result<()> {
+    let mut term = Term::stdout();
+
+    writeln!(term)?;
+    writeln!(
+        term,
+        "{}: {} tokens in {} files - No LLM calls will be made",
+        style("Token Report").yellow().bold(),
+        format_number(report.total_tokens),
+        format_number(report.root.files)
+    )?;
+    writeln!(term)?;
+    writeln!(term, "{}", node_line(&report.root))?;
+    write_children(&mut term, &report.root, "", 1)?;
+
+    if !report.suggestions.is_empty() {
+        writeln!(term)?;
+        writeln!(term, "{}:", style("Suggested excludes").bold())?;
+        for (i, suggestion) in report.suggestions.iter().enumerate() {
+            let prefix = if i == report.suggestions.len() - 1 {
+                "\u{2514}\u{2500}"
+            } else {
+                "\u{251c}\u{2500}"
+            };
+            writeln!(
+                term,
+                "{} --exclude '{}' ({}, {} tokens, {:.1}%)",
+                style(prefix).dim(),
+                suggestion.pattern,
+                suggestion.reason,
+                format_number(suggestion.tokens),
+                suggestion.percent
+            )?;
+        }
+    }
+
+    writeln!(term)?;
+    Ok(())
+}
+
+fn node_line(node: &ReportNode) -> String {
+    let name = match node.kind {
+        NodeKind::Directory => format!("{}/", node.name),
+        NodeKind::File => node.name.clone(),
+    };
+    format!(
+        "{} ({} tokens, {:.1}%, {} \u{2192} {} bytes)",
+        name,
+        format_number(node.tokens),
+        node.percent,
+        format_number(node.original_size),
+        format_number(node.compressed_size)
+    )
+}
+
+fn write_children(term: &mut Term, node: &ReportNode, indent: &str, depth: usize) -> Result<()> {
+    let shown = node.children.len().min(MAX_DISPLAY_CHILDREN);
+    let hidden = &node.children[shown..];
+
+    for (i, child) in node.children[..shown].iter().enumerate() {
+        let is_last = i == shown - 1 && hidden.is_empty();
+        let (prefix, child_indent) = if is_last {
+            ("\u{2514}\u{2500}", "   ")
+        } else {
+            ("\u{251c}\u{2500}", "\u{2502}  ")
+        };
+        writeln!(
+            term,
+            "{}{} {}",
+            indent,
+            style(prefix).dim(),
+            node_line(child)
+        )?;
+        if depth < MAX_DISPLAY_DEPTH {
+            write_children(term, child, &format!("{indent}{child_indent}"), depth + 1)?;
+        }
+    }
+
+    if !hidden.is_empty() {
+        let tokens: usize = hidden.iter().map(|c| c.tokens).sum();
+        writeln!(
+            term,
+            "{}{} ... ({} more, {} tokens)",
+            indent,
+            style("\u{2514}\u{2500}").dim(),
+            hidden.len(),
+            format_number(tokens)
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the full report (no depth or child limits) to `path` as JSON.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_token_report_json(report: &TokenReport, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(report).context("Failed to serialize token report")?;
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write token report to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::test_support::{self, WordTokenizer};
+
+    /// Codebase rooted at `/repo`, compressed to half its original size.
+    fn create_test_codebase(files: Vec<(&str, &str)>) -> CompressedCodebase {
+        let mut files = test_support::create_test_codebase(files).files;
+        for file in &mut files {
+            file.path = Path::new("/repo").join(&file.path);
+            file.original_size *= 2;
+        }
+        CompressedCodebase::new(files)
+    }
+
+    fn words(n: usize) -> String {
+        vec!["w"; n].join(" ")
+    }
+
+    #[test]
+    fn test_tree_is_ranked_by_tokens() {
+        let codebase = create_test_codebase(vec![
+            ("src/lib.rs", &words(9)),
+            ("src/packer/walker.rs", &words(29)),
+            ("README.md", &words(59)),
+        ]);
+        let report = build_token_report(&codebase, Path::new("/repo"), &WordTokenizer);
+
+        // One path token per file
+        assert_eq!(report.total_tokens, 100);
+        assert_eq!(report.root.files, 3);
+        assert_eq!(report.root.percent, 100.0);
+
+        let names: Vec<&str> = report
+            .root
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["README.md", "src"]);
+
+        let src = &report.root.children[1];
+        assert_eq!(src.kind, NodeKind::Directory);
+        assert_eq!(src.path, "src");
+        assert_eq!(src.tokens, 40);
+        assert_eq!(src.percent, 40.0);
+        assert_eq!(src.files, 2);
+        assert_eq!(src.compressed_size, 2 * 9 - 1 + 2 * 29 - 1);
+        assert_eq!(src.original_size, 2 * src.compressed_size);
+
+        let packer = &src.children[0];
+        assert_eq!(packer.path, "src/packer");
+        assert_eq!(packer.children[0].path, "src/packer/walker.rs");
+        assert_eq!(packer.children[0].percent, 30.0);
+        assert!(report.suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_exclude_suggestions() {
+        let codebase = create_test_codebase(vec![
+            ("src/lib.rs", &words(49)),
+            ("tests/fixtures/big.json", &words(19)),
+            ("tests/fixtures/nested/vendor/x.js", &words(9)),
+            ("vendor/lib.js", &words(4)),
+            ("third_party/tiny.c", &words(1)),
+            ("src/__snapshots__/a.snap", &words(5)),
+            ("tests/b.snap", &words(9)),
+        ]);
+        let report = build_token_report(&codebase, Path::new("/repo"), &WordTokenizer);
+        assert_eq!(report.total_tokens, 103);
+
+        let patterns: Vec<(&str, &str)> = report
+            .suggestions
+            .iter()
+            .map(|s| (s.pattern.as_str(), s.reason.as_str()))
+            .collect();
+        // fixtures/ is suggested whole; third_party/ is too small; the snapshot
+        // inside src/__snapshots__/ counts towards that directory only
+        assert_eq!(
+            patterns,
+            vec![
+                ("tests/fixtures/**", "fixtures"),
+                ("**/*.snap", "snapshots"),
+                ("src/__snapshots__/**", "snapshots"),
+            ]
+        );
+        assert_eq!(report.suggestions[0].tokens, 30);
+        assert_eq!(report.suggestions[1].tokens, 10);
+    }
+
+    #[test]
+    fn test_json_export() {
+        let codebase = create_test_codebase(vec![("src/lib.rs", "fn main() {}")]);
+        let report = build_token_report(&codebase, Path::new("/repo"), &WordTokenizer);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        write_token_report_json(&report, &path).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["total_tokens"], 4);
+        assert_eq!(json["root"]["children"][0]["path"], "src");
+        assert_eq!(json["root"]["children"][0]["kind"], "directory");
+        assert_eq!(json["root"]["children"][0]["children"][0]["kind"], "file");
+        assert!(json["root"]["children"][0]["children"][0]["children"].is_null());
+    }
+}
@@ -14,9 +14,12 @@
 
 use crate::cli::config::SemanticValidationConfig;
 use crate::packer::CompressedCodebase;
-use anyhow::Result;
+use crate::utils::finalization::rule_file_format;
+use anyhow::{Context, Result};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fmt;
+use std::path::Path;
 use std::sync::LazyLock;
 
 /// Regex for extracting file paths from rule content.
@@ -76,7 +79,8 @@
 });
 
 /// Identifies which validation layer produced an error or warning.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ValidationLayer {
     /// Content parsing validation (JSON, Markdown, YAML)
     Syntax,
@@ -146,6 +150,8 @@ pub struct ValidationResult {
     pub errors: Vec<ValidationError>,
     /// Non-blocking warnings
     pub warnings: Vec<ValidationWarning>,
+    /// Rule file the content was read from, when validating files on disk
+    pub file: Option<String>,
 }
 
 impl ValidationResult {
@@ -157,6 +163,7 @@ fn pass(format: &str) -> Self {
             passed: true,
             errors: Vec::new(),
             warnings: Vec::new(),
+            file: None,
         }
     }
 
@@ -171,6 +178,7 @@ fn from_checks(
             passed: errors.is_empty(),
             errors,
             warnings,
+            file: None,
         }
     }
 }
@@ -485,10 +493,14 @@ fn validate_markdown_syntax(content: &str, errors: &mut Vec<ValidationError>) {
     // Check for unclosed code blocks
     let triple_backtick_count = content.matches("```").count();
     if !triple_backtick_count.is_multiple_of(2) {
+        // The last delimiter is the one left open
+        let line = content
+            .rfind("```")
+            .map(|offset| content[..offset].matches('\n').count() + 1);
         errors.push(ValidationError {
             layer: ValidationLayer::Syntax,
             message: "Unclosed code block (odd number of ``` delimiters)".to_string(),
-            location: None,
+            location: line.map(|l| format!("line {}", l)),
             suggestion: Some("Add closing ``` to unclosed code blocks".to_string()),
         });
     }
@@ -510,7 +522,10 @@ fn validate_semantic(
                 warnings.push(ValidationWarning {
                     layer: ValidationLayer::Semantic,
                     message: format!("File path \"{}\" not found in codebase", path),
-                    location: None,
+                    location: content
+                        .lines()
+                        .position(|line| line.contains(path.as_str()))
+                        .map(|i| format!("line {}", i + 1)),
                     suggestion: Some("Remove this reference or fix the file path".to_string()),
                 });
             }
@@ -973,6 +988,57 @@ pub fn validate_all_formats(
     Ok(results)
 }
 
+/// Validate rule files read from disk, such as hand-edited rules.
+///
+/// Each file is validated as the format its path belongs to (see
+/// [`rule_file_format`]); other files are skipped. When consistency checks
+/// are enabled for any of the formats, the files are also checked against
+/// each other.
+///
+/// # Arguments
+///
+/// * `files` - Path relative to the project and content of each rule file
+/// * `config` - Validation configuration
+/// * `codebase` - The compressed codebase for file path validation
+pub fn validate_rule_files(
+    files: &[(String, String)],
+    config: &crate::cli::config::ValidationConfig,
+    codebase: &CompressedCodebase,
+) -> Result<Vec<ValidationResult>> {
+    let mut results = Vec::new();
+    let mut contents: HashMap<String, String> = HashMap::new();
+    let mut consistency_enabled = false;
+
+    for (path, content) in files {
+        let Some(format) = rule_file_format(path) else {
+            tracing::debug!("Skipping {}: not a known rule file", path);
+            continue;
+        };
+
+        let semantic_config = config.semantic_for_format(format);
+        consistency_enabled |= semantic_config.check_consistency;
+        let mut result = get_validator(format)?.validate(content, semantic_config, codebase)?;
+        result.file = Some(path.clone());
+        results.push(result);
+        contents.insert(path.clone(), content.clone());
+    }
+
+    if consistency_enabled && contents.len() >= 2 {
+        let mut cross_file_errors = Vec::new();
+        validate_cross_format_consistency(&contents, &mut cross_file_errors);
+
+        if !cross_file_errors.is_empty() {
+            results.push(ValidationResult::from_checks(
+                "cross-format",
+                cross_file_errors,
+                Vec::new(),
+            ));
+        }
+    }
+
+    Ok(results)
+}
+
 /// Display a formatted validation report to the user.
 pub fn display_validation_report(results: &[ValidationResult], quiet: bool) {
     if quiet {
@@ -989,7 +1055,10 @@ pub fn display_validation_report(results: &[ValidationResult], quiet: bool) {
     for result in results {
         let status = if result.passed { "PASSED" } else { "FAILED" };
         println!();
-        println!("{} ({}):", result.format, status);
+        match result.file {
+            Some(ref file) => println!("{} [{}] ({}):", file, result.format, status),
+            None => println!("{} ({}):", result.format, status),
+        }
 
         // Display errors
         for error in &result.errors {
@@ -998,7 +1067,13 @@ pub fn display_validation_report(results: &[ValidationResult], quiet: bool) {
 
         // Display warnings
         for warning in &result.warnings {
-            println!("  \u{26a0} [{}] {}", warning.layer, warning.message);
+            match warning.location {
+                Some(ref loc) => println!(
+                    "  \u{26a0} [{}] {} at {}",
+                    warning.layer, warning.message, loc
+                ),
+                None => println!("  \u{26a0} [{}] {}", warning.layer, warning.message),
+            }
             if let Some(ref sug) = warning.suggestion {
                 println!("      Suggestion: {}", sug);
             }
@@ -1009,17 +1084,154 @@ pub fn display_validation_report(results: &[ValidationResult], quiet: bool) {
         }
     }
 
+    let noun = if results.iter().any(|r| r.file.is_some()) {
+        "rule file(s)"
+    } else {
+        "format(s)"
+    };
     println!();
     if failed_count > 0 {
         println!(
-            "Summary: {} of {} format(s) failed validation",
-            failed_count, total
+            "Summary: {} of {} {} failed validation",
+            failed_count, total, noun
         );
     } else {
-        println!("Summary: All {} format(s) passed validation", total);
+        println!("Summary: All {} {} passed validation", total, noun);
     }
 }
 
+/// Version of the JSON validation report schema, bumped on breaking changes.
+pub const VALIDATION_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Machine-readable validation report, as written by `ruley validate --json`.
+///
+/// The field names and values form a stable schema: fields may be added, but
+/// renaming or removing one bumps [`VALIDATION_REPORT_SCHEMA_VERSION`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub schema_version: u32,
+    /// Whether every result passed
+    pub passed: bool,
+    pub results: Vec<ValidationReportResult>,
+}
+
+/// One [`ValidationResult`] in the report.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReportResult {
+    /// Validated format, or `cross-format` for consistency checks
+    pub format: String,
+    /// Rule file the content was read from, if any
+    pub file: Option<String>,
+    pub passed: bool,
+    pub errors: Vec<ValidationIssue>,
+    pub warnings: Vec<ValidationIssue>,
+}
+
+/// An error or warning in the report.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    /// `syntax`, `schema` or `semantic`
+    pub layer: ValidationLayer,
+    pub message: String,
+    /// Where the issue is, when the validator could determine it
+    pub location: Option<IssueLocation>,
+    pub suggestion: Option<String>,
+}
+
+/// File and line of an issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IssueLocation {
+    pub file: Option<String>,
+    /// 1-based line number
+    pub line: Option<usize>,
+}
+
+impl ValidationIssue {
+    fn new(
+        layer: ValidationLayer,
+        message: &str,
+        location: Option<&str>,
+        suggestion: Option<&String>,
+        file: Option<&String>,
+    ) -> Self {
+        let line = location
+            .and_then(|loc| loc.strip_prefix("line "))
+            .and_then(|line| line.trim().parse().ok());
+        let location = (file.is_some() || line.is_some()).then(|| IssueLocation {
+            file: file.cloned(),
+            line,
+        });
+        Self {
+            layer,
+            message: message.to_string(),
+            location,
+            suggestion: suggestion.cloned(),
+        }
+    }
+}
+
+impl ValidationReport {
+    /// Build the report from validation results.
+    pub fn from_results(results: &[ValidationResult]) -> Self {
+        let results: Vec<ValidationReportResult> = results
+            .iter()
+            .map(|result| {
+                let file = result.file.as_ref();
+                ValidationReportResult {
+                    format: result.format.clone(),
+                    file: result.file.clone(),
+                    passed: result.passed,
+                    errors: result
+                        .errors
+                        .iter()
+                        .map(|e| {
+                            ValidationIssue::new(
+                                e.layer,
+                                &e.message,
+                                e.location.as_deref(),
+                                e.suggestion.as_ref(),
+                                file,
+                            )
+                        })
+                        .collect(),
+                    warnings: result
+                        .warnings
+                        .iter()
+                        .map(|w| {
+                            ValidationIssue::new(
+                                w.layer,
+                                &w.message,
+                                w.location.as_deref(),
+                                w.suggestion.as_ref(),
+                                file,
+                            )
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Self {
+            schema_version: VALIDATION_REPORT_SCHEMA_VERSION,
+            passed: results.iter().all(|r| r.passed),
+            results,
+        }
+    }
+}
+
+/// Write the validation results to `path` as a JSON [`ValidationReport`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_validation_json(results: &[ValidationResult], path: &Path) -> Result<()> {
+    let report = ValidationReport::from_results(results);
+    let json =
+        serde_json::to_string_pretty(&report).context("Failed to serialize validation report")?;
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write validation report to {}", path.display()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1218,6 +1430,69 @@ fn test_extract_conventions_semicolons() {
         assert_eq!(conventions.semicolons.as_deref(), Some("use"));
     }
 
+    #[test]
+    fn test_validate_rule_files() {
+        let files = vec![
+            (
+                ".cursor/rules/style.mdc".to_string(),
+                "---\nglobs: **/*.rs\n---\n# Style\n\nUse tabs for indentation.\n".to_string(),
+            ),
+            (
+                "CLAUDE.md".to_string(),
+                "# Rules\n\nUse spaces for indentation. See `src/main.rs`.\n".to_string(),
+            ),
+            ("README.md".to_string(), "# Demo".to_string()),
+        ];
+        let config = crate::cli::config::ValidationConfig::default();
+
+        let results = validate_rule_files(&files, &config, &test_codebase()).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].format, "cursor");
+        assert_eq!(results[0].file.as_deref(), Some(".cursor/rules/style.mdc"));
+        assert!(results[0].passed);
+        assert!(
+            results[0]
+                .warnings
+                .iter()
+                .any(|w| w.message.contains("missing 'description'"))
+        );
+        assert_eq!(results[1].file.as_deref(), Some("CLAUDE.md"));
+        assert!(results[1].passed);
+        assert_eq!(results[2].format, "cross-format");
+        assert!(!results[2].passed);
+        assert!(results[2].errors[0].message.contains("CLAUDE.md"));
+    }
+
+    #[test]
+    fn test_validation_report_json() {
+        let files = vec![(
+            "CLAUDE.md".to_string(),
+            "# Rules\n\nSee `src/old.rs`.\n\n```rust\nfn main() {}\n".to_string(),
+        )];
+        let config = crate::cli::config::ValidationConfig::default();
+        let results = validate_rule_files(&files, &config, &test_codebase()).unwrap();
+
+        let report = serde_json::to_value(ValidationReport::from_results(&results)).unwrap();
+        assert_eq!(report["schema_version"], VALIDATION_REPORT_SCHEMA_VERSION);
+        assert_eq!(report["passed"], false);
+
+        let result = &report["results"][0];
+        assert_eq!(result["format"], "claude");
+        assert_eq!(result["file"], "CLAUDE.md");
+        assert_eq!(result["errors"][0]["layer"], "syntax");
+        assert_eq!(
+            result["errors"][0]["location"],
+            serde_json::json!({"file": "CLAUDE.md", "line": 5})
+        );
+        assert_eq!(result["warnings"][0]["layer"], "semantic");
+        assert_eq!(result["warnings"][0]["location"]["line"], 3);
+        assert!(result["warnings"][0]["suggestion"].is_string());
+
+        // Generated output has no file, so only a known line is kept
+        let issue = ValidationIssue::new(ValidationLayer::Schema, "Missing", None, None, None);
+        assert!(issue.location.is_none());
+    }
+
     #[test]
     fn test_cross_format_consistency_conflict() {
         let mut outputs = HashMap::new();
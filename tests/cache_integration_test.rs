@@ -86,10 +86,10 @@ pub fn greet() -> &'static str {
     let chunk_1 = r#"{"chunk_id": 1, "analysis": "Library module with greet function"}"#;
 
     let chunk_0_path = manager
-        .write_chunk_result(0, chunk_0)
+        .write_chunk_result("0", chunk_0)
         .expect("Failed to write chunk 0");
     let chunk_1_path = manager
-        .write_chunk_result(1, chunk_1)
+        .write_chunk_result("1", chunk_1)
         .expect("Failed to write chunk 1");
 
     assert!(chunk_0_path.exists(), "chunk-0.json should be created");
@@ -97,10 +97,10 @@ pub fn greet() -> &'static str {
 
     // 5. Read chunk results back and verify content
     let read_chunk_0 = manager
-        .read_chunk_result(0)
+        .read_chunk_result("0")
         .expect("Failed to read chunk 0");
     let read_chunk_1 = manager
-        .read_chunk_result(1)
+        .read_chunk_result("1")
         .expect("Failed to read chunk 1");
 
     assert_eq!(read_chunk_0, chunk_0, "Chunk 0 content should match");
@@ -266,6 +266,7 @@ fn test_state_persistence_across_managers() {
         cost_spent: 0.0567,
         token_count: 123456,
         compression_ratio: 0.72,
+        ..Default::default()
     };
 
     save_state(&original_state, manager1.ruley_dir()).expect("Failed to save state");
@@ -282,3 +282,23 @@ pub fn run_cli_with_config(dir: &PathBuf, args: &[&str]) -> std::process::Output
 
     cmd.output().expect("Failed to execute command")
 }
+
+/// Fingerprint of the generation settings `ruley <dir> <args>` runs with, as
+/// recorded in `.ruley/state.json` as `config_hash`.
+pub fn config_fingerprint(dir: &std::path::Path, args: &[&str]) -> String {
+    use clap::{CommandFactory, FromArgMatches};
+    use ruley::cli::args::{Args, ArgsPresence};
+
+    let dir = dir.to_string_lossy();
+    let argv = ["ruley", dir.as_ref()]
+        .into_iter()
+        .chain(args.iter().copied());
+    let matches = Args::command()
+        .try_get_matches_from(argv)
+        .expect("Failed to parse arguments");
+    let presence = ArgsPresence::from_matches(&matches);
+    let args = Args::from_arg_matches(&matches).expect("Failed to parse arguments");
+    let config = ruley::cli::config::load(&args).expect("Failed to load config");
+    let merged = ruley::cli::config::merge_config(&args, config, &presence);
+    ruley::utils::staleness::config_fingerprint(&merged)
+}
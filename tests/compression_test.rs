@@ -6,7 +6,9 @@
 //! Tests tree-sitter and whitespace compression with representative code samples.
 //! Verifies compression ratios, node extraction, and error handling.
 
-use ruley::packer::compress::{Compressor, Language, TreeSitterCompressor, WhitespaceCompressor};
+use ruley::packer::compress::{
+    Compressor, Language, TreeSitterCompressor, TruncatingCompressor, WhitespaceCompressor,
+};
 
 #[cfg(feature = "compression-typescript")]
 mod tree_sitter_tests {
@@ -31,7 +33,7 @@ fn test_tree_sitter_typescript_function_compression() {
         }
         "#;
 
-        let compressor = TreeSitterCompressor;
+        let compressor = TreeSitterCompressor::default();
         let result = compressor
             .compress(source, Language::TypeScript)
             .expect("Compression should succeed");
@@ -84,7 +86,7 @@ fn test_tree_sitter_typescript_class_compression() {
         }
         "#;
 
-        let compressor = TreeSitterCompressor;
+        let compressor = TreeSitterCompressor::default();
         let result = compressor
             .compress(source, Language::TypeScript)
             .expect("Compression should succeed");
@@ -144,7 +146,7 @@ fn test_tree_sitter_typescript_imports_exports() {
         export default MyComponent;
         "#;
 
-        let compressor = TreeSitterCompressor;
+        let compressor = TreeSitterCompressor::default();
         // Use Tsx because the source contains JSX syntax
         let result = compressor
             .compress(source, Language::Tsx)
@@ -165,7 +167,7 @@ fn test_tree_sitter_typescript_imports_exports() {
     fn test_tree_sitter_parse_failure_graceful_handling() {
         let invalid_source = "function broken( { // missing closing paren\nconst x = {";
 
-        let compressor = TreeSitterCompressor;
+        let compressor = TreeSitterCompressor::default();
         let result = compressor.compress(invalid_source, Language::TypeScript);
 
         // Should return an error that can be handled (used for fallback)
@@ -193,7 +195,7 @@ fn test_tree_sitter_jsx_compression() {
         export default Greeting;
         "#;
 
-        let compressor = TreeSitterCompressor;
+        let compressor = TreeSitterCompressor::default();
         let result = compressor
             .compress(source, Language::Jsx)
             .expect("JSX compression should succeed");
@@ -228,7 +230,7 @@ mod tree_sitter_disabled_tests {
     #[test]
     fn test_tree_sitter_feature_disabled() {
         let source = "function test(): void {}";
-        let compressor = TreeSitterCompressor;
+        let compressor = TreeSitterCompressor::default();
         let result = compressor.compress(source, Language::TypeScript);
 
         // Should return error when feature disabled
@@ -239,6 +241,668 @@ fn test_tree_sitter_feature_disabled() {
     }
 }
 
+#[cfg(feature = "compression-java")]
+mod java_tests {
+    use super::*;
+
+    /// Test Java compression keeps declarations and signatures and drops bodies.
+    #[test]
+    fn test_tree_sitter_java_compression() {
+        let source = r#"
+package com.example.users;
+
+import java.util.List;
+
+@Service
+public class UserService extends BaseService implements UserApi {
+    private final UserRepository repository;
+
+    public UserService(UserRepository repository) {
+        this.repository = repository;
+    }
+
+    @Override
+    @Transactional(readOnly = true)
+    public List<User> findAll(int page) throws IOException {
+        List<User> users = repository.findAll(page);
+        users.sort(Comparator.comparing(User::getName));
+        return users;
+    }
+}
+
+enum Status { ACTIVE, DISABLED; boolean enabled() { return this == ACTIVE; } }
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::Java)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("package com.example.users;"));
+        assert!(result.contains("import java.util.List;"));
+        assert!(result.contains(
+            "@Service\npublic class UserService extends BaseService implements UserApi {"
+        ));
+        assert!(result.contains("private final UserRepository repository;"));
+        assert!(result.contains("public UserService(UserRepository repository) { /* ... */ }"));
+        assert!(result.contains("@Transactional(readOnly = true)"));
+        assert!(
+            result.contains("public List<User> findAll(int page) throws IOException { /* ... */ }")
+        );
+        assert!(result.contains("ACTIVE"));
+        assert!(result.contains("boolean enabled() { /* ... */ }"));
+        assert!(!result.contains("repository.findAll(page)"));
+        assert!(!result.contains("this.repository = repository"));
+    }
+}
+
+#[cfg(feature = "compression-csharp")]
+mod csharp_tests {
+    use super::*;
+
+    /// Test C# compression keeps declarations and signatures and drops bodies.
+    #[test]
+    fn test_tree_sitter_csharp_compression() {
+        let source = r#"
+using System.Collections.Generic;
+
+namespace Example.Users
+{
+    [ApiController]
+    public class UserController : ControllerBase
+    {
+        public string Name { get; private set; }
+        public int Count => users.Count;
+
+        [HttpGet("{id}")]
+        public async Task<User> Get(int id)
+        {
+            var user = await repository.FindAsync(id);
+            return user ?? throw new NotFoundException();
+        }
+
+        public int Double(int x) => x * 2;
+    }
+
+    public record Point(int X, int Y);
+}
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::CSharp)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("using System.Collections.Generic;"));
+        assert!(result.contains("namespace Example.Users"));
+        assert!(
+            result.contains("[ApiController]\n    public class UserController : ControllerBase")
+        );
+        assert!(result.contains("public string Name { get; private set; }"));
+        assert!(result.contains("public int Count { get; }"));
+        assert!(result.contains("[HttpGet(\"{id}\")]"));
+        assert!(result.contains("public async Task<User> Get(int id) { /* ... */ }"));
+        assert!(result.contains("public int Double(int x) { /* ... */ }"));
+        assert!(result.contains("public record Point(int X, int Y);"));
+        assert!(!result.contains("FindAsync"));
+        assert!(!result.contains("x * 2"));
+    }
+}
+
+#[cfg(feature = "compression-kotlin")]
+mod kotlin_tests {
+    use super::*;
+
+    /// Test Kotlin compression keeps declarations and signatures and drops bodies.
+    #[test]
+    fn test_tree_sitter_kotlin_compression() {
+        let source = r#"
+package com.example.users
+
+import kotlinx.coroutines.flow.Flow
+
+@Service
+class UserService(private val repository: UserRepository) : BaseService() {
+    private val cache = mutableMapOf<Int, User>()
+
+    init {
+        warmUp()
+    }
+
+    @Transactional
+    override suspend fun findAll(page: Int): List<User> {
+        val users = repository.findAll(page)
+        return users.sortedBy { it.name }
+    }
+
+    fun double(x: Int) = x * 2
+
+    companion object {
+        fun create(): UserService { return UserService(InMemoryRepository()) }
+    }
+}
+
+data class Point(val x: Int, val y: Int)
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::Kotlin)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("package com.example.users"));
+        assert!(result.contains("import kotlinx.coroutines.flow.Flow"));
+        assert!(result.contains(
+            "@Service\nclass UserService(private val repository: UserRepository) : BaseService() {"
+        ));
+        assert!(result.contains("private val cache = mutableMapOf<Int, User>()"));
+        assert!(result.contains("@Transactional"));
+        assert!(
+            result.contains("override suspend fun findAll(page: Int): List<User> { /* ... */ }")
+        );
+        assert!(result.contains("fun double(x: Int) { /* ... */ }"));
+        assert!(result.contains("fun create(): UserService { /* ... */ }"));
+        assert!(result.contains("data class Point(val x: Int, val y: Int)"));
+        assert!(!result.contains("warmUp"));
+        assert!(!result.contains("sortedBy"));
+    }
+}
+
+#[cfg(feature = "compression-c")]
+mod c_tests {
+    use super::*;
+
+    /// Test C compression keeps includes, macros, types and prototypes and drops bodies.
+    #[test]
+    fn test_tree_sitter_c_compression() {
+        let source = r#"
+#include <stdint.h>
+#define MAX_DEVICES 4
+#define SQUARE(x) ((x) * (x))
+
+typedef struct device {
+    int fd;
+    uint32_t flags;
+} device_t;
+
+static const uint8_t crc_table[8] = {
+    0x00, 0x07, 0x0e, 0x09, 0x1c, 0x1b, 0x12, 0x15,
+};
+
+int device_open(device_t *dev, const char *path);
+
+#ifdef DEBUG
+void device_dump(const device_t *dev) {
+    printf("fd=%d flags=%u\n", dev->fd, dev->flags);
+}
+#endif
+
+int device_open(device_t *dev, const char *path) {
+    dev->fd = open(path, O_RDWR);
+    return dev->fd < 0 ? -1 : 0;
+}
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::C)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("#include <stdint.h>"));
+        assert!(result.contains("#define MAX_DEVICES 4"));
+        assert!(result.contains("#define SQUARE(x) ((x) * (x))"));
+        assert!(result.contains("uint32_t flags;"));
+        assert!(result.contains("} device_t;"));
+        assert!(result.contains("static const uint8_t crc_table[8] = { /* ... */ };"));
+        assert!(result.contains("int device_open(device_t *dev, const char *path);"));
+        assert!(
+            result.contains(
+                "#ifdef DEBUG\nvoid device_dump(const device_t *dev) { /* ... */ }\n#endif"
+            )
+        );
+        assert!(result.contains("int device_open(device_t *dev, const char *path) { /* ... */ }"));
+        assert!(!result.contains("O_RDWR"));
+        assert!(!result.contains("0x1c"));
+    }
+
+    /// Test that items around a region the grammar cannot parse are still
+    /// compressed, and the region itself is kept rather than dropped.
+    #[test]
+    fn test_tree_sitter_c_preprocessor_split_function() {
+        let source = r#"
+#ifdef LEGACY_API
+int open_device(int fd) {
+#else
+int open_device(int fd, int flags) {
+#endif
+    return ioctl(fd, OPEN, flags);
+}
+
+int close_device(int fd) {
+    return close(fd);
+}
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::C)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("#ifdef LEGACY_API\nint open_device(int fd) {"));
+        assert!(result.contains("int open_device(int fd, int flags) { /* ... */ }"));
+        assert!(result.contains("int close_device(int fd) { /* ... */ }"));
+        assert!(!result.contains("return close(fd)"));
+    }
+}
+
+#[cfg(feature = "compression-cpp")]
+mod cpp_tests {
+    use super::*;
+
+    /// Test C++ compression keeps namespaces, class and template signatures and drops bodies.
+    #[test]
+    fn test_tree_sitter_cpp_compression() {
+        let source = r#"
+#include <vector>
+
+namespace app::core {
+
+template <typename T>
+class Buffer : public Base<T> {
+public:
+    explicit Buffer(size_t n) : data_(n) { reserve(n); }
+    virtual void flush() = 0;
+    size_t size() const noexcept { return data_.size(); }
+
+private:
+    std::vector<T> data_;
+};
+
+template <typename T>
+T max_of(T a, T b) {
+    return a > b ? a : b;
+}
+
+enum class Mode : uint8_t { Read, Write };
+
+}  // namespace app::core
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::Cpp)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("#include <vector>"));
+        assert!(result.contains("namespace app::core {"));
+        assert!(result.contains("template <typename T>\nclass Buffer : public Base<T> {"));
+        assert!(result.contains("public:"));
+        assert!(result.contains("explicit Buffer(size_t n) : data_(n) { /* ... */ }"));
+        assert!(result.contains("virtual void flush() = 0;"));
+        assert!(result.contains("size_t size() const noexcept { /* ... */ }"));
+        assert!(result.contains("std::vector<T> data_;"));
+        assert!(result.contains("template <typename T>\nT max_of(T a, T b) { /* ... */ }"));
+        assert!(result.contains("enum class Mode : uint8_t { Read, Write };"));
+        assert!(!result.contains("reserve(n)"));
+        assert!(!result.contains("a > b"));
+    }
+}
+
+#[cfg(feature = "compression-ruby")]
+mod ruby_tests {
+    use super::*;
+
+    /// Test Ruby compression keeps module/class/method definitions and drops bodies.
+    #[test]
+    fn test_tree_sitter_ruby_compression() {
+        let source = r#"
+require "json"
+
+module Billing
+  class Invoice < ApplicationRecord
+    include Payable
+    belongs_to :customer
+
+    def initialize(total, currency: "USD")
+      @total = total
+      @currency = currency
+    end
+
+    def self.build(params)
+      new(params.fetch(:total))
+    end
+
+    private
+
+    def tax = @total * 0.2
+  end
+end
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::Ruby)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("require \"json\""));
+        assert!(result.contains("module Billing"));
+        assert!(result.contains("class Invoice < ApplicationRecord"));
+        assert!(result.contains("include Payable"));
+        assert!(result.contains("belongs_to :customer"));
+        assert!(result.contains("def initialize(total, currency: \"USD\"); end"));
+        assert!(result.contains("def self.build(params); end"));
+        assert!(result.contains("private"));
+        assert!(result.contains("def tax; end"));
+        assert!(!result.contains("@currency = currency"));
+        assert!(!result.contains("params.fetch"));
+    }
+}
+
+#[cfg(feature = "compression-php")]
+mod php_tests {
+    use super::*;
+
+    /// Test PHP compression keeps namespace, class and function signatures and drops bodies.
+    #[test]
+    fn test_tree_sitter_php_compression() {
+        let source = r#"<?php
+namespace App\Http\Controllers;
+
+use App\Models\User;
+
+#[Route('/users')]
+final class UserController extends Controller implements HasMiddleware
+{
+    use AuthorizesRequests;
+
+    public const PER_PAGE = 20;
+
+    public function index(Request $request): JsonResponse
+    {
+        $users = User::query()->paginate(self::PER_PAGE);
+        return response()->json($users);
+    }
+}
+
+function helper(int $x): int
+{
+    return $x * 2;
+}
+
+echo helper(2);
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::Php)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("namespace App\\Http\\Controllers;"));
+        assert!(result.contains("use App\\Models\\User;"));
+        assert!(result.contains(
+            "#[Route('/users')]\nfinal class UserController extends Controller implements HasMiddleware"
+        ));
+        assert!(result.contains("use AuthorizesRequests;"));
+        assert!(result.contains("public const PER_PAGE = 20;"));
+        assert!(
+            result.contains("public function index(Request $request): JsonResponse { /* ... */ }")
+        );
+        assert!(result.contains("function helper(int $x): int { /* ... */ }"));
+        assert!(!result.contains("paginate"));
+        assert!(!result.contains("echo"));
+    }
+}
+
+#[cfg(feature = "compression-shell")]
+mod shell_tests {
+    use super::*;
+
+    /// Test shell compression keeps function definitions and top-level commands.
+    #[test]
+    fn test_tree_sitter_shell_compression() {
+        let source = r#"#!/usr/bin/env bash
+set -euo pipefail
+source "$(dirname "$0")/lib.sh"
+export CARGO_TERM_COLOR=always
+
+build() {
+  cargo build --release
+  strip target/release/app
+}
+
+if [[ -n "${CI:-}" ]]; then
+  echo "running in CI"
+fi
+
+build && deploy prod
+"#;
+
+        let result = TreeSitterCompressor::default()
+            .compress(source, Language::Shell)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("#!/usr/bin/env bash"));
+        assert!(result.contains("set -euo pipefail"));
+        assert!(result.contains("source \"$(dirname \"$0\")/lib.sh\""));
+        assert!(result.contains("export CARGO_TERM_COLOR=always"));
+        assert!(result.contains("build() { # ...\n}"));
+        assert!(result.contains("build && deploy prod"));
+        assert!(!result.contains("cargo build --release"));
+        assert!(!result.contains("running in CI"));
+    }
+}
+
+#[cfg(feature = "compression-rust")]
+mod query_tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn queries(language: &str, path: &str) -> HashMap<String, PathBuf> {
+        HashMap::from([(language.to_string(), PathBuf::from(path))])
+    }
+
+    /// Test that a configured query file replaces the built-in Rust extractor.
+    #[test]
+    fn test_query_file_overrides_builtin_extractor() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp.path().join("queries")).unwrap();
+        std::fs::write(
+            temp.path().join("queries/rust.scm"),
+            r##"
+            (line_comment) @keep
+            (attribute_item) @keep
+            (struct_item) @keep
+            (function_item body: (block) @elide) @keep
+            ((attribute_item) @_cfg @drop . (mod_item) @drop
+              (#eq? @_cfg "#[cfg(test)]"))
+            "##,
+        )
+        .unwrap();
+
+        let source = r#"
+/// A user record.
+#[derive(Debug, Clone)]
+pub struct User {
+    pub name: String,
+}
+
+/// Greets the user.
+pub fn greet(user: &User) -> String {
+    format!("Hello, {}", user.name)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_greet() {
+        assert!(true);
+    }
+}
+"#;
+
+        let compressor =
+            TreeSitterCompressor::from_config(&queries("rust", "queries/rust.scm"), temp.path())
+                .expect("query should load");
+        let result = compressor
+            .compress(source, Language::Rust)
+            .expect("Compression should succeed");
+
+        assert!(result.contains("/// A user record.\n#[derive(Debug, Clone)]\npub struct User"));
+        assert!(result.contains("/// Greets the user.\npub fn greet(user: &User) -> String ..."));
+        assert!(!result.contains("format!"));
+        assert!(!result.contains("test_greet"));
+
+        // Languages without a query keep their built-in extractor
+        let builtin = TreeSitterCompressor::default()
+            .compress(source, Language::Rust)
+            .unwrap();
+        assert!(builtin.contains("{ /* ... */ }"));
+    }
+
+    /// Test that bad query configuration is reported with the file and reason.
+    #[test]
+    fn test_invalid_query_configuration_errors() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("bad.scm"),
+            "(function_item body: (nope) @elide) @keep",
+        )
+        .unwrap();
+
+        let err = TreeSitterCompressor::from_config(&queries("rust", "bad.scm"), temp.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("bad.scm"), "{err}");
+        assert!(err.contains("nope"), "{err}");
+
+        let err = TreeSitterCompressor::from_config(&queries("rust", "missing.scm"), temp.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing.scm"), "{err}");
+
+        let err = TreeSitterCompressor::from_config(&queries("cobol", "bad.scm"), temp.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("cobol"), "{err}");
+    }
+}
+
+mod truncate_tests {
+    use super::*;
+
+    /// Test that long TypeScript bodies keep their first lines and closing brace.
+    #[cfg(feature = "compression-typescript")]
+    #[test]
+    fn test_truncate_typescript_bodies() {
+        let source = r#"import { readFile } from "fs";
+
+export async function load(path: string): Promise<Config> {
+    const raw = await readFile(path, "utf8");
+    if (!raw) {
+        throw new Error("empty config");
+    }
+    const parsed = JSON.parse(raw);
+    validate(parsed);
+    return parsed;
+}
+
+function short(a: number) {
+    return a * 2;
+}
+
+class Store {
+    get(key: string) {
+        const value = this.map.get(key);
+        if (value === undefined) {
+            throw new Error(key);
+        }
+        return value;
+    }
+}
+"#;
+
+        let result = TruncatingCompressor::new(3)
+            .compress(source, Language::TypeScript)
+            .expect("Truncation should succeed");
+
+        assert_eq!(
+            result,
+            r#"import { readFile } from "fs";
+
+export async function load(path: string): Promise<Config> {
+    const raw = await readFile(path, "utf8");
+    if (!raw) {
+        throw new Error("empty config");
+    ...
+}
+
+function short(a: number) {
+    return a * 2;
+}
+
+class Store {
+    get(key: string) {
+        const value = this.map.get(key);
+        if (value === undefined) {
+            throw new Error(key);
+        ...
+    }
+}
+"#
+        );
+    }
+
+    /// Test that Python bodies, which have no closing line, end at the marker.
+    #[cfg(feature = "compression-python")]
+    #[test]
+    fn test_truncate_python_bodies() {
+        let source = r#"def parse(text):
+    try:
+        value = int(text)
+    except ValueError:
+        raise ConfigError(text)
+    return value
+
+x = 1
+"#;
+
+        let result = TruncatingCompressor::new(2)
+            .compress(source, Language::Python)
+            .expect("Truncation should succeed");
+
+        assert_eq!(
+            result,
+            "def parse(text):\n    try:\n        value = int(text)\n    ...\n\nx = 1\n"
+        );
+    }
+
+    /// Test that Ruby methods keep their `end`.
+    #[cfg(feature = "compression-ruby")]
+    #[test]
+    fn test_truncate_ruby_methods() {
+        let source = r#"class Cart
+  def total
+    items.sum do |item|
+      item.price * item.quantity
+    end
+  end
+end
+"#;
+
+        let result = TruncatingCompressor::new(1)
+            .compress(source, Language::Ruby)
+            .expect("Truncation should succeed");
+
+        assert_eq!(
+            result,
+            "class Cart\n  def total\n    items.sum do |item|\n    ...\n  end\nend\n"
+        );
+    }
+
+    /// Test that languages without a grammar report an error so callers fall back.
+    #[test]
+    fn test_truncate_unknown_language_errors() {
+        assert!(
+            TruncatingCompressor::default()
+                .compress("some text", Language::Unknown)
+                .is_err()
+        );
+    }
+}
+
 mod whitespace_tests {
     use super::*;
 
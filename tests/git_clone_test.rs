@@ -0,0 +1,200 @@
+// Copyright (c) 2025-2026 the ruley contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for cloning remote repositories.
+//!
+//! A local bare repository served over `file://` stands in for the remote:
+//! `main` has two commits (the first tagged `v1`), and `feature` branches
+//! off the first commit.
+
+#[allow(dead_code)]
+mod common;
+
+use git2::{Oid, Repository, RepositoryInitOptions, Signature};
+use ruley::packer::git::{CloneOptions, clone_repo};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// The bare repository fixture and the commits it contains.
+struct Fixture {
+    _dir: TempDir,
+    url: String,
+    first: Oid,
+    second: Oid,
+}
+
+/// Commit `files` on top of HEAD of `repo` and move `branch` to the commit.
+fn commit(repo: &Repository, branch: &str, parent: Option<Oid>, files: &[(&str, &str)]) -> Oid {
+    let workdir = repo.workdir().expect("fixture repo has a workdir");
+    for (path, content) in files {
+        std::fs::write(workdir.join(path), content).unwrap();
+    }
+
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now("Test", "test@example.com").unwrap();
+    let parents: Vec<_> = parent
+        .map(|oid| repo.find_commit(oid).unwrap())
+        .into_iter()
+        .collect();
+    let parents: Vec<_> = parents.iter().collect();
+    repo.commit(
+        Some(&format!("refs/heads/{}", branch)),
+        &signature,
+        &signature,
+        "commit",
+        &tree,
+        &parents,
+    )
+    .unwrap()
+}
+
+fn create_fixture() -> Fixture {
+    let dir = tempfile::tempdir().unwrap();
+    let work_path = dir.path().join("work");
+    let bare_path = dir.path().join("origin.git");
+
+    let mut opts = RepositoryInitOptions::new();
+    opts.initial_head("main");
+    let work = Repository::init_opts(&work_path, &opts).unwrap();
+
+    let first = commit(&work, "main", None, &[("lib.rs", "pub fn v1() {}\n")]);
+    work.tag_lightweight("v1", &work.find_object(first, None).unwrap(), false)
+        .unwrap();
+    let second = commit(
+        &work,
+        "main",
+        Some(first),
+        &[("lib.rs", "pub fn v2() {}\n")],
+    );
+    work.branch("feature", &work.find_commit(first).unwrap(), false)
+        .unwrap();
+
+    let mut bare_opts = RepositoryInitOptions::new();
+    bare_opts.bare(true).initial_head("main");
+    Repository::init_opts(&bare_path, &bare_opts).unwrap();
+
+    let mut remote = work.remote("origin", bare_path.to_str().unwrap()).unwrap();
+    remote
+        .push(
+            &["refs/heads/main", "refs/heads/feature", "refs/tags/v1"],
+            None,
+        )
+        .unwrap();
+
+    Fixture {
+        url: format!("file://{}", bare_path.display()),
+        _dir: dir,
+        first,
+        second,
+    }
+}
+
+fn head_commit(path: &Path) -> Oid {
+    Repository::open(path)
+        .unwrap()
+        .head()
+        .unwrap()
+        .peel_to_commit()
+        .unwrap()
+        .id()
+}
+
+async fn clone_with_ref(fixture: &Fixture, git_ref: Option<&str>) -> (TempDir, std::path::PathBuf) {
+    let dest_dir = tempfile::tempdir().unwrap();
+    let dest = dest_dir.path().join("repo");
+    let options = CloneOptions {
+        git_ref: git_ref.map(str::to_string),
+        depth: 1,
+    };
+    clone_repo(&fixture.url, &dest, &options)
+        .await
+        .expect("clone should succeed");
+    (dest_dir, dest)
+}
+
+#[tokio::test]
+async fn test_clone_default_branch() {
+    let fixture = create_fixture();
+    let (_dir, dest) = clone_with_ref(&fixture, None).await;
+
+    assert_eq!(head_commit(&dest), fixture.second);
+    assert_eq!(
+        std::fs::read_to_string(dest.join("lib.rs")).unwrap(),
+        "pub fn v2() {}\n"
+    );
+}
+
+#[tokio::test]
+async fn test_clone_branch_and_tag() {
+    let fixture = create_fixture();
+
+    let (_dir, dest) = clone_with_ref(&fixture, Some("feature")).await;
+    assert_eq!(head_commit(&dest), fixture.first);
+
+    let (_dir, dest) = clone_with_ref(&fixture, Some("v1")).await;
+    assert_eq!(head_commit(&dest), fixture.first);
+    assert_eq!(
+        std::fs::read_to_string(dest.join("lib.rs")).unwrap(),
+        "pub fn v1() {}\n"
+    );
+}
+
+#[tokio::test]
+async fn test_clone_commit_sha() {
+    let fixture = create_fixture();
+    let short_sha = fixture.first.to_string()[..10].to_string();
+
+    let (_dir, dest) = clone_with_ref(&fixture, Some(&short_sha)).await;
+
+    assert_eq!(head_commit(&dest), fixture.first);
+}
+
+#[tokio::test]
+async fn test_clone_unknown_ref_fails() {
+    let fixture = create_fixture();
+    let dest_dir = tempfile::tempdir().unwrap();
+    let options = CloneOptions {
+        git_ref: Some("does-not-exist".to_string()),
+        depth: 1,
+    };
+
+    let Err(err) = clone_repo(&fixture.url, &dest_dir.path().join("repo"), &options).await else {
+        panic!("unknown ref should fail");
+    };
+
+    assert!(err.to_string().contains("does-not-exist"), "{err}");
+}
+
+#[test]
+fn test_cli_dry_run_on_remote_url() {
+    let fixture = create_fixture();
+
+    let output = common::run_cli_with_config(&fixture.url.clone().into(), &["--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(
+        output.status.success(),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        stdout.contains("Dry Run") || stdout.contains("No LLM calls"),
+        "Expected dry-run output: {stdout}"
+    );
+}
+
+#[test]
+fn test_cli_remote_url_requires_output_dir() {
+    let fixture = create_fixture();
+
+    let output = common::run_cli_with_config(&fixture.url.clone().into(), &["--no-confirm"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("--output-dir"), "stderr: {stderr}");
+}
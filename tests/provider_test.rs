@@ -3,7 +3,8 @@
 
 //! Integration tests for LLM provider implementations.
 //!
-//! Uses mockito HTTP mocking to test Ollama and OpenRouter providers
+//! Uses mockito HTTP mocking to test Ollama, OpenRouter, xAI, Groq, Gemini and
+//! OpenAI-compatible providers
 //! without requiring actual servers or API keys.
 
 #[cfg(feature = "ollama")]
@@ -85,6 +86,61 @@ async fn test_ollama_completion_success() {
         completion_mock.assert_async().await;
     }
 
+    /// Test Ollama streamed completion via the native NDJSON `/api/chat` endpoint.
+    #[tokio::test]
+    async fn test_ollama_streaming_completion() {
+        let mut server = mockito::Server::new_async().await;
+
+        let tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"models":[{"name":"llama3.1:70b","size":1000}]}"#)
+            .create_async()
+            .await;
+
+        let chat_mock = server
+            .mock("POST", "/api/chat")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"model":"llama3.1:70b","stream":true}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/x-ndjson")
+            .with_body(concat!(
+                r#"{"message":{"role":"assistant","content":"Hello"},"done":false}"#,
+                "\n",
+                r#"{"message":{"role":"assistant","content":" from Ollama!"},"done":false}"#,
+                "\n",
+                r#"{"message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":10,"eval_count":4}"#,
+                "\n",
+            ))
+            .create_async()
+            .await;
+
+        let provider = OllamaProvider::new(server.url(), "llama3.1:70b".to_string()).unwrap();
+
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }];
+        let deltas = std::sync::Mutex::new(Vec::new());
+
+        let response = provider
+            .complete_stream(&messages, &CompletionOptions::default(), &|delta| {
+                deltas.lock().unwrap().push(delta.to_string())
+            })
+            .await
+            .expect("Streamed completion should succeed");
+
+        assert_eq!(response.content, "Hello from Ollama!");
+        assert_eq!(response.prompt_tokens, 10);
+        assert_eq!(response.completion_tokens, 4);
+        assert_eq!(*deltas.lock().unwrap(), vec!["Hello", " from Ollama!"]);
+
+        tags_mock.assert_async().await;
+        chat_mock.assert_async().await;
+    }
+
     /// Test Ollama model not found returns descriptive error.
     #[tokio::test]
     async fn test_ollama_model_not_found() {
@@ -542,3 +598,579 @@ fn test_openrouter_request_payload_namespace() {
         );
     }
 }
+
+#[cfg(feature = "xai")]
+mod xai_tests {
+    use ruley::llm::provider::{CompletionOptions, LLMProvider, Message};
+    use ruley::llm::providers::xai::XaiProvider;
+    use ruley::utils::error::RuleyError;
+
+    /// Test xAI successful completion via mock server.
+    #[tokio::test]
+    async fn test_xai_completion_success() {
+        let mut server = mockito::Server::new_async().await;
+
+        let completion_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer test-key")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"model":"grok-4"}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "choices": [{"message": {"content": "Hello from Grok!"}}],
+                    "usage": {"prompt_tokens": 12, "completion_tokens": 4}
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = XaiProvider::new("test-key".to_string(), "grok-4".to_string())
+            .unwrap()
+            .with_base_url(format!("{}/v1", server.url()));
+
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }];
+
+        let response = provider
+            .complete(&messages, &CompletionOptions::default())
+            .await
+            .expect("Completion should succeed");
+
+        assert_eq!(response.content, "Hello from Grok!");
+        assert_eq!(response.prompt_tokens, 12);
+        assert_eq!(response.completion_tokens, 4);
+
+        completion_mock.assert_async().await;
+    }
+
+    /// Test xAI rate limiting (HTTP 429) returns RateLimited with retry-after.
+    #[tokio::test]
+    async fn test_xai_rate_limited() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(429)
+            .with_header("retry-after", "7")
+            .create_async()
+            .await;
+
+        let provider = XaiProvider::new("test-key".to_string(), "grok-4".to_string())
+            .unwrap()
+            .with_base_url(format!("{}/v1", server.url()));
+
+        let result = provider.complete(&[], &CompletionOptions::default()).await;
+
+        match result {
+            Err(RuleyError::RateLimited {
+                provider,
+                retry_after,
+            }) => {
+                assert_eq!(provider, "xai");
+                assert_eq!(retry_after, Some(std::time::Duration::from_secs(7)));
+            }
+            other => panic!(
+                "Expected RateLimited error, got {:?}",
+                other.map(|r| r.content)
+            ),
+        }
+    }
+
+    /// Test xAI flat error shape is surfaced in the provider error.
+    #[tokio::test]
+    async fn test_xai_flat_error_response() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(400)
+            .with_body(r#"{"code":"Client specified an invalid argument","error":"Incorrect API key provided"}"#)
+            .create_async()
+            .await;
+
+        let provider = XaiProvider::new("bad-key".to_string(), "grok-4".to_string())
+            .unwrap()
+            .with_base_url(format!("{}/v1", server.url()));
+
+        let err = provider
+            .complete(&[], &CompletionOptions::default())
+            .await
+            .expect_err("Completion should fail");
+
+        assert!(
+            err.to_string().contains("Incorrect API key"),
+            "Error should include provider message: {}",
+            err
+        );
+    }
+
+    /// Test xAI pricing is non-zero.
+    #[test]
+    fn test_xai_pricing() {
+        let provider = XaiProvider::new("test-key".to_string(), "grok-4".to_string()).unwrap();
+        let pricing = provider.pricing();
+        assert!(pricing.input_per_1k > 0.0);
+        assert!(pricing.output_per_1k > pricing.input_per_1k);
+    }
+}
+
+#[cfg(feature = "groq")]
+mod groq_tests {
+    use ruley::llm::provider::{CompletionOptions, LLMProvider, Message};
+    use ruley::llm::providers::groq::GroqProvider;
+
+    /// Test Groq successful completion via mock server.
+    #[tokio::test]
+    async fn test_groq_completion_success() {
+        let mut server = mockito::Server::new_async().await;
+
+        let completion_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer test-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "choices": [{"message": {"content": "Hello from Groq!"}}],
+                    "usage": {"prompt_tokens": 20, "completion_tokens": 8}
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = GroqProvider::new(
+            "test-key".to_string(),
+            "llama-3.3-70b-versatile".to_string(),
+        )
+        .unwrap()
+        .with_base_url(format!("{}/v1", server.url()));
+
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }];
+
+        let response = provider
+            .complete(&messages, &CompletionOptions::default())
+            .await
+            .expect("Completion should succeed");
+
+        assert_eq!(response.content, "Hello from Groq!");
+        assert_eq!(response.prompt_tokens, 20);
+        assert_eq!(response.completion_tokens, 8);
+
+        completion_mock.assert_async().await;
+    }
+
+    /// Test Groq structured error is parsed into a provider error.
+    #[tokio::test]
+    async fn test_groq_error_response() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(404)
+            .with_body(
+                r#"{"error":{"message":"The model `nope` does not exist","type":"invalid_request_error","code":"model_not_found"}}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = GroqProvider::new("test-key".to_string(), "nope".to_string())
+            .unwrap()
+            .with_base_url(format!("{}/v1", server.url()));
+
+        let err = provider
+            .complete(&[], &CompletionOptions::default())
+            .await
+            .expect_err("Completion should fail");
+
+        let display = err.to_string();
+        assert!(
+            display.contains("groq"),
+            "Error should name provider: {}",
+            display
+        );
+        assert!(
+            display.contains("does not exist"),
+            "Error should include message: {}",
+            display
+        );
+    }
+
+    /// Test Groq empty content is rejected.
+    #[tokio::test]
+    async fn test_groq_empty_content() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices": [{"message": {"content": ""}}]}"#)
+            .create_async()
+            .await;
+
+        let provider = GroqProvider::new(
+            "test-key".to_string(),
+            "llama-3.3-70b-versatile".to_string(),
+        )
+        .unwrap()
+        .with_base_url(format!("{}/v1", server.url()));
+
+        let result = provider.complete(&[], &CompletionOptions::default()).await;
+
+        assert!(result.is_err(), "Empty content should be an error");
+    }
+}
+
+#[cfg(feature = "gemini")]
+mod gemini_tests {
+    use ruley::llm::provider::{CompletionOptions, LLMProvider, Message};
+    use ruley::llm::providers::gemini::GeminiProvider;
+
+    /// Test Gemini successful completion, including thinking tokens in output usage.
+    #[tokio::test]
+    async fn test_gemini_completion_success() {
+        let mut server = mockito::Server::new_async().await;
+
+        let completion_mock = server
+            .mock("POST", "/v1beta/models/gemini-2.5-pro:generateContent")
+            .match_header("x-goog-api-key", "test-key")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"systemInstruction":{"parts":[{"text":"Be brief"}]}}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "candidates": [{
+                        "content": {"role": "model", "parts": [{"text": "Hello "}, {"text": "from Gemini!"}]},
+                        "finishReason": "STOP"
+                    }],
+                    "usageMetadata": {
+                        "promptTokenCount": 30,
+                        "candidatesTokenCount": 6,
+                        "thoughtsTokenCount": 10,
+                        "totalTokenCount": 46
+                    }
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = GeminiProvider::new("test-key".to_string(), "gemini-2.5-pro".to_string())
+            .unwrap()
+            .with_base_url(server.url());
+
+        let messages = vec![
+            Message {
+                role: "system".to_string(),
+                content: "Be brief".to_string(),
+            },
+            Message {
+                role: "user".to_string(),
+                content: "Hello".to_string(),
+            },
+        ];
+
+        let response = provider
+            .complete(&messages, &CompletionOptions::default())
+            .await
+            .expect("Completion should succeed");
+
+        assert_eq!(response.content, "Hello from Gemini!");
+        assert_eq!(response.prompt_tokens, 30);
+        assert_eq!(response.completion_tokens, 16);
+
+        completion_mock.assert_async().await;
+    }
+
+    /// Test Gemini error status is surfaced in the provider error.
+    #[tokio::test]
+    async fn test_gemini_error_response() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/v1beta/models/gemini-2.5-pro:generateContent")
+            .with_status(400)
+            .with_body(
+                r#"{"error":{"code":400,"message":"API key not valid.","status":"INVALID_ARGUMENT"}}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = GeminiProvider::new("bad-key".to_string(), "gemini-2.5-pro".to_string())
+            .unwrap()
+            .with_base_url(server.url());
+
+        let err = provider
+            .complete(&[], &CompletionOptions::default())
+            .await
+            .expect_err("Completion should fail");
+
+        let display = err.to_string();
+        assert!(
+            display.contains("INVALID_ARGUMENT") && display.contains("API key not valid"),
+            "Error should include status and message: {}",
+            display
+        );
+    }
+
+    /// Test Gemini blocked response (no content) reports the finish reason.
+    #[tokio::test]
+    async fn test_gemini_blocked_response() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/v1beta/models/gemini-2.5-pro:generateContent")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"candidates": [{"finishReason": "SAFETY"}]}"#)
+            .create_async()
+            .await;
+
+        let provider = GeminiProvider::new("test-key".to_string(), "gemini-2.5-pro".to_string())
+            .unwrap()
+            .with_base_url(server.url());
+
+        let err = provider
+            .complete(&[], &CompletionOptions::default())
+            .await
+            .expect_err("Blocked response should fail");
+
+        assert!(
+            err.to_string().contains("SAFETY"),
+            "Error should include finish reason: {}",
+            err
+        );
+    }
+}
+
+#[cfg(feature = "openai")]
+mod openai_compatible_tests {
+    use ruley::llm::provider::{CompletionOptions, LLMProvider, Message, Pricing};
+    use ruley::llm::providers::openai_compatible::OpenAICompatibleProvider;
+    use ruley::utils::error::RuleyError;
+    use std::collections::HashMap;
+
+    fn user_message() -> Vec<Message> {
+        vec![Message {
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+        }]
+    }
+
+    /// Test completion against a custom base URL with API key and extra headers.
+    #[tokio::test]
+    async fn test_openai_compatible_sends_auth_and_custom_headers() {
+        let mut server = mockito::Server::new_async().await;
+
+        let completion_mock = server
+            .mock("POST", "/gateway/v1/chat/completions")
+            .match_header("authorization", "Bearer gateway-key")
+            .match_header("x-gateway-team", "platform")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"model":"qwen2.5-coder"}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "choices": [{"message": {"content": "Hello from vLLM!"}}],
+                    "usage": {"prompt_tokens": 9, "completion_tokens": 5}
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let headers = HashMap::from([("X-Gateway-Team".to_string(), "platform".to_string())]);
+        let provider = OpenAICompatibleProvider::new(
+            format!("{}/gateway/v1", server.url()),
+            "qwen2.5-coder".to_string(),
+        )
+        .unwrap()
+        .with_api_key("gateway-key")
+        .with_headers(&headers)
+        .unwrap();
+
+        let response = provider
+            .complete(&user_message(), &CompletionOptions::default())
+            .await
+            .expect("Completion should succeed");
+
+        assert_eq!(response.content, "Hello from vLLM!");
+        assert_eq!(response.prompt_tokens, 9);
+        assert_eq!(response.completion_tokens, 5);
+
+        completion_mock.assert_async().await;
+    }
+
+    /// Test that no Authorization header is sent when no API key is configured,
+    /// and that missing usage data (common on local servers) is tolerated.
+    #[tokio::test]
+    async fn test_openai_compatible_without_api_key_or_usage() {
+        let mut server = mockito::Server::new_async().await;
+
+        let completion_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices": [{"message": {"content": "Local answer"}}]}"#)
+            .create_async()
+            .await;
+
+        let provider = OpenAICompatibleProvider::new(
+            format!("{}/v1", server.url()),
+            "local-model".to_string(),
+        )
+        .unwrap();
+
+        let response = provider
+            .complete(&user_message(), &CompletionOptions::default())
+            .await
+            .expect("Completion should succeed");
+
+        assert_eq!(response.content, "Local answer");
+        assert_eq!(response.prompt_tokens, 0);
+        assert_eq!(response.completion_tokens, 0);
+
+        completion_mock.assert_async().await;
+    }
+
+    /// Test that errors are labeled with the openai-compatible provider name.
+    #[tokio::test]
+    async fn test_openai_compatible_error_response() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"error": {"type": "NotFoundError", "message": "The model `missing` does not exist."}}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider =
+            OpenAICompatibleProvider::new(format!("{}/v1", server.url()), "missing".to_string())
+                .unwrap();
+
+        let result = provider
+            .complete(&user_message(), &CompletionOptions::default())
+            .await;
+
+        match result {
+            Err(RuleyError::Provider { provider, message }) => {
+                assert_eq!(provider, "openai-compatible");
+                assert!(message.contains("NotFoundError"));
+                assert!(message.contains("does not exist"));
+            }
+            other => panic!(
+                "Expected Provider error, got {:?}",
+                other.map(|r| r.content)
+            ),
+        }
+    }
+
+    /// Test that custom pricing overrides the free default.
+    #[test]
+    fn test_openai_compatible_custom_pricing() {
+        let provider = OpenAICompatibleProvider::new(
+            "http://localhost:8000/v1".to_string(),
+            "local-model".to_string(),
+        )
+        .unwrap()
+        .with_pricing(Pricing {
+            input_per_1k: 0.0005,
+            output_per_1k: 0.0015,
+        });
+
+        let pricing = provider.pricing();
+        assert_eq!(pricing.input_per_1k, 0.0005);
+        assert_eq!(pricing.output_per_1k, 0.0015);
+    }
+
+    /// Test a streamed completion assembled from SSE chunks.
+    #[tokio::test]
+    async fn test_openai_compatible_streaming_completion() {
+        let mut server = mockito::Server::new_async().await;
+
+        let completion_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"stream":true,"stream_options":{"include_usage":true}}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(concat!(
+                "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"Streamed\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\" answer\"},\"finish_reason\":\"stop\"}]}\n\n",
+                "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":7,\"completion_tokens\":2}}\n\n",
+                "data: [DONE]\n\n",
+            ))
+            .create_async()
+            .await;
+
+        let provider = OpenAICompatibleProvider::new(
+            format!("{}/v1", server.url()),
+            "local-model".to_string(),
+        )
+        .unwrap();
+        let deltas = std::sync::Mutex::new(Vec::new());
+
+        let response = provider
+            .complete_stream(&user_message(), &CompletionOptions::default(), &|delta| {
+                deltas.lock().unwrap().push(delta.to_string())
+            })
+            .await
+            .expect("Streamed completion should succeed");
+
+        assert_eq!(response.content, "Streamed answer");
+        assert_eq!(response.prompt_tokens, 7);
+        assert_eq!(response.completion_tokens, 2);
+        assert_eq!(*deltas.lock().unwrap(), vec!["Streamed", " answer"]);
+
+        completion_mock.assert_async().await;
+    }
+
+    /// Test that a stream cut off mid-response surfaces as a retryable network error.
+    #[tokio::test]
+    async fn test_openai_compatible_stream_cut_off() {
+        let mut server = mockito::Server::new_async().await;
+
+        let completion_mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body("data: {\"choices\":[{\"delta\":{\"content\":\"Partial\"}}]}\n\n")
+            .create_async()
+            .await;
+
+        let provider = OpenAICompatibleProvider::new(
+            format!("{}/v1", server.url()),
+            "local-model".to_string(),
+        )
+        .unwrap();
+
+        let result = provider
+            .complete_stream(&user_message(), &CompletionOptions::default(), &|_| {})
+            .await;
+
+        assert!(
+            matches!(result, Err(RuleyError::NetworkError { .. })),
+            "Expected NetworkError, got {:?}",
+            result.map(|r| r.content)
+        );
+
+        completion_mock.assert_async().await;
+    }
+}